@@ -5,6 +5,7 @@ pub mod output;
 pub mod parents;
 
 use crate::ast::elements::{ArgModifier, DebugElements, ElIndex, Element, ElementInfo, Elements};
+use crate::ast::output::Target;
 use crate::Token;
 use std::fmt;
 
@@ -34,11 +35,23 @@ pub struct Ast {
     //first element is always root. Real elements start at index 1
     pub elements: Elements,
     pub output: String,
+    //the language of the output
+    pub target: Target,
     pub output_stack: Vec<ElIndex>,
     pub premain_output: String,
+    //byte index in output where each Element's output starts, in output order, so invalid output can be traced back to an Element
+    pub output_map: Vec<(usize, ElIndex)>,
+    //the same for premain_output, which is merged into output_map once premain_output is prepended to output
+    pub premain_output_map: Vec<(usize, ElIndex)>,
+    //line (from 1) and column (from 0) in output where each Element's output starts, and the origin Token of that Element, in output order
+    pub source_map: Vec<(usize, usize, Token)>,
     //note: parents are only used for building, ignored output.
     //becuse of that, split outputting to be less confusing?
     pub parents: Vec<ElIndex>,
+    //origin Token of each Element, same order as elements. Internal elements added by init() have an empty Token
+    pub element_tokens: Vec<Token>,
+    //the Token currently being parsed, which is recorded against any Elements appended while parsing it
+    pub current_token: Token,
     pub debug: bool,
     pub logs: Logs,
     pub debug_compiler_history: Vec<String>,
@@ -46,12 +59,20 @@ pub struct Ast {
 
 impl Default for Ast {
     fn default() -> Self {
+        let elements = init();
+        let element_tokens = vec![("".to_string(), 0, 0, 0); elements.len()];
         Ast {
-            elements: init(),
+            elements,
             output: "".to_string(),
+            target: Target::default(),
             output_stack: vec![],
             premain_output: "".to_string(),
+            output_map: vec![],
+            premain_output_map: vec![],
+            source_map: vec![],
             parents: vec![0], // get current indent from length of parents
+            element_tokens,
+            current_token: ("".to_string(), 0, 0, 0),
             debug: false,
             logs: vec![],
             debug_compiler_history: vec![],
@@ -186,10 +186,15 @@ pub fn get_struct_index_by_name(ast: &Ast, name: &String) -> Option<usize> {
     })
 }
 
-/// Get the index of the Arg based on its name
+/// Get the index of the Arg based on its name.
+/// Only Args of a function containing the current parent are found, so each function has its own Args
 pub fn get_arg_index_by_name(ast: &Ast, name: &String) -> Option<usize> {
+    let scopes = match ast.parents.last() {
+        Some(&parent_ref) => [vec![parent_ref], get_ancestor_refs(ast, parent_ref)].concat(),
+        None => vec![],
+    };
     ast.elements.iter().position(|(elinfo, _)| match elinfo {
-        ElementInfo::Arg(n, _, _, _) => n == name,
+        ElementInfo::Arg(n, scope, _, _) => n == name && scopes.contains(scope),
         _ => false,
     })
 }
@@ -204,11 +209,22 @@ pub fn get_inbuilt_type_index_by_name(ast: &Ast, name: &String) -> Option<usize>
 
 /// Get the index of the Constant based on its name
 pub fn get_constant_index_by_name(ast: &Ast, name: &String) -> Option<usize> {
-    ast.elements.iter().position(|(elinfo, _)| match elinfo {
-        ElementInfo::Constant(n, _t) => n == name,
-        ElementInfo::ConstantRef(n, _t, _refname) => n == name,
-        _ => false,
-    })
+    ast.elements
+        .iter()
+        .enumerate()
+        .position(|(el_index, (elinfo, _))| match elinfo {
+            ElementInfo::Constant(n, _t) => n == name,
+            // a reference to an Arg isn't a constant, since the Arg is only in scope of its own function
+            ElementInfo::ConstantRef(n, _t, _refname) => n == name && !is_arg_ref(ast, el_index),
+            _ => false,
+        })
+}
+
+fn is_arg_ref(ast: &Ast, el_index: usize) -> bool {
+    matches!(
+        get_definition_index_of_element(ast, el_index).map(|index| &ast.elements[index].0),
+        Some(ElementInfo::Arg(_, _, _, _))
+    )
 }
 
 /// Get the Constant based on its name
@@ -264,6 +280,70 @@ pub fn get_inbuilt_function_by_name(ast: &Ast, name: &String) -> Option<ElementI
     None
 }
 
+/// Get the index of the Element which defines what this Element refers to,
+/// e.g. the Constant of a ConstantRef, or the FunctionDef of a FunctionCall. Definitions refer to themselves
+pub fn get_definition_index_of_element(ast: &Ast, el_index: usize) -> Option<usize> {
+    match &ast.elements[el_index].0 {
+        ElementInfo::ConstantRef(_, _, refname) => {
+            get_definition_index_by_name(ast, refname, el_index)
+        }
+        ElementInfo::FunctionCall(name, _, _) => get_definition_index_by_name(ast, name, el_index),
+        ElementInfo::Constant(_, _) => Some(el_index),
+        ElementInfo::FunctionDef(_, _, _, _) => Some(el_index),
+        ElementInfo::Arg(_, _, _, _) => Some(el_index),
+        // explicitly listing other types rather than using _ to not overlook new types in future
+        ElementInfo::List(_) => None,
+        ElementInfo::CommentSingleLine(_) => None,
+        ElementInfo::Int(_) => None,
+        ElementInfo::Float(_) => None,
+        ElementInfo::String(_) => None,
+        ElementInfo::Bool(_) => None,
+        ElementInfo::Type(_) => None,
+        ElementInfo::Eol => None,
+        ElementInfo::Seol => None,
+        ElementInfo::Indent => None,
+        ElementInfo::Unused => None,
+        ElementInfo::Rust(_, _) => None,
+        ElementInfo::Struct(_, _, _) => None,
+        ElementInfo::StructEdit(_, _) => None,
+        ElementInfo::Assignment => None,
+        ElementInfo::InbuiltFunctionDef(_, _, _, _, _, _) => None,
+        ElementInfo::InbuiltFunctionCall(_, _, _) => None,
+        ElementInfo::FunctionDefWIP => None,
+        ElementInfo::Parens => None,
+        ElementInfo::LoopForRangeWIP => None,
+        ElementInfo::LoopForRange(_, _, _) => None,
+        ElementInfo::Println => None,
+        ElementInfo::If(_) => None,
+        ElementInfo::Root => None,
+    }
+}
+
+/// Get the index of the Constant, FunctionDef or Arg with this name.
+/// Args are only found if they belong to a function containing the element at from_index
+pub fn get_definition_index_by_name(ast: &Ast, name: &String, from_index: usize) -> Option<usize> {
+    let ancestors = get_ancestor_refs(ast, from_index);
+    ast.elements.iter().position(|(elinfo, _)| match elinfo {
+        ElementInfo::Constant(n, _) => n == name,
+        ElementInfo::FunctionDef(n, _, _, _) => n == name,
+        ElementInfo::Arg(n, scope, _, _) => n == name && ancestors.contains(scope),
+        _ => false,
+    })
+}
+
+/// Get the indexes of the parent, grandparent, etc, of an Element, up to Root
+pub fn get_ancestor_refs(ast: &Ast, from_index: usize) -> Vec<usize> {
+    let mut ancestors = vec![];
+    let mut child_ref = from_index;
+    while let Some(parent_ref) =
+        parents::get_current_parent_ref_from_element_children_search(ast, child_ref)
+    {
+        ancestors.push(parent_ref);
+        child_ref = parent_ref;
+    }
+    ancestors
+}
+
 /*
 pub fn _get_inbuilt_function_by_name_and_returntype(
     ast: &Ast,
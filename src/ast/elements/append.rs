@@ -15,6 +15,7 @@ use super::ArgModifier;
 pub fn append(ast: &mut Ast, element: Element) -> usize {
     // add element to list, and add to list of children of current parent where 0 = root
     ast.elements.push(element.clone());
+    ast.element_tokens.push(ast.current_token.clone());
     let new_items_index = ast.elements.len() - 1;
     let current_parent_ref = parents::get_current_parent_ref_from_parents(ast);
     ast.elements[current_parent_ref].1.push(new_items_index);
@@ -40,6 +41,7 @@ pub fn append_as_nth_child_of_elindex(
     position: usize,
 ) -> usize {
     ast.elements.push(element);
+    ast.element_tokens.push(ast.current_token.clone());
     let new_items_index = ast.elements.len() - 1;
 
     let parent = ast.elements[parent_index].clone();
@@ -667,6 +669,7 @@ pub fn function_ref_or_call(
         );
         let parens_ref = parents::get_current_parent_ref_from_parents(&compiler.ast);
         compiler.ast.elements[parens_ref] = new_constant_ref;
+        compiler.ast.element_tokens[parens_ref] = compiler.ast.current_token.clone();
 
         check_parens_parent(compiler, current_token, parens_ref);
         return seol_if_last_in_line(compiler);
@@ -832,12 +835,22 @@ pub fn function_ref_or_call(
                     .iter()
                     .position(|&r| r == fn_index_being_referenced)
                     .unwrap();
+                // on its own indented line, since outputs like Python rely on the indent
                 elements::append::append_as_nth_child_of_elindex(
+                    &mut compiler.ast,
+                    (ElementInfo::Indent, vec![]),
+                    parent_of_current_fn_ref,
+                    current_fn_position,
+                );
+                let duplicate_fn_ref = elements::append::append_as_nth_child_of_elindex(
                     &mut compiler.ast,
                     duplicate_fn.clone(),
                     parent_of_current_fn_ref,
                     current_fn_position + 1,
                 );
+                // the duplicate is defined wherever the original fn was
+                compiler.ast.element_tokens[duplicate_fn_ref] =
+                    compiler.ast.element_tokens[fn_index_being_referenced].clone();
 
                 switch_old_fn_ref_for_new(compiler, parens_ref, new_fn_name);
             }
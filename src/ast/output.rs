@@ -1,13 +1,183 @@
 /*! Handles formatting the AST into the final output code
+
+    The AST is walked the same way for every [Target], and the code for each Element comes from that Target's [Backend],
+    e.g. Rust by default, JavaScript with `--target js`, or Python with `--target py`.
+    WebAssembly, with `--target wat`, and C, with `--target c`, are output separately by [wat::set_output] and [c::set_output]
  */
+pub mod c;
+pub mod js;
+pub mod py;
+pub mod rust;
+pub mod wat;
 
 use crate::ast::elements;
 use crate::ast::elements::CodePosition;
+use crate::ast::elements::ElIndex;
 use crate::ast::elements::ElementInfo;
 use crate::ast::parents;
-use crate::formatting;
+use crate::formatting::{LineColumn, PositionMap};
 use crate::Ast;
 use crate::Compiler;
+use crate::Token;
+use serde::Serialize;
+use std::str::FromStr;
+
+/// The language which the AST is output as
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Target {
+    #[default]
+    Rust,
+    Js,
+    Py,
+    Wat,
+    C,
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rust" | "rs" => Ok(Target::Rust),
+            "js" | "javascript" => Ok(Target::Js),
+            "py" | "python" => Ok(Target::Py),
+            "wat" | "wasm" => Ok(Target::Wat),
+            "c" => Ok(Target::C),
+            _ => Err(format!(
+                "Unknown target '{}', expected one of: rust, js, py, wat, c",
+                s
+            )),
+        }
+    }
+}
+
+impl Target {
+    /// Wat and C aren't output by a Backend, see [wat::set_output] and [c::set_output]
+    pub fn backend(self) -> &'static dyn Backend {
+        match self {
+            Target::Rust => &rust::Rust,
+            Target::Js => &js::Js,
+            Target::Py => &py::Py,
+            Target::Wat => unreachable!("wat is output by wat::set_output, not a Backend"),
+            Target::C => unreachable!("c is output by c::set_output, not a Backend"),
+        }
+    }
+
+    /// The name of the file the output is saved as
+    pub fn output_filename(self) -> &'static str {
+        match self {
+            Target::Rust => "output.rs",
+            Target::Js => "output.js",
+            Target::Py => "output.py",
+            Target::Wat => "output.wat",
+            Target::C => "output.c",
+        }
+    }
+}
+
+/// The code for each kind of Element, in the language of a Target.
+/// Arguments are the already formatted output of any child Elements
+pub trait Backend {
+    /// Output once at the very start, before any structs
+    fn imports(&self) -> String {
+        "".to_string()
+    }
+    /// Wraps the output of the children of Root
+    fn main_start(&self) -> String;
+    fn main_end(&self) -> String;
+    /// The comment includes its leading "//"
+    fn comment(&self, comment: &str) -> String {
+        comment.to_string()
+    }
+    /// A name from the toylang code, e.g. of a constant, function or argument
+    fn identifier(&self, name: &str) -> String {
+        name.to_string()
+    }
+    /// Ints used as return expressions have " as i64" appended by the parser
+    fn int(&self, value: &str) -> String;
+    fn float(&self, value: &str) -> String {
+        value.to_string()
+    }
+    /// The value includes its quotes
+    fn string(&self, value: &str) -> String;
+    fn bool(&self, value: &str) -> String;
+    fn constant_ref(&self, name: &str) -> String;
+    /// Everything before the value, e.g. "let a: i64 = "
+    fn assignment(
+        &self,
+        name: &str,
+        returntype: &str,
+        is_struct: bool,
+        is_struct_edit: bool,
+    ) -> String;
+    fn list(&self, items: &[String], returntype: &str) -> String;
+    /// The format of an inbuilt function with placeholders for its args, e.g. "arg~1 + arg~2".
+    /// The default_format is the one defined for Rust in the InbuiltFunctionDef
+    fn inbuilt_function_format(&self, name: &str, returntype: &str, default_format: &str)
+        -> String;
+    fn inbuilt_function_def(&self, name: &str) -> String;
+    /// The start of the function, up to and including the opening bracket of the body
+    fn function_def(
+        &self,
+        name: &str,
+        argnames: &[String],
+        argtypes: &[String],
+        returntype: &str,
+    ) -> String;
+    fn function_def_close(&self, indent: &str) -> String {
+        format!("\r\n{}}}\r\n", indent)
+    }
+    /// The last expression of a function body
+    fn return_expression(&self, expression: &str) -> String {
+        expression.to_string()
+    }
+    /// An argument of a function call, based on the type of the argument in the FunctionDef
+    fn function_arg(&self, arg: &str, argtype: &str) -> String;
+    fn loop_for_range(&self, name: &str, from: usize, to: usize) -> String;
+    /// The body of the loop already ends with a line break
+    fn loop_close(&self, indent: &str) -> String {
+        format!("{}}}\r\n", indent)
+    }
+    fn seol(&self) -> String {
+        ";\r\n".to_string()
+    }
+    /// is_debug is true for structs and lists
+    fn println(&self, output: &str, is_debug: bool) -> String;
+    /// indent is for the true and false expressions, outdent for the closing brackets
+    fn if_expression(
+        &self,
+        condition: &str,
+        true_expression: &str,
+        false_expression: &str,
+        indent: &str,
+        outdent: &str,
+    ) -> String;
+    fn struct_new(&self, name: &str, args: &[String]) -> String;
+    /// The definition of the struct, output before the main function
+    fn premain_struct(&self, name: &str, keys: &[String], types: &[String]) -> String;
+}
+
+/// The JSON saved as output.rs.map, mapping ranges of the saved (pretty printed) output.rs to the origin Token in the .toy file
+#[derive(Serialize)]
+struct SourceMapJson {
+    file: String,
+    source: String,
+    mappings: Vec<SourceMapping>,
+}
+
+/// Generated lines start at 1, columns at 0, and the end is exclusive.
+/// Row, start and end are the same as the Token in lines_of_tokens, i.e. the same as the --tokens output
+#[derive(Serialize, Debug, PartialEq)]
+struct SourceMapping {
+    generated_line: usize,
+    generated_column: usize,
+    generated_end_line: usize,
+    generated_end_column: usize,
+    row: usize,
+    start: usize,
+    end: usize,
+    token: String,
+}
 
 /// The main function to set the output string from the compiler
 ///
@@ -17,8 +187,19 @@ pub fn set_output(compiler: &mut Compiler) {
     compiler.ast.log(format!("output::set_output {:?}", ""));
 
     replace_any_unknown_types(&mut compiler.ast);
+    match compiler.ast.target {
+        Target::Wat => return wat::set_output(compiler),
+        Target::C => return c::set_output(compiler),
+        _ => (),
+    }
+    compiler.ast.output_map = vec![];
+    compiler.ast.premain_output_map = vec![];
 
-    set_output_append(&mut compiler.ast, "fn main() {\r\n");
+    // Root marks the output which doesn't come from any Element, so isn't mapped to any Token
+    let backend = compiler.ast.target.backend();
+    set_premain_output_append(&mut compiler.ast, &backend.imports());
+    compiler.ast.output_map.push((compiler.ast.output.len(), 0));
+    set_output_append(&mut compiler.ast, &backend.main_start());
 
     // we re-use the ast.parents from the parser, using it's length only, as a simple way to define the ongoing indent level.
     // The actual contents don't matter, so we reset it with two zeros for good measure to define the first indent level under the main function for the first line of code.
@@ -63,8 +244,141 @@ pub fn set_output(compiler: &mut Compiler) {
         }
     }
     parents::outdent::outdent(compiler);
-    set_output_append(&mut compiler.ast, "}\r\n");
+    compiler.ast.output_map.push((compiler.ast.output.len(), 0));
+    set_output_append(&mut compiler.ast, &backend.main_end());
+    let premain_len = compiler.ast.premain_output.len();
+    let main_output_map = compiler
+        .ast
+        .output_map
+        .iter()
+        .map(|(index, el_index)| (index + premain_len, *el_index));
+    compiler.ast.output_map = compiler
+        .ast
+        .premain_output_map
+        .iter()
+        .cloned()
+        .chain(main_output_map)
+        .collect();
     compiler.ast.output = format!("{}{}", compiler.ast.premain_output, compiler.ast.output);
+    compiler.ast.source_map = get_source_map(&compiler.ast);
+}
+
+/// Converts the output_map into the line and column of each Element's output, with its origin Token.
+/// Internal elements, like Root, have an empty Token
+fn get_source_map(ast: &Ast) -> Vec<(usize, usize, Token)> {
+    let mut source_map = vec![];
+    for (output_index, el_index) in &ast.output_map {
+        let before = &ast.output[..*output_index];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count();
+        source_map.push((line, column, ast.element_tokens[*el_index].clone()));
+    }
+    source_map
+}
+
+/// The JSON for output.rs.map. Each token of the pretty printed output is traced back to its origin Token, via the PositionMap and source_map,
+/// and consecutive tokens from the same origin Token are merged into one range
+pub fn get_source_map_json(
+    ast: &Ast,
+    filename: &str,
+    pretty_output: &str,
+    position_map: &PositionMap,
+) -> String {
+    let source_map_json = SourceMapJson {
+        file: "output.rs".to_string(),
+        source: filename.to_string(),
+        mappings: get_source_mappings(ast, pretty_output, position_map),
+    };
+    serde_json::to_string(&source_map_json).unwrap_or_default()
+}
+
+fn get_source_mappings(
+    ast: &Ast,
+    pretty_output: &str,
+    position_map: &PositionMap,
+) -> Vec<SourceMapping> {
+    let mut mappings = vec![];
+    let mut current: Option<(LineColumn, Token)> = None;
+    for (pretty_position, original_position) in position_map {
+        let token = get_token_of_output_position(ast, original_position.0, original_position.1);
+        if current.as_ref().map(|(_, current_token)| current_token) == token.as_ref() {
+            continue;
+        }
+        if let Some((start, current_token)) = current {
+            mappings.push(get_source_mapping(start, *pretty_position, current_token));
+        }
+        current = token.map(|token| (*pretty_position, token));
+    }
+    if let Some((start, current_token)) = current {
+        let last_line = pretty_output.lines().count();
+        let last_column = pretty_output.lines().last().unwrap_or("").chars().count();
+        mappings.push(get_source_mapping(
+            start,
+            (last_line, last_column),
+            current_token,
+        ));
+    }
+    mappings
+}
+
+fn get_source_mapping(start: LineColumn, end: LineColumn, token: Token) -> SourceMapping {
+    SourceMapping {
+        generated_line: start.0,
+        generated_column: start.1,
+        generated_end_line: end.0,
+        generated_end_column: end.1,
+        row: token.1,
+        start: token.2,
+        end: token.3,
+        token: token.0,
+    }
+}
+
+/// The origin Token of the Element whose output contains this line (from 1) and column (from 0) of the output, using the source_map
+pub fn get_token_of_output_position(ast: &Ast, line: usize, column: usize) -> Option<Token> {
+    ast.source_map
+        .iter()
+        .rev()
+        .find(|(source_line, source_column, _)| (*source_line, *source_column) <= (line, column))
+        .filter(|(_, _, token)| !token.0.is_empty())
+        .map(|(_, _, token)| token.clone())
+}
+
+/// The Element whose output contains this byte index of the final output, using the output_map.
+/// None for the output added around the Elements, e.g. "fn main() {", which is marked as Root
+pub fn get_element_index_of_output_index(ast: &Ast, output_index: usize) -> Option<ElIndex> {
+    ast.output_map
+        .iter()
+        .rev()
+        .find(|(index, _)| *index <= output_index)
+        .map(|(_, el_index)| *el_index)
+        .filter(|el_index| *el_index != 0)
+}
+
+/// Elements which only affect the layout of the output
+pub fn is_layout(el_info: &ElementInfo) -> bool {
+    matches!(
+        el_info,
+        ElementInfo::Indent
+            | ElementInfo::Seol
+            | ElementInfo::Eol
+            | ElementInfo::CommentSingleLine(_)
+    )
+}
+
+/// The children which are values, i.e. not only for the layout of the output.
+/// Used by whatever walks the AST itself, e.g. the wat and c targets, eval and the bytecode compiler
+pub fn get_value_children(compiler: &Compiler, children: &[ElIndex]) -> Vec<ElIndex> {
+    children
+        .iter()
+        .filter(|&&child_ref| !is_layout(&compiler.ast.elements[child_ref].0))
+        .copied()
+        .collect()
+}
+
+/// The type of an Element, once replace_any_unknown_types has infered it
+pub fn get_element_type(compiler: &Compiler, el_index: ElIndex) -> String {
+    elements::get_elementinfo_type(&compiler.ast, &compiler.ast.elements[el_index].0)
 }
 
 /// indent from current parent and list children to output next
@@ -221,18 +535,21 @@ fn get_output_for_element_index(
         return "".to_string();
     }
 
+    let backend = ast.target.backend();
     match element.0 {
         ElementInfo::Root => empty_string,
-        ElementInfo::CommentSingleLine(comment_string) => comment_string,
-        ElementInfo::Int(val) => val,
-        ElementInfo::Float(val) => val,
-        ElementInfo::String(val) => format!("{}.to_string()", val),
-        ElementInfo::Bool(val) => format!("{}.to_string()", val),
-        ElementInfo::Arg(name, _scope, _argmodifier, _returntype) => name,
+        ElementInfo::CommentSingleLine(comment_string) => backend.comment(&comment_string),
+        ElementInfo::Int(val) => backend.int(&val),
+        ElementInfo::Float(val) => backend.float(&val),
+        ElementInfo::String(val) => backend.string(&val),
+        ElementInfo::Bool(val) => backend.bool(&val),
+        ElementInfo::Arg(name, _scope, _argmodifier, _returntype) => backend.identifier(&name),
         ElementInfo::Struct(name, _, _) => get_output_for_struct(ast, name, children),
         ElementInfo::StructEdit(name, _) => name,
-        ElementInfo::Constant(name, _) => name,
-        ElementInfo::ConstantRef(name, _, _reference) => format!("{}.clone()", name),
+        ElementInfo::Constant(name, _) => backend.identifier(&name),
+        ElementInfo::ConstantRef(name, _, _reference) => {
+            backend.constant_ref(&backend.identifier(&name))
+        }
         ElementInfo::Rust(code, code_position) => {
             let indent = format!("{}\r\n", &(" ".repeat(4 * (ast.parents.len()))));
             if let CodePosition::Main = code_position {
@@ -242,33 +559,32 @@ fn get_output_for_element_index(
             }
         }
         ElementInfo::Assignment => get_output_for_assignment(ast, children),
-        ElementInfo::InbuiltFunctionDef(name, _, _, _, _, _) => {
-            format!("fn {}() ->{{ /* stuff */ }}", name)
-        }
+        ElementInfo::InbuiltFunctionDef(name, _, _, _, _, _) => backend.inbuilt_function_def(&name),
         ElementInfo::List(returntype) => get_output_for_list(ast, children, returntype),
-        ElementInfo::InbuiltFunctionCall(name, _, _) => {
-            get_output_for_inbuiltfncall(ast, name, children)
+        ElementInfo::InbuiltFunctionCall(name, _, returntype) => {
+            get_output_for_inbuiltfncall(ast, name, children, returntype)
         }
         ElementInfo::FunctionDefWIP => empty_string,
         ElementInfo::FunctionDef(name, argnames, argtypes, returntype) => {
-            let empty_arg_modifiers = argnames.iter().map(|_s| String::new()).collect();
-            let args = formatting::get_formatted_argname_argtype_pairs(
+            let argnames: Vec<String> = argnames
+                .iter()
+                .map(|argname| backend.identifier(argname))
+                .collect();
+            backend.function_def(
+                &backend.identifier(&name),
                 &argnames,
                 &argtypes,
-                &empty_arg_modifiers,
-            );
-            format!("fn {}({}) -> {} {{\r\n", name, args, returntype)
+                &returntype,
+            )
         }
         ElementInfo::FunctionCall(name, skip_args, _) => {
             get_output_for_functioncall(ast, name, skip_args, children)
         }
         ElementInfo::Parens => get_output_for_parens(ast, children),
         ElementInfo::LoopForRangeWIP => empty_string,
-        ElementInfo::LoopForRange(name, from, to) => {
-            format!("For {} in {}..{} {{\r\n", name, from, to)
-        }
+        ElementInfo::LoopForRange(name, from, to) => backend.loop_for_range(&name, from, to),
         ElementInfo::Eol => format!("\r\n"),
-        ElementInfo::Seol => format!(";\r\n"),
+        ElementInfo::Seol => backend.seol(),
         ElementInfo::Indent => parents::get_indent(ast),
         ElementInfo::Type(name) => format!("{}", name),
         ElementInfo::Unused => empty_string,
@@ -322,7 +638,7 @@ fn is_skippable_due_to_parent(ast: &mut Ast, element_index: usize) -> bool {
 }
 
 /// Pre-main Output for Struct
-/// Should output something like this ...
+/// For Rust it should output something like this ...
 /// ```
 /// #[derive(Clone, Debug)]
 /// pub struct Newstruct {
@@ -352,26 +668,14 @@ fn get_premain_output_for_struct(ast: &mut Ast, name: String, children: Vec<usiz
     ast.log(format!("output::get_premain_output_for_struct {:?}", name));
 
     //skip defining the struct if it has been defined before
-    if ast
-        .premain_output
-        .contains(&format!("pub struct {} {{", name))
-    {
+    let is_already_defined = ast.premain_output_map.iter().any(|(_, el_index)| {
+        matches!(&ast.elements[*el_index].0, ElementInfo::Struct(n, _, _) if *n == name)
+    });
+    if is_already_defined {
         "".to_string()
     } else {
-        let struct_child_info = get_struct_child_info(ast, children);
-        let derive = "#[derive(Clone, Debug)]\r\n".to_string();
-        let a_struct = format!(
-            "pub struct {} {{\r\n{}}}\r\n\r\n",
-            name, struct_child_info.0
-        );
-        let new_fn = format!(
-            "    pub fn new(\r\n{}) -> {} {{\r\n        {} {{\r\n{}        }}\r\n    }}",
-            struct_child_info.1, name, name, struct_child_info.2
-        );
-        format!(
-            "{}{}impl Newstruct {{\r\n{}\r\n}}\r\n\r\n",
-            derive, a_struct, new_fn
-        )
+        let (keys, types) = get_struct_child_info(ast, children);
+        ast.target.backend().premain_struct(&name, &keys, &types)
     }
 }
 
@@ -382,23 +686,23 @@ pub fn get_existing_identical_struct_el_ref(ast: &mut Ast, children: Vec<usize>)
         let el = ast.elements[el_ref].clone();
         if let (ElementInfo::Struct(_n, _, _), existing_struct_children) = el {
             let existing_struct_child_info = get_struct_child_info(ast, existing_struct_children);
-            let existing_struct_has_keys = existing_struct_child_info.3.len() > 0;
-            let new_struct_has_keys = struct_child_info.3.len() > 0;
+            let existing_struct_has_keys = existing_struct_child_info.0.len() > 0;
+            let new_struct_has_keys = struct_child_info.0.len() > 0;
             let new_struct_has_same_num_types_as_keys =
-                struct_child_info.3.len() == struct_child_info.4.len();
+                struct_child_info.0.len() == struct_child_info.1.len();
             let new_struct_has_same_num_types_as_existing_struct =
-                struct_child_info.4.len() == existing_struct_child_info.4.len();
+                struct_child_info.1.len() == existing_struct_child_info.1.len();
             let both_have_same_num_keys_and_types = existing_struct_has_keys
                 && new_struct_has_keys
                 && new_struct_has_same_num_types_as_keys
                 && new_struct_has_same_num_types_as_existing_struct;
             if both_have_same_num_keys_and_types {
                 let mut all_keys_and_types_match = true;
-                for i in 0..struct_child_info.3.len() {
-                    let new_key = &struct_child_info.3[i];
-                    let existing_key = &existing_struct_child_info.3[i];
-                    let new_type = &struct_child_info.4[i];
-                    let existing_type = &existing_struct_child_info.4[i];
+                for i in 0..struct_child_info.0.len() {
+                    let new_key = &struct_child_info.0[i];
+                    let existing_key = &existing_struct_child_info.0[i];
+                    let new_type = &struct_child_info.1[i];
+                    let existing_type = &existing_struct_child_info.1[i];
                     if new_key != existing_key || new_type != existing_type {
                         all_keys_and_types_match = false;
                         break;
@@ -414,17 +718,12 @@ pub fn get_existing_identical_struct_el_ref(ast: &mut Ast, children: Vec<usize>)
     found
 }
 
-fn get_struct_child_info(
-    ast: &mut Ast,
-    children: Vec<usize>,
-) -> (String, String, String, Vec<String>, Vec<String>) {
+/// The keys and types of a Struct
+fn get_struct_child_info(ast: &mut Ast, children: Vec<usize>) -> (Vec<String>, Vec<String>) {
     // a Structs children should all be:
     // - Assignments, each Assignment should have one child Constant, and one Value
     // - ConstantRef
 
-    let mut struct_pub_keys_types = "".to_string();
-    let mut struct_new_fn_keys_types = "".to_string();
-    let mut struct_new_fn_keys = "".to_string();
     let mut struct_keys: Vec<String> = vec![];
     let mut struct_types: Vec<String> = vec![];
     for i in 0..children.len() as usize {
@@ -433,43 +732,18 @@ fn get_struct_child_info(
             ElementInfo::Assignment => {
                 let assignment_ref = child_el.1[0];
                 if let ElementInfo::Constant(key, a_type) = &ast.elements[assignment_ref].0 {
-                    struct_pub_keys_types = format!(
-                        "{}    pub {}: {},\r\n",
-                        &struct_pub_keys_types, &key, &a_type
-                    );
-                    struct_new_fn_keys_types = format!(
-                        "{}        {}: {},\r\n",
-                        &struct_new_fn_keys_types, &key, &a_type
-                    );
-                    struct_new_fn_keys =
-                        format!("{}            {},\r\n", &struct_new_fn_keys, &key);
                     struct_keys.push(key.clone());
                     struct_types.push(a_type.clone());
                 }
             }
             ElementInfo::ConstantRef(key, a_type, _) => {
-                struct_pub_keys_types = format!(
-                    "{}    pub {}: {},\r\n",
-                    &struct_pub_keys_types, &key, &a_type
-                );
-                struct_new_fn_keys_types = format!(
-                    "{}        {}: {},\r\n",
-                    &struct_new_fn_keys_types, &key, &a_type
-                );
-                struct_new_fn_keys = format!("{}            {},\r\n", &struct_new_fn_keys, &key);
                 struct_keys.push(key);
                 struct_types.push(a_type);
             }
             _ => (),
         }
     }
-    return (
-        struct_pub_keys_types,
-        struct_new_fn_keys_types,
-        struct_new_fn_keys,
-        struct_keys,
-        struct_types,
-    );
+    (struct_keys, struct_types)
 }
 
 /// Output for Struct
@@ -500,19 +774,11 @@ fn get_output_for_struct(ast: &mut Ast, name: String, children: Vec<usize>) -> S
     }
 
     // each Expression will be used as an argument to the fn new(arg1, arg2)
-    let mut args_output = "".to_string();
-    for i in 0..arg_expressions.len() as usize {
-        let expr_ref = arg_expressions[i];
-        let arg = get_output_for_element_index(ast, expr_ref, false);
-        let no_first_comma = if i == 0 {
-            "".to_string()
-        } else {
-            ", ".to_string()
-        };
-        args_output = format!("{}{}{}", &args_output, &no_first_comma, &arg);
+    let mut args = vec![];
+    for expr_ref in arg_expressions {
+        args.push(get_output_for_element_index(ast, expr_ref, false));
     }
-
-    format!("{}::new({})", name, args_output)
+    ast.target.backend().struct_new(&name, &args)
 }
 
 /// Output for Assignment
@@ -534,64 +800,56 @@ fn get_output_for_assignment(ast: &mut Ast, children: Vec<usize>) -> String {
             }
             _ => (),
         }
-        let mut mut_if_assigning_to_struct = "".to_string();
-        let mut let_if_not_struct_edit = "let ".to_string();
+        let mut is_struct = false;
         let first_child_ref = children[0];
         if let ElementInfo::Constant(_, _) = ast.elements[first_child_ref].0 {
             let constant_first_child_ref = ast.elements[first_child_ref].1[0];
             if let ElementInfo::Struct(_, _, _) = ast.elements[constant_first_child_ref].0 {
-                mut_if_assigning_to_struct = "mut ".to_string();
+                is_struct = true;
             }
         }
-        if let ElementInfo::StructEdit(_, _) = ast.elements[first_child_ref].0 {
-            let_if_not_struct_edit = "".to_string();
-            returntype = "".to_string();
-        } else {
-            returntype = format!(": {}", returntype);
-        }
-        format!(
-            "{}{}{}{} = ",
-            let_if_not_struct_edit, mut_if_assigning_to_struct, constant_output, returntype
-        )
+        let is_struct_edit = matches!(
+            ast.elements[first_child_ref].0,
+            ElementInfo::StructEdit(_, _)
+        );
+        ast.target
+            .backend()
+            .assignment(&constant_output, &returntype, is_struct, is_struct_edit)
     }
 }
 
 /// Output for List
 fn get_output_for_list(ast: &mut Ast, children: Vec<usize>, returntype: String) -> String {
     ast.log(format!("output::get_output_for_list {:?}", ""));
-    if children.len() > 0 {
-        let mut output = "vec![ ".to_string();
-        for i in 0..children.len() {
-            let child_ref = children[i];
-            let child_output = get_output_for_element_index(ast, child_ref, false);
-            let no_first_comma = if i == 0 {
-                "".to_string()
-            } else {
-                ", ".to_string()
-            };
-            output = format!("{}{}{}", &output, &no_first_comma, &child_output);
-        }
-        format!("{} ]", &output)
-    } else {
-        let mut vec_type = returntype.clone();
-        if returntype.len() > 5 && returntype[..3] == "Vec".to_string() {
-            vec_type = returntype[4..returntype.len() - 1].to_string();
-        }
-        format!("Vec::<{}>::new()", vec_type)
+    let mut items = vec![];
+    for child_ref in children {
+        items.push(get_output_for_element_index(ast, child_ref, false));
     }
+    ast.target.backend().list(&items, &returntype)
 }
 
 /// Output for InbuiltFnCall
-fn get_output_for_inbuiltfncall(ast: &mut Ast, name: String, children: Vec<usize>) -> String {
+fn get_output_for_inbuiltfncall(
+    ast: &mut Ast,
+    name: String,
+    children: Vec<usize>,
+    returntype: String,
+) -> String {
     ast.log(format!("output::get_output_for_inbuiltfncall {:?}", ""));
     if let Some(def) = elements::get_inbuilt_function_by_name(ast, &name) {
         match def.clone() {
             ElementInfo::InbuiltFunctionDef(_, argnames, _, _, _, format) => {
-                let mut output = format;
+                let mut output =
+                    ast.target
+                        .backend()
+                        .inbuilt_function_format(&name, &returntype, &format);
                 for i in 0..argnames.len() {
                     let arg_var_num = format!("arg~{}", i + 1);
                     if i >= children.len() {
-                        dbg!("output error", &name, &children, i);
+                        ast.log(format!(
+                            "output error - missing arg {:?} {:?} {:?}",
+                            &name, &children, i
+                        ));
                     } else {
                         let arg_value_el_ref = children[i];
                         let arg_output = get_output_for_element_index(ast, arg_value_el_ref, true);
@@ -602,7 +860,7 @@ fn get_output_for_inbuiltfncall(ast: &mut Ast, name: String, children: Vec<usize
                     let last_child = elements::get_last_element(ast);
                     match &last_child.0 {
                         ElementInfo::Seol => {
-                            output = format!("{};\r\n", output);
+                            output = format!("{}{}", output, ast.target.backend().seol());
                             ()
                         }
                         _ => (),
@@ -624,24 +882,22 @@ fn get_output_for_functioncall(
     arguments: Vec<usize>,
 ) -> String {
     ast.log(format!("output::get_output_for_functioncall {:?}", ""));
+    let backend = ast.target.backend();
     if skip_args {
-        name
+        backend.identifier(&name)
     } else {
         let empty_string = "".to_string();
         let mut args = empty_string.clone();
         for i in 0..arguments.len() {
             let arg_el_ref = arguments[i];
             //let arg_el = ast.elements[arg_el_ref];
-            let arg = get_output_for_element_index(ast, arg_el_ref, false);
-            let mut borrow = empty_string.clone();
+            let mut arg = get_output_for_element_index(ast, arg_el_ref, false);
             if let Some(fndef_ref) = elements::get_function_index_by_name(ast, &name) {
                 let fndef = &ast.elements[fndef_ref];
                 match &fndef.0 {
                     ElementInfo::FunctionDef(_, _, argtypes, _) => {
                         if argtypes.len() == arguments.len() {
-                            if argtypes[i].contains("&dyn Fn") {
-                                borrow = "&".to_string();
-                            }
+                            arg = backend.function_arg(&arg, &argtypes[i]);
                         }
                     }
                     _ => (),
@@ -653,9 +909,9 @@ fn get_output_for_functioncall(
             } else {
                 ", ".to_string()
             };
-            args = format!("{}{}{}{}", args, borrow, arg, comma);
+            args = format!("{}{}{}", args, arg, comma);
         }
-        format!("{}({})", name, args)
+        format!("{}({})", backend.identifier(&name), args)
     }
 }
 
@@ -682,8 +938,7 @@ fn get_output_for_println(ast: &mut Ast, children: Vec<usize>) -> String {
         output = format!("{}{}", output, child);
 
         // print with Display {} or Debug {:?}
-        let mut debug = "".to_string();
-        let mut borrowed = "".to_string();
+        let mut is_debug = false;
         let constant_el = ast.elements[child_ref].clone();
         if let ElementInfo::ConstantRef(name, _, _) = constant_el.0 {
             let mut is_a_struct = false;
@@ -713,11 +968,10 @@ fn get_output_for_println(ast: &mut Ast, children: Vec<usize>) -> String {
             }
 
             if is_a_struct || is_a_list {
-                debug = ":?".to_string();
-                borrowed = "&".to_string();
+                is_debug = true;
             }
         }
-        format!("println!(\"{{{}}}\", {}{})", debug, borrowed, output)
+        ast.target.backend().println(&output, is_debug)
     } else {
         format!("println!(\"[Error - no child element to print!]\")")
     }
@@ -728,25 +982,20 @@ fn get_output_for_if(ast: &mut Ast, children: Vec<usize>, returntype: String) ->
     ast.log(format!("output::get_output_for_if {:?}", ""));
     let mut output = "".to_string();
     if children.len() < 3 {
-        dbg!("output error", &returntype, &children);
+        ast.log(format!(
+            "output error - missing children {:?} {:?}",
+            &returntype, &children
+        ));
     } else {
         let child1_output = get_output_for_element_index(ast, children[0], false);
-        output = format!("{}if {} {{", output, child1_output);
         let child2_output = get_output_for_element_index(ast, children[1], false);
-        output = format!(
-            "{}\r\n{}{}\r\n{}}} else {{",
-            output,
-            " ".repeat(4 * (ast.parents.len())),
-            child2_output,
-            " ".repeat(4 * (ast.parents.len() - 1)),
-        );
         let child3_output = get_output_for_element_index(ast, children[2], false);
-        output = format!(
-            "{}\r\n{}{}\r\n{}}}",
-            output,
-            " ".repeat(4 * (ast.parents.len())),
-            child3_output,
-            " ".repeat(4 * (ast.parents.len() - 1)),
+        output = ast.target.backend().if_expression(
+            &child1_output,
+            &child2_output,
+            &child3_output,
+            &" ".repeat(4 * (ast.parents.len())),
+            &" ".repeat(4 * (ast.parents.len() - 1)),
         );
     }
     //parents::outdent::outdent(&mut ast);
@@ -761,6 +1010,10 @@ fn set_premain_output_for_element(ast: &mut Ast, el_index: usize) {
         el_index
     ));
     let element_string = get_premain_output_for_element_index(ast, el_index, true);
+    if !element_string.is_empty() {
+        ast.premain_output_map
+            .push((ast.premain_output.len(), el_index));
+    }
     set_premain_output_append(ast, &element_string);
 }
 
@@ -770,21 +1023,48 @@ fn set_output_for_element_open(ast: &mut Ast, el_index: usize) {
         "output::set_output_for_element_open {:?}",
         el_index
     ));
-    let element_string = get_output_for_element_index(ast, el_index, true);
+    let mut element_string = get_output_for_element_index(ast, el_index, true);
+    if !element_string.is_empty() {
+        ast.output_map.push((ast.output.len(), el_index));
+        if is_return_expression_of_function(ast, el_index) {
+            element_string = ast.target.backend().return_expression(&element_string);
+        }
+    }
     set_output_append(ast, &element_string);
 }
 
+/// The last expression in the body of a FunctionDef, i.e. its return value
+fn is_return_expression_of_function(ast: &Ast, el_index: usize) -> bool {
+    match parents::get_current_parent_element_from_element_children_search(ast, el_index) {
+        Some((ElementInfo::FunctionDef(_, _, _, _), children)) => {
+            let last_child = children.iter().rev().find(|&&child_ref| {
+                !matches!(
+                    ast.elements[child_ref].0,
+                    ElementInfo::Indent
+                        | ElementInfo::Seol
+                        | ElementInfo::Eol
+                        | ElementInfo::CommentSingleLine(_)
+                )
+            });
+            last_child == Some(&el_index)
+                && elements::append::is_return_expression(&ast.elements[el_index].0)
+        }
+        _ => false,
+    }
+}
+
 /// Append an indented closing bracket for FunctionDef to the output string, or nothing
 fn set_output_for_element_close(ast: &mut Ast, el_index: usize) {
     ast.log(format!("output::set_output_for_element_close {:?}", ""));
     if el_index < ast.elements.len() {
         let element = &ast.elements[el_index];
         let element_string = match element.0 {
-            ElementInfo::FunctionDef(_, _, _, _) => {
-                format!("\r\n{}}}\r\n", parents::get_indent(ast))
-            }
+            ElementInfo::FunctionDef(_, _, _, _) => ast
+                .target
+                .backend()
+                .function_def_close(&parents::get_indent(ast)),
             ElementInfo::LoopForRange(_, _, _) => {
-                format!(";\r\n{}}}", parents::get_indent(ast))
+                ast.target.backend().loop_close(&parents::get_indent(ast))
             }
             ElementInfo::Struct(_, _, _) => "".to_string(),
             _ => "".to_string(),
@@ -807,3 +1087,46 @@ fn set_premain_output_append(ast: &mut Ast, append_string: &str) {
     ));
     ast.premain_output = format!("{}{}", ast.premain_output, append_string);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatting;
+
+    #[test]
+    fn test_get_source_mappings() {
+        let mut compiler = Compiler::default();
+        compiler.file.filecontents = "= a 123\r\n@ a".to_string();
+        let _ = compiler.run_main_tasks();
+        let (pretty, position_map) =
+            formatting::pretty_print_with_position_map(&compiler.ast.output);
+        assert_eq!(
+            pretty,
+            "fn main() {\r\n    let a: i64 = 123;\r\n    println!(\"{}\", a.clone());\r\n}\r\n"
+        );
+        let mappings: Vec<(usize, usize, usize, usize, String)> =
+            get_source_mappings(&compiler.ast, &pretty, &position_map)
+                .into_iter()
+                .map(|m| {
+                    (
+                        m.generated_line,
+                        m.generated_column,
+                        m.generated_end_line,
+                        m.generated_end_column,
+                        m.token,
+                    )
+                })
+                .collect();
+        assert_eq!(
+            mappings,
+            vec![
+                // "let a: i64 =" is all output by the Assignment
+                (2, 4, 2, 17, "=".to_string()),
+                (2, 17, 3, 4, "123".to_string()),
+                (3, 4, 3, 29, "@".to_string()),
+                // the semicolon is output by the Seol at the end of the line
+                (3, 29, 4, 0, "a".to_string()),
+            ]
+        );
+    }
+}
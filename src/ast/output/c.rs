@@ -0,0 +1,420 @@
+/*! Outputs a single C file, i.e. `--target c`, for the primitive subset of toylang
+
+    Like [wat](super::wat), C needs the type of every value, and functions can't be nested,
+    so the AST is walked separately here, rather than by a [Backend](super::Backend).
+
+    - i64 is an `int64_t`, f64 a `double`, and bool a `bool` from stdbool.h
+    - each FunctionDef is output before `main`, wherever it was defined, after a prototype of every function so they can call each other
+    - constants are `const` locals, and `..` loops are `for` loops over an `int64_t`
+    - int literals use `INT64_C`, so arithmetic on them can't overflow an `int`
+    - `@` prints with `printf`, in the same format as Rust, so floats are printed by a `print_f64` helper with the shortest digits which round trip
+
+    Anything else, e.g. strings, lists or structs, is a compile error.
+
+    ```text
+    int64_t double_it(int64_t x);
+
+    int64_t double_it(int64_t x) {
+        return x * INT64_C(2);
+    }
+
+    int main(void) {
+        const int64_t a = double_it(INT64_C(3));
+        printf("%" PRId64 "\n", a);
+        return 0;
+    }
+    ```
+*/
+use crate::ast::elements::{ElIndex, ElementInfo};
+use crate::ast::output::{get_element_type, get_value_children};
+use crate::diagnostic::{Diagnostic, Span};
+use crate::errors::ERRORS;
+use crate::Compiler;
+
+const INCLUDES: &str =
+    "#include <inttypes.h>\r\n#include <math.h>\r\n#include <stdbool.h>\r\n#include <stdint.h>\r\n#include <stdio.h>\r\n#include <stdlib.h>\r\n#include <string.h>\r\n";
+
+/// Prints a double like Rust's Display, i.e. the fewest digits which parse back to the same value, and never an exponent.
+/// `%g` would only keep 6 significant digits, e.g. 1.23457e+06 for 1234567
+const PRINT_F64: &str = "\r\nstatic void print_f64(double value) {\r\n    if (isnan(value)) {\r\n        printf(\"NaN\\n\");\r\n        return;\r\n    }\r\n    if (isinf(value)) {\r\n        printf(\"%sinf\\n\", value < 0 ? \"-\" : \"\");\r\n        return;\r\n    }\r\n    char buffer[32];\r\n    int precision = 0;\r\n    snprintf(buffer, sizeof buffer, \"%.*e\", precision, value);\r\n    while (strtod(buffer, NULL) != value && precision < 16) {\r\n        precision++;\r\n        snprintf(buffer, sizeof buffer, \"%.*e\", precision, value);\r\n    }\r\n    int decimals = precision - atoi(strchr(buffer, 'e') + 1);\r\n    printf(\"%.*f\\n\", decimals > 0 ? decimals : 0, value);\r\n}\r\n";
+
+/// A function being output
+struct Function {
+    signature: String,
+    /// the args, constants and loop variables in scope
+    names: Vec<String>,
+    /// the loop variables in scope, which are always i64, although the AST doesn't know their type yet
+    loop_names: Vec<String>,
+    body: Vec<String>,
+}
+
+impl Function {
+    fn new(signature: String, names: Vec<String>) -> Function {
+        Function {
+            signature,
+            names,
+            loop_names: vec![],
+            body: vec![],
+        }
+    }
+
+    fn get_output(&self) -> String {
+        let lines: Vec<String> = self
+            .body
+            .iter()
+            .map(|line| format!("{}\r\n", line))
+            .collect();
+        format!("{} {{\r\n{}}}\r\n", self.signature, lines.concat())
+    }
+}
+
+struct Program<'a> {
+    compiler: &'a Compiler,
+    prototypes: Vec<String>,
+    functions: Vec<String>,
+    /// the Elements which can't be output
+    errors: Vec<ElIndex>,
+    /// whether print_f64 is needed
+    prints_f64: bool,
+}
+
+/// Sets the output to the C file, or adds an error for each Element which isn't supported
+pub fn set_output(compiler: &mut Compiler) {
+    compiler.ast.log(format!("c::set_output {:?}", ""));
+    let mut program = Program {
+        compiler: &*compiler,
+        prototypes: vec![],
+        functions: vec![],
+        errors: vec![],
+        prints_f64: false,
+    };
+    let mut main = Function::new("int main(void)".to_string(), vec![]);
+    let root_children = compiler.ast.elements[0].1.clone();
+    program.add_block(&mut main, &root_children, 1, false);
+    main.body.push("    return 0;".to_string());
+    program.functions.push(main.get_output());
+
+    let prototypes = if program.prototypes.is_empty() {
+        "".to_string()
+    } else {
+        format!("\r\n{}", program.prototypes.concat())
+    };
+    let print_f64 = if program.prints_f64 { PRINT_F64 } else { "" };
+    let output = format!(
+        "{}{}{}\r\n{}",
+        INCLUDES,
+        print_f64,
+        prototypes,
+        program.functions.join("\r\n")
+    );
+    let errors = program.errors;
+    for el_index in errors {
+        let span = Span::from(&compiler.ast.element_tokens[el_index]);
+        let error = Diagnostic::error(ERRORS.c_unsupported, span);
+        compiler.error_stack.push(error);
+    }
+    compiler.ast.output = output;
+    compiler.ast.output_map = vec![];
+    compiler.ast.source_map = vec![];
+}
+
+impl Program<'_> {
+    /// Outputs each line of a block at the depth of indent. Only a function has a result, its last line
+    fn add_block(
+        &mut self,
+        function: &mut Function,
+        children: &[ElIndex],
+        depth: usize,
+        has_result: bool,
+    ) {
+        let children = get_value_children(self.compiler, children);
+        for (i, &child_ref) in children.iter().enumerate() {
+            let is_result = has_result && i == children.len() - 1;
+            if let Err(el_index) = self.add_line(function, child_ref, depth, is_result) {
+                self.errors.push(el_index);
+            }
+        }
+    }
+
+    fn add_line(
+        &mut self,
+        function: &mut Function,
+        el_index: ElIndex,
+        depth: usize,
+        is_result: bool,
+    ) -> Result<(), ElIndex> {
+        let compiler = self.compiler;
+        let (el_info, all_children) = &compiler.ast.elements[el_index];
+        let children = get_value_children(compiler, all_children);
+        let indent = " ".repeat(4 * depth);
+        match el_info {
+            // a function can't be defined inside another, but it can't use anything from its parent either
+            ElementInfo::FunctionDef(name, argnames, argtypes, returntype) => {
+                let mut args = vec![];
+                for (argname, argtype) in argnames.iter().zip(argtypes) {
+                    let argtype = get_type(argtype).ok_or(el_index)?;
+                    args.push(format!("{} {}", argtype, argname));
+                }
+                let args = if args.is_empty() {
+                    "void".to_string()
+                } else {
+                    args.join(", ")
+                };
+                let returntype = get_type(returntype).ok_or(el_index)?;
+                let signature = format!("{} {}({})", returntype, name, args);
+                self.prototypes.push(format!("{};\r\n", signature));
+                let mut new_function = Function::new(signature, argnames.clone());
+                self.add_block(&mut new_function, all_children, 1, true);
+                self.functions.push(new_function.get_output());
+            }
+            ElementInfo::Assignment => {
+                let constant_ref = *children.first().ok_or(el_index)?;
+                match &compiler.ast.elements[constant_ref] {
+                    (ElementInfo::Constant(name, returntype), constant_children) => {
+                        let value_children = get_value_children(compiler, constant_children);
+                        let value_ref = *value_children.first().ok_or(constant_ref)?;
+                        let value = self.get_expression(function, value_ref, false)?;
+                        let returntype = get_type(returntype).ok_or(constant_ref)?;
+                        function.names.push(name.clone());
+                        function.body.push(format!(
+                            "{}const {} {} = {};",
+                            indent, returntype, name, value
+                        ));
+                    }
+                    _ => return Err(constant_ref),
+                }
+            }
+            ElementInfo::LoopForRange(name, from, to) => {
+                function.body.push(format!(
+                    "{}for (int64_t {} = {}; {} < {}; {}++) {{",
+                    indent, name, from, name, to, name
+                ));
+                function.names.push(name.clone());
+                function.loop_names.push(name.clone());
+                self.add_block(function, all_children, depth + 1, false);
+                function.names.retain(|existing| existing != name);
+                function.loop_names.retain(|existing| existing != name);
+                function.body.push(format!("{}}}", indent));
+            }
+            ElementInfo::Println => {
+                let value_ref = *children.first().ok_or(el_index)?;
+                let returntype = self.get_value_type(function, value_ref);
+                let value = self.get_expression(function, value_ref, returntype == "bool")?;
+                let printf = match returntype.as_str() {
+                    "i64" => format!("printf(\"%\" PRId64 \"\\n\", {});", value),
+                    "f64" => {
+                        self.prints_f64 = true;
+                        format!("print_f64({});", value)
+                    }
+                    "bool" => format!("printf(\"%s\\n\", {} ? \"true\" : \"false\");", value),
+                    _ => return Err(value_ref),
+                };
+                function.body.push(format!("{}{}", indent, printf));
+            }
+            // the assignment of a function
+            ElementInfo::Unused => (),
+            // the result is returned, or discarded like a statement
+            _ if is_result => {
+                let value = self.get_expression(function, el_index, false)?;
+                function.body.push(format!("{}return {};", indent, value));
+            }
+            _ => {
+                let value = self.get_expression(function, el_index, true)?;
+                function.body.push(format!("{}(void){};", indent, value));
+            }
+        }
+        Ok(())
+    }
+
+    /// The C expression, or the Element which isn't supported.
+    /// Operators are bracketed when they are nested in another, to keep toylang's order of evaluation
+    fn get_expression(
+        &self,
+        function: &Function,
+        el_index: ElIndex,
+        is_nested: bool,
+    ) -> Result<String, ElIndex> {
+        let (el_info, children) = &self.compiler.ast.elements[el_index];
+        let children = get_value_children(self.compiler, children);
+        let bracket = |expression: String| {
+            if is_nested {
+                format!("({})", expression)
+            } else {
+                expression
+            }
+        };
+        match el_info {
+            // ints used as return expressions have "as i64" appended for the Rust output
+            ElementInfo::Int(value) => Ok(get_int(value.trim_end_matches(" as i64"))),
+            ElementInfo::Float(value) => Ok(value.clone()),
+            ElementInfo::Bool(value) => Ok(value.clone()),
+            ElementInfo::ConstantRef(name, _, _) | ElementInfo::Arg(name, _, _, _) => {
+                if function.names.contains(name) {
+                    Ok(name.clone())
+                } else {
+                    Err(el_index)
+                }
+            }
+            ElementInfo::InbuiltFunctionCall(name, _, _) => match name.as_str() {
+                "true" | "false" => Ok(name.clone()),
+                "+" | "-" | "*" | "/" | "%" | "==" | "!=" | "<" | ">" | "<=" | ">=" => {
+                    if children.len() != 2 {
+                        return Err(el_index);
+                    }
+                    let argtype = self.get_value_type(function, children[0]);
+                    get_type(&argtype).ok_or(el_index)?;
+                    if name == "%" && argtype != "i64" {
+                        return Err(el_index);
+                    }
+                    Ok(bracket(format!(
+                        "{} {} {}",
+                        self.get_expression(function, children[0], true)?,
+                        name,
+                        self.get_expression(function, children[1], true)?
+                    )))
+                }
+                _ => Err(el_index),
+            },
+            ElementInfo::FunctionCall(name, false, _) => {
+                let mut args = vec![];
+                for child_ref in children {
+                    args.push(self.get_expression(function, child_ref, false)?);
+                }
+                Ok(format!("{}({})", name, args.join(", ")))
+            }
+            ElementInfo::If(returntype) if children.len() == 3 => {
+                get_type(returntype).ok_or(el_index)?;
+                Ok(bracket(format!(
+                    "{} ? {} : {}",
+                    self.get_expression(function, children[0], true)?,
+                    self.get_expression(function, children[1], true)?,
+                    self.get_expression(function, children[2], true)?
+                )))
+            }
+            ElementInfo::Parens if children.len() == 1 => {
+                self.get_expression(function, children[0], is_nested)
+            }
+            _ => Err(el_index),
+        }
+    }
+
+    /// The toylang type of a value, including those which depend on a loop variable
+    fn get_value_type(&self, function: &Function, el_index: ElIndex) -> String {
+        let returntype = get_element_type(self.compiler, el_index);
+        if get_type(&returntype).is_some() {
+            return returntype;
+        }
+        let (el_info, children) = &self.compiler.ast.elements[el_index];
+        let children = get_value_children(self.compiler, children);
+        match el_info {
+            ElementInfo::ConstantRef(name, _, _) if function.loop_names.contains(name) => {
+                "i64".to_string()
+            }
+            ElementInfo::InbuiltFunctionCall(name, _, _) => match name.as_str() {
+                "==" | "!=" | "<" | ">" | "<=" | ">=" => "bool".to_string(),
+                _ if !children.is_empty() => self.get_value_type(function, children[0]),
+                _ => returntype,
+            },
+            ElementInfo::Parens if children.len() == 1 => {
+                self.get_value_type(function, children[0])
+            }
+            _ => returntype,
+        }
+    }
+}
+
+/// The C type of a toylang type, if it is supported
+fn get_type(a_type: &str) -> Option<&'static str> {
+    match a_type {
+        "i64" => Some("int64_t"),
+        "f64" => Some("double"),
+        "bool" => Some("bool"),
+        _ => None,
+    }
+}
+
+/// An int literal as an int64_t. The smallest i64 can't be written as a literal in C, since it is negated after
+fn get_int(value: &str) -> String {
+    if value == "-9223372036854775808" {
+        "INT64_MIN".to_string()
+    } else {
+        format!("INT64_C({})", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::output::Target;
+    use crate::eval;
+    use crate::{Compiler, Options};
+    use std::env;
+    use std::fs;
+    use std::process::{self, Command};
+
+    fn compile(text: &str) -> Compiler {
+        let options = Options {
+            target: Target::C,
+            ..Options::default()
+        };
+        crate::compile(text, options)
+    }
+
+    #[test]
+    fn test_set_output() {
+        let test_case_passes = vec![
+            (
+                "= fibonacci \\ i64 i64 n => ? < n 2 1 + fibonacci - n 1 fibonacci - n 2\r\n@ fibonacci 10",
+                "#include <inttypes.h>\r\n#include <math.h>\r\n#include <stdbool.h>\r\n#include <stdint.h>\r\n#include <stdio.h>\r\n#include <stdlib.h>\r\n#include <string.h>\r\n\r\nint64_t fibonacci(int64_t n);\r\n\r\nint64_t fibonacci(int64_t n) {\r\n    return (n < INT64_C(2)) ? INT64_C(1) : (fibonacci(n - INT64_C(1)) + fibonacci(n - INT64_C(2)));\r\n}\r\n\r\nint main(void) {\r\n    printf(\"%\" PRId64 \"\\n\", fibonacci(INT64_C(10)));\r\n    return 0;\r\n}\r\n",
+            ),
+            (
+                "= a * 1.5 2.0\r\n= b > a 2.5\r\n@ b\r\n.. i 0 3\r\n@ + i -2\r\n.",
+                "#include <inttypes.h>\r\n#include <math.h>\r\n#include <stdbool.h>\r\n#include <stdint.h>\r\n#include <stdio.h>\r\n#include <stdlib.h>\r\n#include <string.h>\r\n\r\nint main(void) {\r\n    const double a = 1.5 * 2.0;\r\n    const bool b = a > 2.5;\r\n    printf(\"%s\\n\", b ? \"true\" : \"false\");\r\n    for (int64_t i = 0; i < 3; i++) {\r\n        printf(\"%\" PRId64 \"\\n\", i + INT64_C(-2));\r\n    }\r\n    return 0;\r\n}\r\n",
+            ),
+        ];
+        for (input, expected) in test_case_passes {
+            let compiler = compile(input);
+            assert!(compiler.error_stack.is_empty());
+            assert_eq!(compiler.ast.output, expected);
+        }
+    }
+
+    #[test]
+    fn test_set_output_unsupported() {
+        let compiler = compile("= a 1\r\n= b \"string\"\r\n@ b");
+        assert_eq!(compiler.error_stack.len(), 2);
+        assert_eq!(
+            compiler.get_rendered_errors()[0],
+            "----------\r\nerror[T0052] ./src/:2:0\r\n= b \"string\"\r\n    ^^^^^^^^ Not supported by --target c - only i64, f64 and bool values, arithmetic, comparisons, If, loops, printing and functions can be output as C"
+        );
+    }
+
+    #[test]
+    fn test_set_output_floats_match_eval() {
+        let input = "@ 1234567.0\r\n@ 0.1\r\n@ * 1.5 -2.0\r\n@ 123456789.125\r\n@ / 1.0 3.0\r\n@ 100000000000000000000.0";
+        let compiler = compile(input);
+        assert!(compiler.error_stack.is_empty());
+        let dir = env::temp_dir().join(format!("toylang_test_c_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("output.c");
+        let binary = dir.join("output");
+        fs::write(&source, &compiler.ast.output).unwrap();
+        // skipped without a C compiler
+        let built = match Command::new("cc")
+            .arg(&source)
+            .arg("-o")
+            .arg(&binary)
+            .status()
+        {
+            Ok(status) => status.success(),
+            Err(_) => return,
+        };
+        assert!(built);
+        let result = Command::new(&binary).output().unwrap();
+        let printed: Vec<String> = String::from_utf8_lossy(&result.stdout)
+            .lines()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(printed, eval::eval_str(input, false).unwrap());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
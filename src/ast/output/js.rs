@@ -0,0 +1,193 @@
+/*! A Backend outputting JavaScript, i.e. `--target js`, which can be run with Node or in a browser
+
+    The program is wrapped in a `main` function which is called at the end, like the Rust output.
+    Types are only used where they change the meaning, e.g. `/` of two i64 truncates the result.
+    Raw code in `##` lines is output as it is, so it must be JavaScript too.
+ */
+use crate::ast::output::Backend;
+
+pub struct Js;
+
+/// JavaScript reserved words, and the globals used by the output
+const RESERVED: &str = "await break case catch class const continue debugger default delete do else enum export \
+    extends false finally for function if implements import in instanceof interface let new null package private \
+    protected public return static super switch this throw true try typeof var void while with yield \
+    arguments eval undefined NaN Infinity Math console main";
+
+impl Backend for Js {
+    fn main_start(&self) -> String {
+        "function main() {\r\n".to_string()
+    }
+
+    fn main_end(&self) -> String {
+        "}\r\n\r\nmain();\r\n".to_string()
+    }
+
+    fn identifier(&self, name: &str) -> String {
+        // a trailing underscore avoids shadowing the globals used by the output, or using a reserved word
+        if RESERVED.split_whitespace().any(|reserved| reserved == name) {
+            format!("{}_", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn int(&self, value: &str) -> String {
+        value.trim_end_matches(" as i64").to_string()
+    }
+
+    fn string(&self, value: &str) -> String {
+        value.to_string()
+    }
+
+    fn bool(&self, value: &str) -> String {
+        value.to_string()
+    }
+
+    fn constant_ref(&self, name: &str) -> String {
+        name.to_string()
+    }
+
+    fn assignment(
+        &self,
+        name: &str,
+        _returntype: &str,
+        _is_struct: bool,
+        is_struct_edit: bool,
+    ) -> String {
+        if is_struct_edit {
+            format!("{} = ", name)
+        } else {
+            // the keys of a const object can still be edited
+            format!("const {} = ", name)
+        }
+    }
+
+    fn list(&self, items: &[String], _returntype: &str) -> String {
+        if !items.is_empty() {
+            format!("[ {} ]", items.join(", "))
+        } else {
+            "[]".to_string()
+        }
+    }
+
+    fn inbuilt_function_format(
+        &self,
+        name: &str,
+        returntype: &str,
+        default_format: &str,
+    ) -> String {
+        match name {
+            "/" if returntype == "i64" => "Math.trunc(arg~1 / arg~2)".to_string(),
+            "==" => "arg~1 === arg~2".to_string(),
+            "!=" => "arg~1 !== arg~2".to_string(),
+            "List::map" => "arg~1.map((val) => arg~2(val))".to_string(),
+            "List::mapindex" => "arg~1.map((val, index) => arg~2(index, val))".to_string(),
+            "List::append" => "arg~1.concat(arg~2)".to_string(),
+            "List::len" => "arg~1.length".to_string(),
+            "List::reverse" => "[...arg~1].reverse()".to_string(),
+            // arithmetic, comparisons and booleans are the same as Rust
+            _ => default_format.to_string(),
+        }
+    }
+
+    fn inbuilt_function_def(&self, _name: &str) -> String {
+        "".to_string()
+    }
+
+    fn function_def(
+        &self,
+        name: &str,
+        argnames: &[String],
+        _argtypes: &[String],
+        _returntype: &str,
+    ) -> String {
+        format!("function {}({}) {{\r\n", name, argnames.join(", "))
+    }
+
+    fn return_expression(&self, expression: &str) -> String {
+        format!("return {}", expression)
+    }
+
+    fn function_arg(&self, arg: &str, _argtype: &str) -> String {
+        arg.to_string()
+    }
+
+    fn loop_for_range(&self, name: &str, from: usize, to: usize) -> String {
+        format!(
+            "for (let {} = {}; {} < {}; {}++) {{\r\n",
+            name, from, name, to, name
+        )
+    }
+
+    fn println(&self, output: &str, _is_debug: bool) -> String {
+        format!("console.log({})", output)
+    }
+
+    fn if_expression(
+        &self,
+        condition: &str,
+        true_expression: &str,
+        false_expression: &str,
+        _indent: &str,
+        _outdent: &str,
+    ) -> String {
+        format!(
+            "({} ? {} : {})",
+            condition, true_expression, false_expression
+        )
+    }
+
+    fn struct_new(&self, name: &str, args: &[String]) -> String {
+        format!("new {}({})", name, args.join(", "))
+    }
+
+    fn premain_struct(&self, name: &str, keys: &[String], _types: &[String]) -> String {
+        let assignments: String = keys
+            .iter()
+            .map(|key| format!("        this.{} = {};\r\n", key, key))
+            .collect();
+        format!(
+            "class {} {{\r\n    constructor({}) {{\r\n{}    }}\r\n}}\r\n\r\n",
+            name,
+            keys.join(", "),
+            assignments
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::output::Target;
+    use crate::Compiler;
+
+    #[test]
+    fn test_set_output() {
+        let test_case_passes = vec![
+            (
+                "= a + 1 2\r\n@ a",
+                "function main() {\r\n    const a = 1 + 2;\r\n    console.log(a);\r\n}\r\n\r\nmain();\r\n",
+            ),
+            (
+                "= half \\ i64 i64 x => / x 2\r\n@ half 5",
+                "function main() {\r\n    function half(x) {\r\n        return Math.trunc(x / 2)\r\n    }\r\n    console.log(half(5));\r\n}\r\n\r\nmain();\r\n",
+            ),
+            (
+                "= a ? == 1 2 \"yes\" \"no\"",
+                // the value of a constant is indented by the parser, which prettyplease removes from the Rust output
+                "function main() {\r\n    const a =             (1 === 2 ? \"yes\" : \"no\");\r\n}\r\n\r\nmain();\r\n",
+            ),
+            (
+                "= class 1\r\n= new + class 1\r\n@ new",
+                "function main() {\r\n    const class_ = 1;\r\n    const new_ = class_ + 1;\r\n    console.log(new_);\r\n}\r\n\r\nmain();\r\n",
+            ),
+        ];
+        for (input, expected) in test_case_passes {
+            let mut compiler = Compiler::default();
+            compiler.ast.target = Target::Js;
+            compiler.file.filecontents = input.to_string();
+            let _ = compiler.run_main_tasks();
+            assert_eq!(compiler.ast.output, expected);
+        }
+    }
+}
@@ -0,0 +1,279 @@
+/*! A Backend outputting Python, i.e. `--target py`, e.g. to paste into a notebook
+
+    Blocks are defined by indentation only, so functions and loops have no closing brackets,
+    and the program is wrapped in a `main` function which is called at the end, like the Rust output.
+    Structs are output as dataclasses, and Ifs as conditional expressions.
+    Raw code in `##` lines is output as it is, so it must be Python too.
+*/
+use crate::ast::output::Backend;
+
+pub struct Py;
+
+/// Python keywords, and the builtins and helpers used by the output
+const RESERVED: &str = "False None True and as assert async await break class continue def del elif else except \
+    finally for from global if import in is lambda nonlocal not or pass raise return try while with yield \
+    dataclass enumerate int len list main map print range reversed _trunc_div _trunc_mod";
+
+/// Rust's `/` and `%` truncate towards zero, but Python's `//` and `%` round down, so they are corrected by the signs.
+/// Neither goes through a float, which would lose the precision of large ints
+const HELPERS: &str = "def _trunc_div(a, b):\r\n    q = a // b\r\n    return q + 1 if q < 0 and q * b != a else q\r\n\r\n\r\n\
+    def _trunc_mod(a, b):\r\n    r = a % b\r\n    return r - b if r != 0 and (r < 0) != (a < 0) else r\r\n\r\n\r\n";
+
+impl Backend for Py {
+    fn imports(&self) -> String {
+        format!("from dataclasses import dataclass\r\n\r\n\r\n{}", HELPERS)
+    }
+
+    fn main_start(&self) -> String {
+        // the docstring means main is never empty, which Python doesn't allow
+        "def main():\r\n    \"\"\"toylang\"\"\"\r\n".to_string()
+    }
+
+    fn main_end(&self) -> String {
+        "\r\n\r\nmain()\r\n".to_string()
+    }
+
+    fn comment(&self, comment: &str) -> String {
+        format!("#{}", comment.trim_start_matches("//"))
+    }
+
+    fn identifier(&self, name: &str) -> String {
+        // a trailing underscore avoids shadowing the builtins used by the output, or using a keyword
+        if RESERVED.split_whitespace().any(|reserved| reserved == name) {
+            format!("{}_", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn int(&self, value: &str) -> String {
+        value.trim_end_matches(" as i64").to_string()
+    }
+
+    fn string(&self, value: &str) -> String {
+        get_string(value)
+    }
+
+    fn bool(&self, value: &str) -> String {
+        get_bool(value)
+    }
+
+    fn constant_ref(&self, name: &str) -> String {
+        name.to_string()
+    }
+
+    fn assignment(
+        &self,
+        name: &str,
+        _returntype: &str,
+        _is_struct: bool,
+        _is_struct_edit: bool,
+    ) -> String {
+        format!("{} = ", name)
+    }
+
+    fn list(&self, items: &[String], _returntype: &str) -> String {
+        format!("[{}]", items.join(", "))
+    }
+
+    fn inbuilt_function_format(
+        &self,
+        name: &str,
+        returntype: &str,
+        default_format: &str,
+    ) -> String {
+        match name {
+            "true" | "false" => get_bool(name),
+            "/" if returntype == "i64" => "_trunc_div(arg~1, arg~2)".to_string(),
+            "%" => "_trunc_mod(arg~1, arg~2)".to_string(),
+            "List::map" => "list(map(arg~2, arg~1))".to_string(),
+            "List::mapindex" => {
+                "[arg~2(index, val) for index, val in enumerate(arg~1)]".to_string()
+            }
+            "List::append" => "arg~1 + arg~2".to_string(),
+            "List::len" => "len(arg~1)".to_string(),
+            "List::reverse" => "list(reversed(arg~1))".to_string(),
+            // the rest of the arithmetic, and comparisons, are the same as Rust
+            _ => default_format.to_string(),
+        }
+    }
+
+    fn inbuilt_function_def(&self, _name: &str) -> String {
+        "".to_string()
+    }
+
+    fn function_def(
+        &self,
+        name: &str,
+        argnames: &[String],
+        _argtypes: &[String],
+        _returntype: &str,
+    ) -> String {
+        format!("def {}({}):\r\n", name, argnames.join(", "))
+    }
+
+    fn function_def_close(&self, _indent: &str) -> String {
+        "\r\n".to_string()
+    }
+
+    fn return_expression(&self, expression: &str) -> String {
+        format!("return {}", expression)
+    }
+
+    fn function_arg(&self, arg: &str, _argtype: &str) -> String {
+        arg.to_string()
+    }
+
+    fn loop_for_range(&self, name: &str, from: usize, to: usize) -> String {
+        format!("for {} in range({}, {}):\r\n", name, from, to)
+    }
+
+    fn loop_close(&self, _indent: &str) -> String {
+        "".to_string()
+    }
+
+    fn seol(&self) -> String {
+        "\r\n".to_string()
+    }
+
+    fn println(&self, output: &str, _is_debug: bool) -> String {
+        format!("print({})", output)
+    }
+
+    fn if_expression(
+        &self,
+        condition: &str,
+        true_expression: &str,
+        false_expression: &str,
+        _indent: &str,
+        _outdent: &str,
+    ) -> String {
+        format!(
+            "({} if {} else {})",
+            true_expression, condition, false_expression
+        )
+    }
+
+    fn struct_new(&self, name: &str, args: &[String]) -> String {
+        format!("{}({})", name, args.join(", "))
+    }
+
+    fn premain_struct(&self, name: &str, keys: &[String], types: &[String]) -> String {
+        let fields: String = keys
+            .iter()
+            .zip(types)
+            .map(|(key, a_type)| format!("    {}: {}\r\n", key, get_type(a_type)))
+            .collect();
+        format!("@dataclass\r\nclass {}:\r\n{}\r\n\r\n", name, fields)
+    }
+}
+
+/// Strings are the same as in Rust, except Python has no `\u{..}` escape, so use its `\U` form with 8 hex digits, e.g. `\U0001f600`
+fn get_string(value: &str) -> String {
+    let mut output = "".to_string();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        output.push(c);
+        if c == '\\' {
+            match chars.next() {
+                Some('u') => {
+                    let hex: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+                    output.push_str(&format!("U{:0>8}", hex));
+                }
+                Some(escaped) => output.push(escaped),
+                None => (),
+            }
+        }
+    }
+    output
+}
+
+fn get_bool(value: &str) -> String {
+    match value {
+        "true" => "True".to_string(),
+        "false" => "False".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+/// The Python type hint for a toylang type, e.g. "list[int]" for "Vec<i64>"
+fn get_type(a_type: &str) -> String {
+    match a_type {
+        "i64" => "int".to_string(),
+        "f64" => "float".to_string(),
+        "String" => "str".to_string(),
+        "bool" => "bool".to_string(),
+        _ => match a_type
+            .strip_prefix("Vec<")
+            .and_then(|t| t.strip_suffix('>'))
+        {
+            Some(item_type) => format!("list[{}]", get_type(item_type)),
+            None => a_type.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::output::Target;
+    use crate::eval;
+    use crate::Compiler;
+    use std::env;
+    use std::fs;
+    use std::process::{self, Command};
+
+    #[test]
+    fn test_set_output() {
+        let test_case_passes = vec![
+            (
+                "= a + 1 2\r\n@ a",
+                "def main():\r\n    \"\"\"toylang\"\"\"\r\n    a = 1 + 2\r\n    print(a)\r\n\r\n\r\nmain()\r\n",
+            ),
+            (
+                ".. i 0 3\r\n@ i\r\n.\r\n= half \\ i64 i64 x => / x 2",
+                "def main():\r\n    \"\"\"toylang\"\"\"\r\n    for i in range(0, 3):\r\n        print(i)\r\n    def half(x):\r\n        return _trunc_div(x, 2)\r\n\r\n\r\nmain()\r\n",
+            ),
+            (
+                "= list [ 3 2 1 ]\r\n= len List::len List::reverse list",
+                "def main():\r\n    \"\"\"toylang\"\"\"\r\n    list_ = [3, 2, 1]\r\n    len_ = len(list(reversed(list_)))\r\n\r\n\r\nmain()\r\n",
+            ),
+            (
+                "= person { = name \"Ann\" = ok true }\r\n= person.ok false",
+                "@dataclass\r\nclass Person:\r\n    name: str\r\n    ok: bool\r\n\r\n\r\ndef main():\r\n    \"\"\"toylang\"\"\"\r\n    person = Person(\"Ann\", True)\r\n    person.ok = False\r\n\r\n\r\nmain()\r\n",
+            ),
+        ];
+        for (input, expected) in test_case_passes {
+            let mut compiler = Compiler::default();
+            compiler.ast.target = Target::Py;
+            compiler.file.filecontents = input.to_string();
+            let _ = compiler.run_main_tasks();
+            assert_eq!(compiler.ast.output, format!("{}{}", Py.imports(), expected));
+        }
+    }
+
+    #[test]
+    fn test_set_output_matches_eval() {
+        // Python's // and % round down, but eval truncates towards zero like Rust
+        let input = "@ / -7 2\r\n@ % -7 2\r\n@ / 7 -2\r\n@ % 7 -2\r\n@ / 9223372036854775807 3\r\n@ % -9223372036854775807 10";
+        let mut compiler = Compiler::default();
+        compiler.ast.target = Target::Py;
+        compiler.file.filecontents = input.to_string();
+        let _ = compiler.run_main_tasks();
+        let dir = env::temp_dir().join(format!("toylang_test_py_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let filepath = dir.join("output.py");
+        fs::write(&filepath, &compiler.ast.output).unwrap();
+        // skipped without a Python interpreter
+        let result = match Command::new("python3").arg(&filepath).output() {
+            Ok(result) => result,
+            Err(_) => return,
+        };
+        let printed: Vec<String> = String::from_utf8_lossy(&result.stdout)
+            .lines()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(printed, eval::eval_str(input, false).unwrap());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
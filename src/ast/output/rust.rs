@@ -0,0 +1,158 @@
+/*! The default Backend, outputting Rust
+ */
+use crate::ast::output::Backend;
+use crate::formatting;
+
+pub struct Rust;
+
+impl Backend for Rust {
+    fn main_start(&self) -> String {
+        "fn main() {\r\n".to_string()
+    }
+
+    fn main_end(&self) -> String {
+        "}\r\n".to_string()
+    }
+
+    fn int(&self, value: &str) -> String {
+        value.to_string()
+    }
+
+    fn string(&self, value: &str) -> String {
+        format!("{}.to_string()", value)
+    }
+
+    fn bool(&self, value: &str) -> String {
+        format!("{}.to_string()", value)
+    }
+
+    fn constant_ref(&self, name: &str) -> String {
+        format!("{}.clone()", name)
+    }
+
+    fn assignment(
+        &self,
+        name: &str,
+        returntype: &str,
+        is_struct: bool,
+        is_struct_edit: bool,
+    ) -> String {
+        if is_struct_edit {
+            format!("{} = ", name)
+        } else if is_struct {
+            format!("let mut {}: {} = ", name, returntype)
+        } else {
+            format!("let {}: {} = ", name, returntype)
+        }
+    }
+
+    fn list(&self, items: &[String], returntype: &str) -> String {
+        if !items.is_empty() {
+            format!("vec![ {} ]", items.join(", "))
+        } else {
+            let mut vec_type = returntype.to_string();
+            if returntype.len() > 5 && &returntype[..3] == "Vec" {
+                vec_type = returntype[4..returntype.len() - 1].to_string();
+            }
+            format!("Vec::<{}>::new()", vec_type)
+        }
+    }
+
+    fn inbuilt_function_format(
+        &self,
+        _name: &str,
+        _returntype: &str,
+        default_format: &str,
+    ) -> String {
+        default_format.to_string()
+    }
+
+    fn inbuilt_function_def(&self, name: &str) -> String {
+        format!("fn {}() ->{{ /* stuff */ }}", name)
+    }
+
+    fn function_def(
+        &self,
+        name: &str,
+        argnames: &[String],
+        argtypes: &[String],
+        returntype: &str,
+    ) -> String {
+        let empty_arg_modifiers = argnames.iter().map(|_s| String::new()).collect();
+        let args = formatting::get_formatted_argname_argtype_pairs(
+            &argnames.to_vec(),
+            &argtypes.to_vec(),
+            &empty_arg_modifiers,
+        );
+        format!("fn {}({}) -> {} {{\r\n", name, args, returntype)
+    }
+
+    fn function_arg(&self, arg: &str, argtype: &str) -> String {
+        // functions are passed as a reference to a closure, e.g. &dyn Fn(i64) -> i64
+        if argtype.contains("&dyn Fn") {
+            format!("&{}", arg)
+        } else {
+            arg.to_string()
+        }
+    }
+
+    fn loop_for_range(&self, name: &str, from: usize, to: usize) -> String {
+        format!("for {} in {}..{} {{\r\n", name, from, to)
+    }
+
+    fn println(&self, output: &str, is_debug: bool) -> String {
+        if is_debug {
+            format!("println!(\"{{:?}}\", &{})", output)
+        } else {
+            format!("println!(\"{{}}\", {})", output)
+        }
+    }
+
+    fn if_expression(
+        &self,
+        condition: &str,
+        true_expression: &str,
+        false_expression: &str,
+        indent: &str,
+        outdent: &str,
+    ) -> String {
+        format!(
+            "if {} {{\r\n{}{}\r\n{}}} else {{\r\n{}{}\r\n{}}}",
+            condition, indent, true_expression, outdent, indent, false_expression, outdent
+        )
+    }
+
+    fn struct_new(&self, name: &str, args: &[String]) -> String {
+        format!("{}::new({})", name, args.join(", "))
+    }
+
+    fn premain_struct(&self, name: &str, keys: &[String], types: &[String]) -> String {
+        let mut struct_pub_keys_types = "".to_string();
+        let mut struct_new_fn_keys_types = "".to_string();
+        let mut struct_new_fn_keys = "".to_string();
+        for (key, a_type) in keys.iter().zip(types) {
+            struct_pub_keys_types = format!(
+                "{}    pub {}: {},\r\n",
+                &struct_pub_keys_types, &key, &a_type
+            );
+            struct_new_fn_keys_types = format!(
+                "{}        {}: {},\r\n",
+                &struct_new_fn_keys_types, &key, &a_type
+            );
+            struct_new_fn_keys = format!("{}            {},\r\n", &struct_new_fn_keys, &key);
+        }
+        let derive = "#[derive(Clone, Debug)]\r\n".to_string();
+        let a_struct = format!(
+            "pub struct {} {{\r\n{}}}\r\n\r\n",
+            name, struct_pub_keys_types
+        );
+        let new_fn = format!(
+            "    pub fn new(\r\n{}) -> {} {{\r\n        {} {{\r\n{}        }}\r\n    }}",
+            struct_new_fn_keys_types, name, name, struct_new_fn_keys
+        );
+        format!(
+            "{}{}impl Newstruct {{\r\n{}\r\n}}\r\n\r\n",
+            derive, a_struct, new_fn
+        )
+    }
+}
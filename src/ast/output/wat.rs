@@ -0,0 +1,337 @@
+/*! Outputs a WebAssembly text module, i.e. `--target wat`, for the numeric subset of toylang
+
+    WebAssembly needs the type of every value, and functions can't be nested,
+    so the AST is walked separately here, rather than by a [Backend](super::Backend).
+
+    - i64 and f64 are the same in WebAssembly, and a bool is an i32 of 0 or 1
+    - each FunctionDef is output as a function exported with the same name, wherever it was defined
+    - the other lines are output in an exported `main` function, with the constants as its locals
+    - `@` calls a function imported from the host, e.g. `env.print_i64`, which is only imported if it is used
+
+    Anything else, e.g. strings, lists, structs or loops, is a compile error.
+
+    ```text
+    (module
+      (func $double (export "double") (param $x i64) (result i64)
+        (i64.mul (local.get $x) (i64.const 2)))
+      (func $main (export "main")
+        (drop (call $double (i64.const 3))))
+    )
+    ```
+*/
+use crate::ast::elements::{ElIndex, ElementInfo};
+use crate::ast::output::{get_element_type, get_value_children};
+use crate::diagnostic::{Diagnostic, Span};
+use crate::errors::ERRORS;
+use crate::Compiler;
+
+/// A function being output, with everything which needs declaring at its start
+struct Function {
+    signature: String,
+    /// the params and locals, which are the only names in scope
+    names: Vec<String>,
+    locals: Vec<String>,
+    body: Vec<String>,
+}
+
+impl Function {
+    fn new(signature: String, names: Vec<String>) -> Function {
+        Function {
+            signature,
+            names,
+            locals: vec![],
+            body: vec![],
+        }
+    }
+
+    fn get_output(&self) -> String {
+        let lines: Vec<String> = self
+            .locals
+            .iter()
+            .chain(&self.body)
+            .map(|line| format!("\r\n    {}", line))
+            .collect();
+        format!("  {}{})\r\n", self.signature, lines.concat())
+    }
+}
+
+struct Module<'a> {
+    compiler: &'a Compiler,
+    functions: Vec<String>,
+    /// the types which are printed, e.g. "i64" for `env.print_i64`
+    print_types: Vec<String>,
+    /// the Elements which can't be output
+    errors: Vec<ElIndex>,
+}
+
+/// Sets the output to the WebAssembly text module, or adds an error for each Element which isn't supported
+pub fn set_output(compiler: &mut Compiler) {
+    compiler.ast.log(format!("wat::set_output {:?}", ""));
+    let mut module = Module {
+        compiler: &*compiler,
+        functions: vec![],
+        print_types: vec![],
+        errors: vec![],
+    };
+    let mut main = Function::new("(func $main (export \"main\")".to_string(), vec![]);
+    let root_children = compiler.ast.elements[0].1.clone();
+    module.add_block(&mut main, &root_children, false);
+    module.functions.push(main.get_output());
+
+    let imports: Vec<String> = module
+        .print_types
+        .iter()
+        .map(|a_type| {
+            format!(
+                "  (import \"env\" \"print_{}\" (func $print_{} (param {})))\r\n",
+                a_type,
+                a_type,
+                get_type(a_type).unwrap_or("i64")
+            )
+        })
+        .collect();
+    let output = format!(
+        "(module\r\n{}{})\r\n",
+        imports.concat(),
+        module.functions.concat()
+    );
+    let errors = module.errors;
+    for el_index in errors {
+        let span = Span::from(&compiler.ast.element_tokens[el_index]);
+        let error = Diagnostic::error(ERRORS.wat_unsupported, span);
+        compiler.error_stack.push(error);
+    }
+    compiler.ast.output = output;
+    compiler.ast.output_map = vec![];
+    compiler.ast.source_map = vec![];
+}
+
+impl Module<'_> {
+    /// Outputs each line of a function body, or of main. Only a function has a result, its last line
+    fn add_block(&mut self, function: &mut Function, children: &[ElIndex], has_result: bool) {
+        let children = get_value_children(self.compiler, children);
+        for (i, &child_ref) in children.iter().enumerate() {
+            let is_result = has_result && i == children.len() - 1;
+            if let Err(el_index) = self.add_line(function, child_ref, is_result) {
+                self.errors.push(el_index);
+            }
+        }
+    }
+
+    fn add_line(
+        &mut self,
+        function: &mut Function,
+        el_index: ElIndex,
+        is_result: bool,
+    ) -> Result<(), ElIndex> {
+        let compiler = self.compiler;
+        let (el_info, all_children) = &compiler.ast.elements[el_index];
+        let children = get_value_children(compiler, all_children);
+        match el_info {
+            // a function can't be defined inside another, but it can't use anything from its parent either
+            ElementInfo::FunctionDef(name, argnames, argtypes, returntype) => {
+                let mut signature = format!("(func ${} (export \"{}\")", name, name);
+                for (argname, argtype) in argnames.iter().zip(argtypes) {
+                    let argtype = get_type(argtype).ok_or(el_index)?;
+                    signature = format!("{} (param ${} {})", signature, argname, argtype);
+                }
+                let returntype = get_type(returntype).ok_or(el_index)?;
+                signature = format!("{} (result {})", signature, returntype);
+                let mut new_function = Function::new(signature, argnames.clone());
+                self.add_block(&mut new_function, all_children, true);
+                self.functions.push(new_function.get_output());
+            }
+            ElementInfo::Assignment => {
+                let constant_ref = *children.first().ok_or(el_index)?;
+                match &compiler.ast.elements[constant_ref] {
+                    (ElementInfo::Constant(name, returntype), constant_children) => {
+                        let value_children = get_value_children(compiler, constant_children);
+                        let value_ref = *value_children.first().ok_or(constant_ref)?;
+                        let value = self.get_expression(function, value_ref)?;
+                        let returntype = get_type(returntype).ok_or(constant_ref)?;
+                        function
+                            .locals
+                            .push(format!("(local ${} {})", name, returntype));
+                        function.names.push(name.clone());
+                        function
+                            .body
+                            .push(format!("(local.set ${} {})", name, value));
+                    }
+                    _ => return Err(constant_ref),
+                }
+            }
+            ElementInfo::Println => {
+                let value_ref = *children.first().ok_or(el_index)?;
+                let value = self.get_expression(function, value_ref)?;
+                let returntype = get_element_type(compiler, value_ref);
+                get_type(&returntype).ok_or(value_ref)?;
+                if !self.print_types.contains(&returntype) {
+                    self.print_types.push(returntype.clone());
+                }
+                function
+                    .body
+                    .push(format!("(call $print_{} {})", returntype, value));
+            }
+            // the assignment of a function
+            ElementInfo::Unused => (),
+            _ => {
+                let value = self.get_expression(function, el_index)?;
+                if is_result {
+                    function.body.push(value);
+                } else {
+                    function.body.push(format!("(drop {})", value));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The folded instructions for an expression, or the Element which isn't supported
+    fn get_expression(&self, function: &Function, el_index: ElIndex) -> Result<String, ElIndex> {
+        let ast = &self.compiler.ast;
+        let (el_info, children) = &ast.elements[el_index];
+        let children = get_value_children(self.compiler, children);
+        match el_info {
+            // ints used as return expressions have "as i64" appended for the Rust output
+            ElementInfo::Int(value) => {
+                Ok(format!("(i64.const {})", value.trim_end_matches(" as i64")))
+            }
+            ElementInfo::Float(value) => Ok(format!("(f64.const {})", value)),
+            ElementInfo::Bool(value) => Ok(get_bool(value == "true")),
+            ElementInfo::ConstantRef(name, _, _) | ElementInfo::Arg(name, _, _, _) => {
+                if function.names.contains(name) {
+                    Ok(format!("(local.get ${})", name))
+                } else {
+                    Err(el_index)
+                }
+            }
+            ElementInfo::InbuiltFunctionCall(name, _, _) => {
+                self.get_inbuilt_function_call(function, el_index, name, &children)
+            }
+            ElementInfo::FunctionCall(name, false, _) => {
+                let mut output = format!("(call ${}", name);
+                for child_ref in children {
+                    output = format!("{} {}", output, self.get_expression(function, child_ref)?);
+                }
+                Ok(format!("{})", output))
+            }
+            ElementInfo::If(_) if children.len() == 3 => {
+                let returntype = get_element_type(self.compiler, el_index);
+                Ok(format!(
+                    "(if (result {}) {} (then {}) (else {}))",
+                    get_type(&returntype).ok_or(el_index)?,
+                    self.get_expression(function, children[0])?,
+                    self.get_expression(function, children[1])?,
+                    self.get_expression(function, children[2])?
+                ))
+            }
+            ElementInfo::Parens if children.len() == 1 => {
+                self.get_expression(function, children[0])
+            }
+            _ => Err(el_index),
+        }
+    }
+
+    fn get_inbuilt_function_call(
+        &self,
+        function: &Function,
+        el_index: ElIndex,
+        name: &str,
+        children: &[ElIndex],
+    ) -> Result<String, ElIndex> {
+        match name {
+            "true" => return Ok(get_bool(true)),
+            "false" => return Ok(get_bool(false)),
+            _ => (),
+        }
+        let first_child_ref = *children.first().ok_or(el_index)?;
+        let argtype = get_element_type(self.compiler, first_child_ref);
+        let argtype = get_type(&argtype).ok_or(el_index)?;
+        // ints are signed, and bools are compared like unsigned ints
+        let instruction = match (name, argtype) {
+            ("+", "i64" | "f64") => "add",
+            ("-", "i64" | "f64") => "sub",
+            ("*", "i64" | "f64") => "mul",
+            ("/", "i64") => "div_s",
+            ("/", "f64") => "div",
+            ("%", "i64") => "rem_s",
+            ("==", _) => "eq",
+            ("!=", _) => "ne",
+            ("<", "i64") => "lt_s",
+            ("<", "f64") => "lt",
+            ("<", _) => "lt_u",
+            (">", "i64") => "gt_s",
+            (">", "f64") => "gt",
+            (">", _) => "gt_u",
+            ("<=", "i64") => "le_s",
+            ("<=", "f64") => "le",
+            ("<=", _) => "le_u",
+            (">=", "i64") => "ge_s",
+            (">=", "f64") => "ge",
+            (">=", _) => "ge_u",
+            _ => return Err(el_index),
+        };
+        let mut output = format!("({}.{}", argtype, instruction);
+        for &child_ref in children {
+            output = format!("{} {}", output, self.get_expression(function, child_ref)?);
+        }
+        Ok(format!("{})", output))
+    }
+}
+
+/// The WebAssembly type of a toylang type, if it is supported
+fn get_type(a_type: &str) -> Option<&'static str> {
+    match a_type {
+        "i64" => Some("i64"),
+        "f64" => Some("f64"),
+        "bool" => Some("i32"),
+        _ => None,
+    }
+}
+
+fn get_bool(value: bool) -> String {
+    format!("(i32.const {})", if value { 1 } else { 0 })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::output::Target;
+    use crate::{Compiler, Options};
+
+    fn compile(text: &str) -> Compiler {
+        let options = Options {
+            target: Target::Wat,
+            ..Options::default()
+        };
+        crate::compile(text, options)
+    }
+
+    #[test]
+    fn test_set_output() {
+        let test_case_passes = vec![
+            (
+                "= fibonacci \\ i64 i64 n => ? < n 2 1 + fibonacci - n 1 fibonacci - n 2\r\n@ fibonacci 10",
+                "(module\r\n  (import \"env\" \"print_i64\" (func $print_i64 (param i64)))\r\n  (func $fibonacci (export \"fibonacci\") (param $n i64) (result i64)\r\n    (if (result i64) (i64.lt_s (local.get $n) (i64.const 2)) (then (i64.const 1)) (else (i64.add (call $fibonacci (i64.sub (local.get $n) (i64.const 1))) (call $fibonacci (i64.sub (local.get $n) (i64.const 2)))))))\r\n  (func $main (export \"main\")\r\n    (call $print_i64 (call $fibonacci (i64.const 10))))\r\n)\r\n",
+            ),
+            (
+                "= a * 1.5 2.0\r\n= b > a 2.5\r\n= c + 7 -2",
+                "(module\r\n  (func $main (export \"main\")\r\n    (local $a f64)\r\n    (local $b i32)\r\n    (local $c i64)\r\n    (local.set $a (f64.mul (f64.const 1.5) (f64.const 2.0)))\r\n    (local.set $b (f64.gt (local.get $a) (f64.const 2.5)))\r\n    (local.set $c (i64.add (i64.const 7) (i64.const -2))))\r\n)\r\n",
+            ),
+        ];
+        for (input, expected) in test_case_passes {
+            let compiler = compile(input);
+            assert!(compiler.error_stack.is_empty());
+            assert_eq!(compiler.ast.output, expected);
+        }
+    }
+
+    #[test]
+    fn test_set_output_unsupported() {
+        let compiler = compile("= a 1\r\n= b \"string\"\r\n@ b");
+        assert_eq!(compiler.error_stack.len(), 2);
+        assert_eq!(
+            compiler.get_rendered_errors()[0],
+            "----------\r\nerror[T0051] ./src/:2:0\r\n= b \"string\"\r\n    ^^^^^^^^ Not supported by --target wat - only i64, f64 and bool values, arithmetic, comparisons, If, and functions can be output as WebAssembly"
+        );
+    }
+}
@@ -34,8 +34,41 @@
     ----------
     ```
 
+    ### Warnings
+    After parsing without errors, a lint pass prints warnings, e.g. for unused constants, which don't stop the output being saved.
+    Each lint can be allowed, or denied to make it an error, see [lint][crate::lint]
+
+    ```bash
+    toylang -i test.toy --allow unused_constants --deny unreachable_code
+    toylang -i test.toy --deny warnings
+    ```
+
     ### Output file
     Or on success a compiled `output.rs` file will be saved to the output directory.
+    It is first parsed with syn, and if the generated Rust is invalid it isn't saved, and a TOYLANG INTERNAL ERROR shows which element caused it.
+    Otherwise it is pretty printed with prettyplease before saving, see [pretty_print][crate::formatting::pretty_print].
+
+    ### Source map
+    An `output.rs.map` JSON file is saved next to `output.rs`. It maps each range of the generated code (lines from 1, columns from 0)
+    to the token of the toylang file it came from (the same row, start and end as the `--tokens` output), e.g. for coverage, profiling or debugger tools.
+
+    ```json
+    {"file":"output.rs","source":"test.toy","mappings":[{"generated_line":2,"generated_column":4,"generated_end_line":2,"generated_end_column":17,"row":0,"start":0,"end":0,"token":"="}]}
+    ```
+
+    ### --target
+    The language of the output, `rust` by default, `js` to save an `output.js` instead, which can be run with Node or in a browser,
+    or `py` to save an `output.py`, which can be run with Python 3.9+ or pasted into a notebook.
+    `wat` saves a WebAssembly text module as `output.wat`, but only for i64, f64 and bool values, arithmetic, comparisons, If and functions.
+    `c` saves a single `output.c`, which builds with any C compiler, for the same subset as `wat`, plus loops and printing.
+    Only the Rust output is validated and pretty printed.
+
+    ```bash
+    toylang -i test.toy --target js
+    node output.js
+    toylang -i test.toy --target py
+    python output.py
+    ```
 
     ### Compiling the output file
     You can then build THAT file with cargo as needed, i.e. output to `src\\bin` and add this to the "Cargo.toml"
@@ -58,12 +91,51 @@
     ```
 
     And your final compiled `output.exe` will be saved to, and run from `\\target\\debug` or `\\target\\release`
+
+    ### -l --lsp
+    Run as a Language Server over stdio instead, see [lsp][crate::lsp]
+
+    ```bash
+    toylang --lsp
+    ```
+
+    ### definition / references
+    Print the location of the definition of, or all references to, the constant, arg or function at a 1-based line and column, see [definition][crate::lsp::definition]
+
+    ```bash
+    toylang definition test.toy 3 5
+    toylang references test.toy 1 3
+    ```
+
+    ### run
+    Compile the toylang file, then build and run the output with rustc in one step, see [rustc_runner][crate::rustc_runner]
+
+    ```bash
+    toylang run test.toy
+    ```
+
+    ### fmt
+    Format a toylang file in place, or just check it is already formatted with `--check`, see [source_formatter][crate::source_formatter]
+
+    ```bash
+    toylang fmt test.toy
+    toylang fmt test.toy --check
+    ```
+
+    ### explain
+    Print a long-form explanation of the compile error with a code, e.g. T0038, with an example of code which causes it and its fix, see [explain][crate::explain]
+
+    ```bash
+    toylang explain T0038
+    ```
 */
 
-use crate::Compiler;
+use crate::lint::LintLevels;
+use crate::{Compiler, Target};
 use std::process;
 
 /// Only function for compiler_runner
+#[allow(clippy::too_many_arguments)]
 pub fn main(
     input: String,
     debug: bool,
@@ -71,11 +143,21 @@ pub fn main(
     nosave: bool,
     tokens: bool,
     code: bool,
+    target: Target,
+    lints: LintLevels,
 ) {
-    let mut compiler = Compiler::new(input, debug, output, nosave, tokens).unwrap_or_else(|err| {
+    if !tokens {
+        println!("\r\nOUTPUT: {:?}", &output);
+    }
+    if debug {
+        println!("DEBUG:  true");
+    }
+    let mut compiler = Compiler::new(input, debug, output, nosave).unwrap_or_else(|err| {
         println!("Problem parsing arguments: {}", err);
         process::exit(1);
     });
+    compiler.ast.target = target;
+    compiler.lints = lints;
 
     if let Err(e) = compiler.run(tokens, code) {
         println!("Application error: {}", e);
@@ -337,12 +337,10 @@ fn init(
     ui.rich_text_control_set_text(&ui.richtext_logs, " ");
     //ui.rich_text_control_set_text(&ui.richtext_tree, " ");
     ui.rich_text_control_set_text(&ui.richtext_output, " ");
-    return Compiler::new(input.clone(), debug, output.clone(), true, false).unwrap_or_else(
-        |err| {
-            println!("Problem parsing arguments: {}", err);
-            process::exit(1);
-        },
-    );
+    return Compiler::new(input.clone(), debug, output.clone(), true).unwrap_or_else(|err| {
+        println!("Problem parsing arguments: {}", err);
+        process::exit(1);
+    });
 }
 
 pub fn run(input: String, debug: bool, output: Option<String>) {
@@ -0,0 +1,320 @@
+/*! Compile errors, as found by the Compiler and returned by [compile_str](crate::compile_str)
+
+    Each Diagnostic has the stable code of its error, e.g. T0038, its severity, the Span of the Token it was found at,
+    and optionally labels at other Tokens, notes, and suggestions of what may have been meant, so tools can show it in their own way, e.g. the `--tokens` JSON,
+    or render it as text like the CLI does:
+
+    ```text
+    ----------
+    error[T0038] ./src/test.toy:2:0
+    = a 456
+      ^ Constants are immutable. You may be trying to assign a value to a constant that has already been defined. Try renaming this as a new constant.
+    ```
+*/
+use crate::errors::ErrorMessage;
+use crate::{End, Row, Start, Token};
+use serde::Serialize;
+use std::fmt;
+
+/// Where a Diagnostic is in the source, the same as its Token, i.e. 0-based, and the end is inclusive
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Span {
+    pub line: Row,
+    pub start: Start,
+    pub end: End,
+}
+
+impl From<&Token> for Span {
+    fn from(token: &Token) -> Span {
+        Span {
+            line: token.1,
+            start: token.2,
+            end: token.3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A line of chars as text, with the 0-based line of the file it starts on, since lines of chars are also split after "=>",
+/// and a multi-line string keeps several lines of the file in one line of chars
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SourceLine {
+    pub text: String,
+    pub file_line: usize,
+}
+
+/// A secondary Span which explains the Diagnostic, e.g. where a constant was first defined
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+/// A compile error or warning
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub code: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    /// the Token it was found at
+    pub span: Span,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    /// similar names which may have been meant, e.g. for an undefined constant
+    pub suggestions: Vec<String>,
+}
+
+impl Diagnostic {
+    pub(crate) fn error(error: ErrorMessage, span: Span) -> Diagnostic {
+        Diagnostic {
+            code: error.code,
+            severity: Severity::Error,
+            message: error.message.to_string(),
+            span,
+            labels: vec![],
+            notes: vec![],
+            suggestions: vec![],
+        }
+    }
+
+    pub(crate) fn warning(error: ErrorMessage, span: Span) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(error, span)
+        }
+    }
+
+    pub fn with_label(mut self, span: Span, message: &str) -> Diagnostic {
+        self.labels.push(Label {
+            span,
+            message: message.to_string(),
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: &str) -> Diagnostic {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    /// Renders it as the CLI prints it, with carets under the Token it was found at, and dashes under any labels.
+    /// The lines are the Compiler's lines_of_chars, which the Span rows refer to
+    pub fn render(&self, filename: &str, lines: &[SourceLine]) -> String {
+        let no_line = SourceLine::default();
+        let get_line = |row: Row| lines.get(row).unwrap_or(&no_line);
+        let mut text = format!(
+            "----------\r\n{}[{}] {}",
+            self.severity,
+            self.code,
+            render_span(
+                filename,
+                get_line(self.span.line),
+                &self.span,
+                '^',
+                &self.message
+            )
+        );
+        for label in &self.labels {
+            text.push_str("\r\n");
+            text.push_str(&render_span(
+                filename,
+                get_line(label.span.line),
+                &label.span,
+                '-',
+                &label.message,
+            ));
+        }
+        for note in &self.notes {
+            text.push_str(&format!("\r\nnote: {}", note));
+        }
+        if let Some(help) = self.get_help() {
+            text.push_str(&format!("\r\nhelp: {}", help));
+        }
+        text
+    }
+
+    /// The suggestions as a question, e.g. "did you mean `monkey`?"
+    pub fn get_help(&self) -> Option<String> {
+        if self.suggestions.is_empty() {
+            return None;
+        }
+        let names: Vec<String> = self
+            .suggestions
+            .iter()
+            .map(|name| format!("`{}`", name))
+            .collect();
+        Some(format!("did you mean {}?", names.join(" or ")))
+    }
+}
+
+/// A short form without the source, e.g. "error[T0038]: Constants are immutable..."
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.code, self.message)
+    }
+}
+
+/// The location, the line of source, and a marker under the Span followed by the message.
+/// Spans start after the leading whitespace of the line, like Tokens.
+/// A line which contains a multi-line string is only shown from the line break before the start of the Span,
+/// up to the next line break, where the marker also stops, and the location is the line of the file it is on
+pub(crate) fn render_span(
+    filename: &str,
+    line: &SourceLine,
+    span: &Span,
+    marker: char,
+    message: &str,
+) -> String {
+    let chars: Vec<char> = line.text.chars().collect();
+    let leading_whitespace = chars.iter().take_while(|c| c.is_whitespace()).count();
+    let start = leading_whitespace + span.start;
+    let mut end = leading_whitespace + span.end;
+    let is_line_break = |c: &char| *c == '\r' || *c == '\n';
+    let line_start = chars[..start.min(chars.len())]
+        .iter()
+        .rposition(is_line_break)
+        .map_or(0, |index| index + 1);
+    if let Some(line_end) = chars[line_start..].iter().position(is_line_break) {
+        end = end
+            .min((line_start + line_end).saturating_sub(1))
+            .max(start);
+    }
+    let shown_line: String = chars[line_start..]
+        .iter()
+        .take_while(|c| !is_line_break(c))
+        .collect();
+    let line_breaks_before_span = chars[..line_start].iter().filter(|&&c| c == '\n').count();
+    format!(
+        "./src/{}:{}:0\r\n{}\r\n{}{} {}",
+        filename,
+        line.file_line + line_breaks_before_span + 1,
+        shown_line,
+        " ".repeat(start - line_start),
+        marker.to_string().repeat(end + 1 - start),
+        message,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ERRORS;
+
+    /// Lines of chars which are each one line of the file
+    fn get_lines(texts: &[&str]) -> Vec<SourceLine> {
+        texts
+            .iter()
+            .enumerate()
+            .map(|(file_line, text)| SourceLine {
+                text: text.to_string(),
+                file_line,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render() {
+        let lines = get_lines(&["= a 123", "= a 456"]);
+        let span = Span {
+            line: 1,
+            start: 2,
+            end: 2,
+        };
+        let first = Span {
+            line: 0,
+            start: 2,
+            end: 2,
+        };
+        let diagnostic = Diagnostic::error(ERRORS.constants_are_immutable, span)
+            .with_label(first, "first defined here")
+            .with_note("try = b 456");
+        assert_eq!(diagnostic.render("test.toy", &lines), "----------\r\nerror[T0038] ./src/test.toy:2:0\r\n= a 456\r\n  ^ Constants are immutable. You may be trying to assign a value to a constant that has already been defined. Try renaming this as a new constant.\r\n./src/test.toy:1:0\r\n= a 123\r\n  - first defined here\r\nnote: try = b 456");
+        assert_eq!(diagnostic.to_string(), "error[T0038]: Constants are immutable. You may be trying to assign a value to a constant that has already been defined. Try renaming this as a new constant.");
+    }
+
+    #[test]
+    fn test_render_multi_line_string() {
+        let lines = vec![
+            SourceLine {
+                text: "= a \"\"\"line 1\r\nbad \\q\"\"\"".to_string(),
+                file_line: 0,
+            },
+            SourceLine {
+                text: "@ zz".to_string(),
+                file_line: 2,
+            },
+        ];
+        let span = Span {
+            line: 0,
+            start: 19,
+            end: 20,
+        };
+        let diagnostic = Diagnostic::error(ERRORS.string_escape, span);
+        assert!(diagnostic.render("test.toy", &lines).starts_with(
+            "----------\r\nerror[T0060] ./src/test.toy:2:0\r\nbad \\q\"\"\"\r\n    ^^ Invalid string escape"
+        ));
+        let span = Span {
+            line: 0,
+            start: 4,
+            end: 23,
+        };
+        let diagnostic = Diagnostic::error(ERRORS.string, span);
+        assert!(diagnostic
+            .render("test.toy", &lines)
+            .contains("./src/test.toy:1:0\r\n= a \"\"\"line 1\r\n    ^^^^^^^^^ Invalid string"));
+
+        // the line after the string is the line of the file after it, not the next line of chars
+        let span = Span {
+            line: 1,
+            start: 2,
+            end: 3,
+        };
+        let diagnostic = Diagnostic::error(ERRORS.constant_undefined, span);
+        assert!(diagnostic
+            .render("test.toy", &lines)
+            .contains("./src/test.toy:3:0\r\n@ zz\r\n  ^^ "));
+    }
+
+    #[test]
+    fn test_render_suggestions() {
+        let lines = get_lines(&["= monkey 1", "@ monkeys"]);
+        let span = Span {
+            line: 1,
+            start: 2,
+            end: 8,
+        };
+        let mut diagnostic = Diagnostic::error(ERRORS.constant_undefined, span);
+        assert_eq!(diagnostic.get_help(), None);
+        diagnostic.suggestions = vec!["monkey".to_string(), "monkeyz".to_string()];
+        assert!(diagnostic.render("test.toy", &lines).ends_with(&format!(
+            "\r\n@ monkeys\r\n  ^^^^^^^ {}\r\nhelp: did you mean `monkey` or `monkeyz`?",
+            ERRORS.constant_undefined.message
+        )));
+    }
+
+    #[test]
+    fn test_json() {
+        let span = Span {
+            line: 1,
+            start: 2,
+            end: 2,
+        };
+        let diagnostic = Diagnostic::error(ERRORS.int, span);
+        assert_eq!(serde_json::to_string(&diagnostic).unwrap(), "{\"code\":\"T0032\",\"severity\":\"error\",\"message\":\"Invalid int: there are characters after the first digit. Must only contain digits\",\"span\":{\"line\":1,\"start\":2,\"end\":2},\"labels\":[],\"notes\":[],\"suggestions\":[]}");
+    }
+}
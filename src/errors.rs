@@ -3,163 +3,280 @@
  * It mainly is a central store of compiler errors, but with some functions to check if an Element is an invalid child of another Element.
  * Still needs a lot of work
  */
-use crate::ast::elements::{get_last_element, Element, ElementInfo};
+use crate::ast::elements::{
+    get_ancestor_refs, get_constant_index_by_name, get_last_element, Element, ElementInfo,
+};
 use crate::ast::parents;
+use crate::diagnostic::{self, Diagnostic, Label, SourceLine, Span};
+use crate::Ast;
 use crate::Compiler;
-use crate::Tokens;
+use crate::Token;
+
+/// A compiler error message, with a stable code which doesn't change if the message is reworded, e.g. T0042
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ErrorMessage {
+    pub code: &'static str,
+    pub message: &'static str,
+}
+
+const fn error(code: &'static str, message: &'static str) -> ErrorMessage {
+    ErrorMessage { code, message }
+}
 
 #[derive(Clone, Debug)]
 pub struct Errors {
-    pub comment_single_line: &'static str,
-    pub comment_cant_be_child_of_assignment: &'static str,
-    pub comment_cant_be_child_of_constant: &'static str,
-    pub comment_cant_be_child_of_inbuiltfncall: &'static str,
-    pub comment_cant_be_child_of_fncall: &'static str,
-    pub comment_cant_be_child_of_parenthesis: &'static str,
-    pub int_cant_be_child_of_assignment: &'static str,
-    pub int_cant_be_child_of_parenthesis: &'static str,
-    pub float_cant_be_child_of_assignment: &'static str,
-    pub float_cant_be_child_of_parenthesis: &'static str,
-    pub string_cant_be_child_of_assignment: &'static str,
-    pub string_cant_be_child_of_parenthesis: &'static str,
-    pub constantref_cant_be_child_of_parenthesis: &'static str,
-    pub assignment_cant_be_child_of_constant: &'static str,
-    pub assignment_cant_be_child_of_inbuiltfncall: &'static str,
-    pub assignment_cant_be_child_of_fncal: &'static str,
-    pub assignment_cant_be_child_of_assignment: &'static str,
-    pub assignment_cant_be_child_of_parenthesis: &'static str,
-    pub assignment_cant_be_child_of_list: &'static str,
-    pub inbuiltfncall_cant_be_child_of_parenthesis: &'static str,
-    pub fncall_wrong_number_of_args: &'static str,
-    pub fncall_cant_be_child_of_parenthesis: &'static str,
-    pub parenthesis_cant_be_child_of_root: &'static str,
-    pub parenthesis_cant_be_child_of_constant: &'static str,
-    pub parenthesis_cant_be_child_of_assignment: &'static str,
-    pub println_cant_be_child_of_element: &'static str,
-    pub fndefwip_can_only_be_child_of_constant: &'static str,
-    pub string: &'static str,
-    pub assign: &'static str,
-    pub list: &'static str,
-    pub a_struct: &'static str,
-    pub int: &'static str,
-    pub int_out_of_bounds: &'static str,
-    pub int_negative: &'static str,
-    pub float: &'static str,
-    //pub typeerror: &'static str,
-    pub funcdef_args: &'static str,
-    pub funcdef_argtypes_first: &'static str,
-    //pub no_valid_assignment: &'static str,
-    //pub no_valid_integer_arithmetic: &'static str,
-    //pub no_valid_expression: &'static str,
-    pub constants_are_immutable: &'static str,
-    pub constant_undefined: &'static str,
-    pub struct_undefined: &'static str,
-    pub struct_edit_error: &'static str,
-    pub loop_for: &'static str,
-    pub loopfor_cant_be_child: &'static str,
-    pub loopfor_end_but_no_start: &'static str,
-    pub loopfor_malformed: &'static str,
-    pub list_cant_be_child: &'static str,
-    pub rustcode: &'static str,
-    pub impossible_error: &'static str,
-    pub issue_with_raw_rust_code: &'static str,
-    pub testing: &'static str,
+    pub comment_single_line: ErrorMessage,
+    pub comment_cant_be_child_of_assignment: ErrorMessage,
+    pub comment_cant_be_child_of_constant: ErrorMessage,
+    pub comment_cant_be_child_of_inbuiltfncall: ErrorMessage,
+    pub comment_cant_be_child_of_fncall: ErrorMessage,
+    pub comment_cant_be_child_of_parenthesis: ErrorMessage,
+    pub int_cant_be_child_of_assignment: ErrorMessage,
+    pub int_cant_be_child_of_parenthesis: ErrorMessage,
+    pub float_cant_be_child_of_assignment: ErrorMessage,
+    pub float_cant_be_child_of_parenthesis: ErrorMessage,
+    pub string_cant_be_child_of_assignment: ErrorMessage,
+    pub string_cant_be_child_of_parenthesis: ErrorMessage,
+    pub constantref_cant_be_child_of_parenthesis: ErrorMessage,
+    pub assignment_cant_be_child_of_constant: ErrorMessage,
+    pub assignment_cant_be_child_of_inbuiltfncall: ErrorMessage,
+    pub assignment_cant_be_child_of_fncal: ErrorMessage,
+    pub assignment_cant_be_child_of_assignment: ErrorMessage,
+    pub assignment_cant_be_child_of_parenthesis: ErrorMessage,
+    pub assignment_cant_be_child_of_list: ErrorMessage,
+    pub inbuiltfncall_cant_be_child_of_parenthesis: ErrorMessage,
+    pub fncall_wrong_number_of_args: ErrorMessage,
+    pub fncall_cant_be_child_of_parenthesis: ErrorMessage,
+    pub parenthesis_cant_be_child_of_root: ErrorMessage,
+    pub parenthesis_cant_be_child_of_constant: ErrorMessage,
+    pub parenthesis_cant_be_child_of_assignment: ErrorMessage,
+    pub println_cant_be_child_of_element: ErrorMessage,
+    pub fndefwip_can_only_be_child_of_constant: ErrorMessage,
+    pub string: ErrorMessage,
+    pub assign: ErrorMessage,
+    pub list: ErrorMessage,
+    pub a_struct: ErrorMessage,
+    pub int: ErrorMessage,
+    pub int_out_of_bounds: ErrorMessage,
+    pub int_negative: ErrorMessage,
+    pub float: ErrorMessage,
+    //pub typeerror: ErrorMessage,
+    pub funcdef_args: ErrorMessage,
+    pub funcdef_argtypes_first: ErrorMessage,
+    //pub no_valid_assignment: ErrorMessage,
+    //pub no_valid_integer_arithmetic: ErrorMessage,
+    //pub no_valid_expression: ErrorMessage,
+    pub constants_are_immutable: ErrorMessage,
+    pub constant_undefined: ErrorMessage,
+    pub struct_undefined: ErrorMessage,
+    pub struct_edit_error: ErrorMessage,
+    pub loop_for: ErrorMessage,
+    pub loopfor_cant_be_child: ErrorMessage,
+    pub loopfor_end_but_no_start: ErrorMessage,
+    pub loopfor_malformed: ErrorMessage,
+    pub list_cant_be_child: ErrorMessage,
+    pub rustcode: ErrorMessage,
+    pub impossible_error: ErrorMessage,
+    pub issue_with_raw_rust_code: ErrorMessage,
+    pub invalid_rust_output: ErrorMessage,
+    pub wat_unsupported: ErrorMessage,
+    pub c_unsupported: ErrorMessage,
+    pub parser_unknown: ErrorMessage,
+    pub inbuilt_undefined: ErrorMessage,
+    // warnings, found by the lint pass after parsing, see lint.rs
+    pub unused_constant: ErrorMessage,
+    pub unused_arg: ErrorMessage,
+    pub unused_function: ErrorMessage,
+    pub unreachable_code: ErrorMessage,
+    pub string_escape: ErrorMessage,
+    pub rustc_error: ErrorMessage,
+    pub testing: ErrorMessage,
 }
 
 pub const ERRORS: Errors = Errors {
-    comment_single_line: "Invalid single line comment: Must begin with two forward slashes '//'",
-    comment_cant_be_child_of_assignment: "Invalid Assignment - comment found instead of constant or function definition",
-    comment_cant_be_child_of_constant: "Invalid Constant Definition - comment found instead of value",
-    comment_cant_be_child_of_inbuiltfncall:"Invalid Inbuilt Function Call - comment found instead of value",
-    comment_cant_be_child_of_fncall:"Invalid Function Call - comment found instead of value",
-    comment_cant_be_child_of_parenthesis:"Invalid Parenthesis - comment found inside parenthesis",
-    int_cant_be_child_of_assignment:"Invalid Assignment - Int found  instead of constant or function definition",
-    int_cant_be_child_of_parenthesis:"Invalid parenthesis - Int found inside parenthesis. Can only include a type in a function definition, or a function name as a reference",
-    float_cant_be_child_of_assignment:"Invalid Assignment - Float found  instead of constant or function definition",
-    float_cant_be_child_of_parenthesis:"Invalid parenthesis - Float found inside parenthesis. Can only include a type in a function definition, or a function name as a reference",
-    string_cant_be_child_of_assignment:"Invalid Assignment - Float found  instead of constant or function definition",
-    string_cant_be_child_of_parenthesis:"Invalid parenthesis - Float found inside parenthesis. Can only include a type in a function definition, or a function name as a reference",
-    constantref_cant_be_child_of_parenthesis:"Invalid Constant Reference - only Types and Function names should be found inside parenthesis",
-    constant_undefined:"Invalid Constant Definition - this constant has not previously been defined, so cannot be used anywhere except in a new definition, e.g. = a 123",
-    struct_undefined:"Invalid Struct Definition - this struct has not previously been defined, so cannot be used anywhere except in a new definition, e.g. = a { = key 123 }",
-    struct_edit_error:"Invalid Struct Edit - it should be preceeded by an assignment, e.g. '= structname.keyname = newvalue'",
-    assignment_cant_be_child_of_constant:"Invalid Constant Definition - \"=\" can't be the value of this constant",
-    assignment_cant_be_child_of_inbuiltfncall:"Invalid Inbuilt Function Call - \"=\" found instead of value",
-    assignment_cant_be_child_of_fncal:"Invalid Function Call - \"=\" found instead of value",
-    assignment_cant_be_child_of_assignment: "Invalid Assignment - \"=\" found instead of constant or function definition",
-    assignment_cant_be_child_of_parenthesis:"Invalid parenthesis - \"=\" found inside parenthesis. Can only include a type in a function definition, or a function name as a reference",
-    assignment_cant_be_child_of_list:"Invalid list - you can't assign a value inside a list",
-    inbuiltfncall_cant_be_child_of_parenthesis:"Invalid parenthesis - inbuilt function call found inside parenthesis. Can only include a type in a function definition, or a function name as a reference",
-    fncall_wrong_number_of_args:"Invalid function call - wrong number of arguments found",
-    fncall_cant_be_child_of_parenthesis:"Invalid parenthesis - function call found inside parenthesis. Can only include a type in a function definition, or a function name as a reference",
-    parenthesis_cant_be_child_of_root:"Invalid parenthesis - parenthesis found at start of line. Can only use in a function definition, or a function name as a reference",
-    parenthesis_cant_be_child_of_constant:"Invalid parenthesis - parenthesis found as value of constant. Can only use in a function definition, or a function name as a reference",
-    parenthesis_cant_be_child_of_assignment:"Invalid parenthesis - parenthesis found as value of assignment. Can only use in a function definition, or a function name as a reference",
-    println_cant_be_child_of_element:"Invalid PrintLn - can't be used as child of this element",
-    fndefwip_can_only_be_child_of_constant:"Invalid Function Definition - \"\\\" found, which defines start of a function. Can only be used after a constant, i.e. = fn_name \\ i64 i64 arg1 : + arg1 123",
-    loopfor_cant_be_child:"Invalid For Loop - can't be placed here",
-    loopfor_end_but_no_start:"Invalid End For Loop found - can't find start of for loop",
-    loopfor_malformed:"Invalid For Loop - is missing key parts like variable name, start or end of range",
-    list_cant_be_child:"Invalid List - can't be placed here",
-    string: "Invalid string found: Must be enclosed in quote marks \"\"",
-    assign: "Invalid assignment: There are characters directly after '='. It must be followed by a space",
-    list: "Invalid list: List must be defined by elements(s) surrounded by [ ] with spaces between. An empty list must contain the type in the list like [ i64 ] . Types can't be values in a list, so they will be removed and the last found type will be assigned to the list returntype.",
-    a_struct: "Invalid struct: Struct must be defined by one or more assignments of a value to a key surrounded by { } with spaces between such as = mystruct { = key1 \"mystring\" = key2 12.34 }",
-    int: "Invalid int: there are characters after the first digit. Must only contain digits",
-    int_out_of_bounds: "Invalid int: is out of bounds. Must be within the value of -9223372036854775808 to 9223372036854775807",
-    int_negative:"Invalid negative int or float: Must follow a negative sign '-' with a digit",
-    float: "Invalid float",
+    comment_single_line: error("T0001", "Invalid single line comment: Must begin with two forward slashes '//'"),
+    comment_cant_be_child_of_assignment: error("T0002", "Invalid Assignment - comment found instead of constant or function definition"),
+    comment_cant_be_child_of_constant: error("T0003", "Invalid Constant Definition - comment found instead of value"),
+    comment_cant_be_child_of_inbuiltfncall: error("T0004", "Invalid Inbuilt Function Call - comment found instead of value"),
+    comment_cant_be_child_of_fncall: error("T0005", "Invalid Function Call - comment found instead of value"),
+    comment_cant_be_child_of_parenthesis: error("T0006", "Invalid Parenthesis - comment found inside parenthesis"),
+    int_cant_be_child_of_assignment: error("T0007", "Invalid Assignment - Int found  instead of constant or function definition"),
+    int_cant_be_child_of_parenthesis: error("T0008", "Invalid parenthesis - Int found inside parenthesis. Can only include a type in a function definition, or a function name as a reference"),
+    float_cant_be_child_of_assignment: error("T0009", "Invalid Assignment - Float found  instead of constant or function definition"),
+    float_cant_be_child_of_parenthesis: error("T0010", "Invalid parenthesis - Float found inside parenthesis. Can only include a type in a function definition, or a function name as a reference"),
+    string_cant_be_child_of_assignment: error("T0011", "Invalid Assignment - String found instead of constant or function definition"),
+    string_cant_be_child_of_parenthesis: error("T0012", "Invalid parenthesis - String found inside parenthesis. Can only include a type in a function definition, or a function name as a reference"),
+    constantref_cant_be_child_of_parenthesis: error("T0013", "Invalid Constant Reference - only Types and Function names should be found inside parenthesis"),
+    constant_undefined: error("T0039", "Invalid Constant Definition - this constant has not previously been defined, so cannot be used anywhere except in a new definition, e.g. = a 123"),
+    struct_undefined: error("T0040", "Invalid Struct Definition - this struct has not previously been defined, so cannot be used anywhere except in a new definition, e.g. = a { = key 123 }"),
+    struct_edit_error: error("T0041", "Invalid Struct Edit - it should be preceeded by an assignment, e.g. '= structname.keyname = newvalue'"),
+    assignment_cant_be_child_of_constant: error("T0014", "Invalid Constant Definition - \"=\" can't be the value of this constant"),
+    assignment_cant_be_child_of_inbuiltfncall: error("T0015", "Invalid Inbuilt Function Call - \"=\" found instead of value"),
+    assignment_cant_be_child_of_fncal: error("T0016", "Invalid Function Call - \"=\" found instead of value"),
+    assignment_cant_be_child_of_assignment: error("T0017", "Invalid Assignment - \"=\" found instead of constant or function definition"),
+    assignment_cant_be_child_of_parenthesis: error("T0018", "Invalid parenthesis - \"=\" found inside parenthesis. Can only include a type in a function definition, or a function name as a reference"),
+    assignment_cant_be_child_of_list: error("T0019", "Invalid list - you can't assign a value inside a list"),
+    inbuiltfncall_cant_be_child_of_parenthesis: error("T0020", "Invalid parenthesis - inbuilt function call found inside parenthesis. Can only include a type in a function definition, or a function name as a reference"),
+    fncall_wrong_number_of_args: error("T0021", "Invalid function call - wrong number of arguments found"),
+    fncall_cant_be_child_of_parenthesis: error("T0022", "Invalid parenthesis - function call found inside parenthesis. Can only include a type in a function definition, or a function name as a reference"),
+    parenthesis_cant_be_child_of_root: error("T0023", "Invalid parenthesis - parenthesis found at start of line. Can only use in a function definition, or a function name as a reference"),
+    parenthesis_cant_be_child_of_constant: error("T0024", "Invalid parenthesis - parenthesis found as value of constant. Can only use in a function definition, or a function name as a reference"),
+    parenthesis_cant_be_child_of_assignment: error("T0025", "Invalid parenthesis - parenthesis found as value of assignment. Can only use in a function definition, or a function name as a reference"),
+    println_cant_be_child_of_element: error("T0026", "Invalid PrintLn - can't be used as child of this element"),
+    fndefwip_can_only_be_child_of_constant: error("T0027", "Invalid Function Definition - \"\\\" found, which defines start of a function. Can only be used after a constant, i.e. = fn_name \\ i64 i64 arg1 => + arg1 123"),
+    loopfor_cant_be_child: error("T0043", "Invalid For Loop - can't be placed here"),
+    loopfor_end_but_no_start: error("T0044", "Invalid End For Loop found - can't find start of for loop"),
+    loopfor_malformed: error("T0045", "Invalid For Loop - is missing key parts like variable name, start or end of range"),
+    list_cant_be_child: error("T0046", "Invalid List - can't be placed here"),
+    string: error("T0028", "Invalid string found: Must be enclosed in quote marks \"\""),
+    assign: error("T0029", "Invalid assignment: There are characters directly after '='. It must be followed by a space"),
+    list: error("T0030", "Invalid list: List must be defined by elements(s) surrounded by [ ] with spaces between. An empty list must contain the type in the list like [ i64 ] . Types can't be values in a list, so they will be removed and the last found type will be assigned to the list returntype."),
+    a_struct: error("T0031", "Invalid struct: Struct must be defined by one or more assignments of a value to a key surrounded by { } with spaces between such as = mystruct { = key1 \"mystring\" = key2 12.34 }"),
+    int: error("T0032", "Invalid int: there are characters after the first digit. Must only contain digits"),
+    int_out_of_bounds: error("T0033", "Invalid int: is out of bounds. Must be within the value of -9223372036854775808 to 9223372036854775807"),
+    int_negative: error("T0034", "Invalid negative int or float: Must follow a negative sign '-' with a digit"),
+    float: error("T0035", "Invalid float"),
     //typeerror: "Invalid type",
-    funcdef_args: "Invalid Functional Definition - wrong number of argument types: should be 1 type for each arg, plus a return type.",
-    funcdef_argtypes_first:"Invalid Functional Definition - argument types should come before argument names.",
+    funcdef_args: error("T0036", "Invalid Functional Definition - wrong number of argument types: should be 1 type for each arg, plus a return type."),
+    funcdef_argtypes_first: error("T0037", "Invalid Functional Definition - argument types should come before argument names."),
     //no_valid_assignment: "No valid assignment found",
     //no_valid_integer_arithmetic: "No valid integer arithmetic found",
     //no_valid_expression: "No valid expression was found",
-    constants_are_immutable: "Constants are immutable. You may be trying to assign a value to a constant that has already been defined. Try renaming this as a new constant.",
-    loop_for:"Found character after \".\" For loops start with \"..\"",
-    rustcode: "Raw rust code can be entered after #. ONe # means inline rust code. two ## means pull this code out and prepend it at the top of the file before the main fn, e.g. ##a use std::io::{stdin, stdout, Write}; or #c /// document comment",
-    impossible_error: "Oh no, this error should be impossible... 'Well here's another nice mess you've gotten me into.'",
-    issue_with_raw_rust_code: "There is an issue with the raw rust code here - unhelpful error!",
-    testing: "error testing"
+    constants_are_immutable: error("T0038", "Constants are immutable. You may be trying to assign a value to a constant that has already been defined. Try renaming this as a new constant."),
+    loop_for: error("T0042", "Found character after \".\" For loops start with \"..\""),
+    rustcode: error("T0047", "Raw rust code can be entered after #. ONe # means inline rust code. two ## means pull this code out and prepend it at the top of the file before the main fn, e.g. ##a use std::io::{stdin, stdout, Write}; or #c /// document comment"),
+    impossible_error: error("T0048", "Oh no, this error should be impossible... 'Well here's another nice mess you've gotten me into.'"),
+    issue_with_raw_rust_code: error("T0049", "There is an issue with the raw rust code here - unhelpful error!"),
+    invalid_rust_output: error("T0050", "Internal error - the Rust code output for this element is invalid, so output.rs was not saved"),
+    wat_unsupported: error("T0051", "Not supported by --target wat - only i64, f64 and bool values, arithmetic, comparisons, If, and functions can be output as WebAssembly"),
+    c_unsupported: error("T0052", "Not supported by --target c - only i64, f64 and bool values, arithmetic, comparisons, If, loops, printing and functions can be output as C"),
+    parser_unknown: error("T0054", "parser - unknown error"),
+    inbuilt_undefined: error("T0055", "Invalid Inbuilt Function Call - there is no inbuilt function with this name, e.g. List::map"),
+    unused_constant: error("T0056", "Unused constant - this constant is never used. Remove it, or start its name with an underscore if that is intended, e.g. = _a 123"),
+    unused_arg: error("T0057", "Unused argument - this argument is never used in the body of its function. Remove it, or start its name with an underscore if that is intended, e.g. _x"),
+    unused_function: error("T0058", "Unused function - this function is never called, or passed to another function. Remove it, or start its name with an underscore if that is intended"),
+    unreachable_code: error("T0059", "Unreachable code - a function ends at its return expression, so this line after it is never run, but it is indented like the body of the function"),
+    string_escape: error("T0060", "Invalid string escape - a backslash in a string must be followed by one of \" \\ n r t, or u{..} with the 1 to 6 hex digits of a unicode character, e.g. \"tab\\there\" or \"\\u{1F600}\""),
+    rustc_error: error("T0061", "rustc found an error in the Rust code output for this element, so it can't be run - see the note for rustc's error"),
+    testing: error("T0053", "error testing"),
     };
 
-/// Adds an error to the compiler error_stack
-pub fn append_error(
+impl Errors {
+    /// Every error, e.g. to find one by its code
+    pub fn all(&self) -> Vec<ErrorMessage> {
+        vec![
+            self.comment_single_line,
+            self.comment_cant_be_child_of_assignment,
+            self.comment_cant_be_child_of_constant,
+            self.comment_cant_be_child_of_inbuiltfncall,
+            self.comment_cant_be_child_of_fncall,
+            self.comment_cant_be_child_of_parenthesis,
+            self.int_cant_be_child_of_assignment,
+            self.int_cant_be_child_of_parenthesis,
+            self.float_cant_be_child_of_assignment,
+            self.float_cant_be_child_of_parenthesis,
+            self.string_cant_be_child_of_assignment,
+            self.string_cant_be_child_of_parenthesis,
+            self.constantref_cant_be_child_of_parenthesis,
+            self.assignment_cant_be_child_of_constant,
+            self.assignment_cant_be_child_of_inbuiltfncall,
+            self.assignment_cant_be_child_of_fncal,
+            self.assignment_cant_be_child_of_assignment,
+            self.assignment_cant_be_child_of_parenthesis,
+            self.assignment_cant_be_child_of_list,
+            self.inbuiltfncall_cant_be_child_of_parenthesis,
+            self.fncall_wrong_number_of_args,
+            self.fncall_cant_be_child_of_parenthesis,
+            self.parenthesis_cant_be_child_of_root,
+            self.parenthesis_cant_be_child_of_constant,
+            self.parenthesis_cant_be_child_of_assignment,
+            self.println_cant_be_child_of_element,
+            self.fndefwip_can_only_be_child_of_constant,
+            self.string,
+            self.assign,
+            self.list,
+            self.a_struct,
+            self.int,
+            self.int_out_of_bounds,
+            self.int_negative,
+            self.float,
+            self.funcdef_args,
+            self.funcdef_argtypes_first,
+            self.constants_are_immutable,
+            self.constant_undefined,
+            self.struct_undefined,
+            self.struct_edit_error,
+            self.loop_for,
+            self.loopfor_cant_be_child,
+            self.loopfor_end_but_no_start,
+            self.loopfor_malformed,
+            self.list_cant_be_child,
+            self.rustcode,
+            self.impossible_error,
+            self.issue_with_raw_rust_code,
+            self.invalid_rust_output,
+            self.wat_unsupported,
+            self.c_unsupported,
+            self.parser_unknown,
+            self.inbuilt_undefined,
+            self.unused_constant,
+            self.unused_arg,
+            self.unused_function,
+            self.unreachable_code,
+            self.string_escape,
+            self.rustc_error,
+            self.testing,
+        ]
+    }
+
+    pub fn get_by_code(&self, code: &str) -> Option<ErrorMessage> {
+        self.all().into_iter().find(|error| error.code == code)
+    }
+}
+
+/// Adds an error to the compiler error_stack, at the current Token
+pub fn append_error(compiler: &mut Compiler, error: ErrorMessage) -> Result<(), ()> {
+    compiler
+        .ast
+        .log(format!("errors::append_error {:?}", error.code));
+    let token = &compiler.lines_of_tokens[compiler.current_line][compiler.current_line_token];
+    let diagnostic = Diagnostic::error(error, Span::from(token));
+    compiler.error_stack.push(diagnostic);
+    Err(())
+}
+
+/// Adds an error to the compiler error_stack, at part of the current Token, e.g. an invalid escape in a string.
+/// The start and end are relative to the start of the Token
+pub fn append_error_in_token(
     compiler: &mut Compiler,
-    mut arrow_indent: usize,
-    arrow_len: usize,
-    error: &str,
+    error: ErrorMessage,
+    start: usize,
+    end: usize,
 ) -> Result<(), ()> {
-    compiler.ast.log(format!(
-        "errors::append_error {:?} {:?} {:?}",
-        arrow_indent, arrow_len, error
-    ));
-    if arrow_indent == 0 && compiler.current_line_token != 0 {
-        let line_of_tokens = compiler.lines_of_tokens[compiler.current_line].clone();
-        arrow_indent = line_of_tokens[0..compiler.current_line_token]
-            .iter()
-            .cloned()
-            .collect::<Tokens>()
-            .len()
-            + compiler.current_line_token;
+    let result = append_error(compiler, error);
+    if let Some(error) = compiler.error_stack.last_mut() {
+        error.span.end = error.span.start + end;
+        error.span.start += start;
     }
+    result
+}
 
-    let e = format!(
-        "----------\r\n./src/{}:{}:0\r\n{}\r\n{}{} {}",
-        compiler.file.filename,
-        compiler.current_line + 1,
-        compiler.lines_of_chars[compiler.current_line]
-            .iter()
-            .map(|p| p.0)
-            .collect::<String>(),
-        " ".repeat(arrow_indent),
-        "^".repeat(arrow_len),
-        error,
-    );
-    let token =
-        compiler.lines_of_tokens[compiler.current_line][compiler.current_line_token].clone();
-    compiler.error_stack.push((e, token));
-    Err(())
+/// Formats an error in the same way as a rendered Diagnostic, but for any Token, e.g. for rustc errors found after compiling
+pub fn get_error_for_token(compiler: &Compiler, token: &Token, error: &str) -> String {
+    let line = compiler
+        .get_source_lines()
+        .get(token.1)
+        .cloned()
+        .unwrap_or_default();
+    get_error_for_line(&compiler.file.filename, &line, token, error)
+}
+
+/// Formats an error for a Token in a line of the source, e.g. in the vm, which only keeps the source, not the Compiler
+pub fn get_error_for_line(filename: &str, line: &SourceLine, token: &Token, error: &str) -> String {
+    format!(
+        "----------\r\n{}",
+        diagnostic::render_span(filename, line, &Span::from(token), '^', error)
+    )
 }
 
 /// Main function to check if parent of Element is invalid - this then calls all the other functions below
@@ -236,24 +353,24 @@ pub fn error_if_parent_is_invalid_for_list(
         ElementInfo::InbuiltFunctionCall(_, _, _) => Ok(()),
         ElementInfo::FunctionCall(_, _, _) => Ok(()),
         ElementInfo::If(_) => Ok(()),
-        ElementInfo::Struct(_, _, _) => append_error(compiler, 0, 1, ERRORS.struct_undefined), // list shouldn't be direct child it should be preceeded by Assignment and key name
+        ElementInfo::Struct(_, _, _) => append_error(compiler, ERRORS.struct_undefined), // list shouldn't be direct child it should be preceeded by Assignment and key name
         ElementInfo::StructEdit(_, _) => Ok(()),
-        ElementInfo::Parens => append_error(compiler, 0, 1, ERRORS.list_cant_be_child),
+        ElementInfo::Parens => append_error(compiler, ERRORS.list_cant_be_child),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Println => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Println => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -277,38 +394,33 @@ pub fn error_if_parent_is_invalid_for_rustcode(
         ElementInfo::Struct(_, _, _) => Ok(()),
         ElementInfo::StructEdit(_, _) => Ok(()),
         ElementInfo::Constant(_, _) => {
-            append_error(compiler, 0, 1, ERRORS.comment_cant_be_child_of_constant)
+            append_error(compiler, ERRORS.comment_cant_be_child_of_constant)
         }
         ElementInfo::Assignment => {
-            append_error(compiler, 0, 1, ERRORS.comment_cant_be_child_of_assignment)
+            append_error(compiler, ERRORS.comment_cant_be_child_of_assignment)
         }
-        ElementInfo::InbuiltFunctionCall(_, _, _) => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.comment_cant_be_child_of_inbuiltfncall,
-        ),
-        ElementInfo::FunctionCall(_, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.comment_cant_be_child_of_fncall)
+        ElementInfo::InbuiltFunctionCall(_, _, _) => {
+            append_error(compiler, ERRORS.comment_cant_be_child_of_inbuiltfncall)
         }
-        ElementInfo::Parens => {
-            append_error(compiler, 0, 1, ERRORS.comment_cant_be_child_of_parenthesis)
+        ElementInfo::FunctionCall(_, _, _) => {
+            append_error(compiler, ERRORS.comment_cant_be_child_of_fncall)
         }
+        ElementInfo::Parens => append_error(compiler, ERRORS.comment_cant_be_child_of_parenthesis),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Println => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Println => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -332,38 +444,33 @@ pub fn error_if_parent_is_invalid_for_commentsingleline(
         ElementInfo::Struct(_, _, _) => Ok(()),
         ElementInfo::StructEdit(_, _) => Ok(()),
         ElementInfo::Constant(_, _) => {
-            append_error(compiler, 0, 1, ERRORS.comment_cant_be_child_of_constant)
+            append_error(compiler, ERRORS.comment_cant_be_child_of_constant)
         }
         ElementInfo::Assignment => {
-            append_error(compiler, 0, 1, ERRORS.comment_cant_be_child_of_assignment)
+            append_error(compiler, ERRORS.comment_cant_be_child_of_assignment)
         }
-        ElementInfo::InbuiltFunctionCall(_, _, _) => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.comment_cant_be_child_of_inbuiltfncall,
-        ),
-        ElementInfo::FunctionCall(_, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.comment_cant_be_child_of_fncall)
+        ElementInfo::InbuiltFunctionCall(_, _, _) => {
+            append_error(compiler, ERRORS.comment_cant_be_child_of_inbuiltfncall)
         }
-        ElementInfo::Parens => {
-            append_error(compiler, 0, 1, ERRORS.comment_cant_be_child_of_parenthesis)
+        ElementInfo::FunctionCall(_, _, _) => {
+            append_error(compiler, ERRORS.comment_cant_be_child_of_fncall)
         }
+        ElementInfo::Parens => append_error(compiler, ERRORS.comment_cant_be_child_of_parenthesis),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Println => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Println => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -389,27 +496,23 @@ pub fn error_if_parent_is_invalid_for_int(
         ElementInfo::Println => Ok(()),
         ElementInfo::If(_) => Ok(()),
         ElementInfo::Struct(_, _, _) => Ok(()),
-        ElementInfo::StructEdit(_, _) => Ok(()), //append_error(compiler, 0, 1, ERRORS.struct_edit_error),
-        ElementInfo::Assignment => {
-            append_error(compiler, 0, 1, ERRORS.int_cant_be_child_of_assignment)
-        }
-        ElementInfo::Parens => {
-            append_error(compiler, 0, 1, ERRORS.int_cant_be_child_of_parenthesis)
-        }
+        ElementInfo::StructEdit(_, _) => Ok(()), //append_error(compiler, ERRORS.struct_edit_error),
+        ElementInfo::Assignment => append_error(compiler, ERRORS.int_cant_be_child_of_assignment),
+        ElementInfo::Parens => append_error(compiler, ERRORS.int_cant_be_child_of_parenthesis),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -436,26 +539,22 @@ pub fn error_if_parent_is_invalid_for_float(
         ElementInfo::If(_) => Ok(()),
         ElementInfo::Struct(_, _, _) => Ok(()),
         ElementInfo::StructEdit(_, _) => Ok(()),
-        ElementInfo::Assignment => {
-            append_error(compiler, 0, 1, ERRORS.float_cant_be_child_of_assignment)
-        }
-        ElementInfo::Parens => {
-            append_error(compiler, 0, 1, ERRORS.float_cant_be_child_of_parenthesis)
-        }
+        ElementInfo::Assignment => append_error(compiler, ERRORS.float_cant_be_child_of_assignment),
+        ElementInfo::Parens => append_error(compiler, ERRORS.float_cant_be_child_of_parenthesis),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -483,25 +582,23 @@ pub fn error_if_parent_is_invalid_for_string(
         ElementInfo::Struct(_, _, _) => Ok(()),
         ElementInfo::StructEdit(_, _) => Ok(()),
         ElementInfo::Assignment => {
-            append_error(compiler, 0, 1, ERRORS.string_cant_be_child_of_assignment)
-        }
-        ElementInfo::Parens => {
-            append_error(compiler, 0, 1, ERRORS.string_cant_be_child_of_parenthesis)
+            append_error(compiler, ERRORS.string_cant_be_child_of_assignment)
         }
+        ElementInfo::Parens => append_error(compiler, ERRORS.string_cant_be_child_of_parenthesis),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -529,25 +626,23 @@ pub fn error_if_parent_is_invalid_for_bool(
         ElementInfo::Struct(_, _, _) => Ok(()),
         ElementInfo::StructEdit(_, _) => Ok(()),
         ElementInfo::Assignment => {
-            append_error(compiler, 0, 1, ERRORS.string_cant_be_child_of_assignment)
-        }
-        ElementInfo::Parens => {
-            append_error(compiler, 0, 1, ERRORS.string_cant_be_child_of_parenthesis)
+            append_error(compiler, ERRORS.string_cant_be_child_of_assignment)
         }
+        ElementInfo::Parens => append_error(compiler, ERRORS.string_cant_be_child_of_parenthesis),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -565,36 +660,113 @@ pub fn error_if_parent_is_invalid_for_arg(
         ElementInfo::FunctionDef(_, _, _, _) => Ok(()),
         ElementInfo::If(_) => Ok(()),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::Struct(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::StructEdit(_, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Root => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Constant(_, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::Struct(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::StructEdit(_, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Root => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Constant(_, _) => append_error(compiler, ERRORS.impossible_error),
         ElementInfo::InbuiltFunctionCall(_, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.impossible_error)
+            append_error(compiler, ERRORS.impossible_error)
         }
-        ElementInfo::FunctionCall(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::FunctionCall(_, _, _) => append_error(compiler, ERRORS.impossible_error),
         ElementInfo::InbuiltFunctionDef(_, _, _, _, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.impossible_error)
+            append_error(compiler, ERRORS.impossible_error)
+        }
+        ElementInfo::Assignment => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::LoopForRangeWIP => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::LoopForRange(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Parens => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Println => append_error(compiler, ERRORS.impossible_error),
+    }
+}
+
+/// Adds the constants_are_immutable error, labelled with where the constant was first defined
+fn append_error_constants_are_immutable(compiler: &mut Compiler) -> Result<(), ()> {
+    let result = append_error(compiler, ERRORS.constants_are_immutable);
+    if let ElementInfo::ConstantRef(name, _, _) = get_last_element(&compiler.ast).0 {
+        if let Some(el_index) = get_constant_index_by_name(&compiler.ast, &name) {
+            let span = Span::from(&compiler.ast.element_tokens[el_index]);
+            if let Some(error) = compiler.error_stack.last_mut() {
+                error.labels.push(Label {
+                    span,
+                    message: "first defined here".to_string(),
+                });
+            }
         }
-        ElementInfo::Assignment => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::LoopForRangeWIP => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::LoopForRange(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Parens => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Println => append_error(compiler, 0, 1, ERRORS.impossible_error),
     }
+    result
+}
+
+/// Adds an error for an undefined name, with suggestions of similar names from the AST which may have been meant
+pub fn append_error_with_suggestions(
+    compiler: &mut Compiler,
+    error: ErrorMessage,
+) -> Result<(), ()> {
+    let result = append_error(compiler, error);
+    let name = &compiler.lines_of_tokens[compiler.current_line][compiler.current_line_token].0;
+    let suggestions = get_suggestions(&compiler.ast, name);
+    if let Some(error) = compiler.error_stack.last_mut() {
+        error.suggestions = suggestions;
+    }
+    result
+}
+
+/// The names of constants, args and functions, including inbuilt functions, closest to the name by edit distance.
+/// Only close enough names are suggested, i.e. up to 1 edit for every 3 characters,
+/// and args only if the name is inside their function, i.e. its scope is an ancestor of the last element
+fn get_suggestions(ast: &Ast, name: &str) -> Vec<String> {
+    let ancestors = get_ancestor_refs(ast, ast.elements.len() - 1);
+    let max_distance = name.chars().count() / 3;
+    let mut candidates: Vec<(usize, String)> = ast
+        .elements
+        .iter()
+        .filter_map(|(elinfo, _)| match elinfo {
+            ElementInfo::Constant(n, _) => Some(n),
+            ElementInfo::Arg(n, scope, _, _) if ancestors.contains(scope) => Some(n),
+            ElementInfo::FunctionDef(n, _, _, _) => Some(n),
+            ElementInfo::InbuiltFunctionDef(n, _, _, _, _, _) => Some(n),
+            _ => None,
+        })
+        // not operators like +, which are never a typo of a name
+        .filter(|n| n.starts_with(|c: char| c.is_alphabetic() || c == '_'))
+        .map(|n| (get_edit_distance(name, n), n.clone()))
+        .filter(|(distance, _)| *distance > 0 && *distance <= max_distance)
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+    let closest = candidates.first().map(|(distance, _)| *distance);
+    candidates
+        .into_iter()
+        .filter(|(distance, _)| Some(*distance) == closest)
+        .map(|(_, n)| n)
+        .collect()
+}
+
+/// Levenshtein distance, i.e. the number of chars which need to be inserted, deleted or substituted to change a into b
+fn get_edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + if a_char == *b_char { 0 } else { 1 };
+            row.push(substitution.min(previous_row[j + 1] + 1).min(row[j] + 1));
+        }
+        previous_row = row;
+    }
+    previous_row[b.len()]
 }
 
 pub fn error_if_parent_is_invalid_for_constantref(
@@ -618,29 +790,26 @@ pub fn error_if_parent_is_invalid_for_constantref(
         ElementInfo::If(_) => Ok(()),
         ElementInfo::Struct(_, _, _) => Ok(()),
         ElementInfo::StructEdit(_, _) => Ok(()),
-        ElementInfo::Assignment => append_error(compiler, 0, 1, ERRORS.constants_are_immutable),
-        ElementInfo::Parens => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.constantref_cant_be_child_of_parenthesis,
-        ),
+        ElementInfo::Assignment => append_error_constants_are_immutable(compiler),
+        ElementInfo::Parens => {
+            append_error(compiler, ERRORS.constantref_cant_be_child_of_parenthesis)
+        }
         ElementInfo::LoopForRangeWIP => Ok(()),
         ElementInfo::LoopForRange(_, _, _) => Ok(()),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -654,7 +823,7 @@ pub fn error_if_parent_is_invalid_for_struct(
     ));
     match parent.0 {
         ElementInfo::Constant(_, _) => Ok(()),
-        _ => append_error(compiler, 0, 1, ERRORS.struct_undefined),
+        _ => append_error(compiler, ERRORS.struct_undefined),
     }
 }
 
@@ -668,7 +837,7 @@ pub fn error_if_parent_is_invalid_for_struct_edit(
     ));
     match parent.0 {
         ElementInfo::Assignment => Ok(()),
-        _ => append_error(compiler, 0, 1, ERRORS.struct_edit_error),
+        _ => append_error(compiler, ERRORS.struct_edit_error),
     }
 }
 
@@ -681,7 +850,7 @@ pub fn error_if_parent_is_invalid_for_constant(
         parent
     ));
     match parent.0 {
-        ElementInfo::Root => append_error(compiler, 0, 1, ERRORS.constant_undefined),
+        ElementInfo::Root => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
         ElementInfo::FunctionDefWIP => Ok(()),
         ElementInfo::FunctionDef(_, _, _, _) => Ok(()),
         ElementInfo::Assignment => Ok(()),
@@ -691,32 +860,42 @@ pub fn error_if_parent_is_invalid_for_constant(
         ElementInfo::List(_) => Ok(()),
         ElementInfo::Constant(_, _) => Ok(()),
         ElementInfo::InbuiltFunctionCall(_, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.constant_undefined)
+            append_error_with_suggestions(compiler, ERRORS.constant_undefined)
         }
         ElementInfo::FunctionCall(_, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.constant_undefined)
+            append_error_with_suggestions(compiler, ERRORS.constant_undefined)
+        }
+        ElementInfo::Println => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
+        ElementInfo::If(_) => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
+        ElementInfo::Struct(_, _, _) => {
+            append_error_with_suggestions(compiler, ERRORS.constant_undefined)
+        }
+        ElementInfo::StructEdit(_, _) => {
+            append_error_with_suggestions(compiler, ERRORS.constant_undefined)
         }
-        ElementInfo::Println => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::If(_) => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::Struct(_, _, _) => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::StructEdit(_, _) => append_error(compiler, 0, 1, ERRORS.constant_undefined),
         ElementInfo::CommentSingleLine(_) => {
-            append_error(compiler, 0, 1, ERRORS.constant_undefined)
+            append_error_with_suggestions(compiler, ERRORS.constant_undefined)
         }
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::Parens => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.constant_undefined),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.constant_undefined),
+        ElementInfo::Rust(_, _) => {
+            append_error_with_suggestions(compiler, ERRORS.constant_undefined)
+        }
+        ElementInfo::Int(_) => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
+        ElementInfo::Float(_) => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
+        ElementInfo::String(_) => {
+            append_error_with_suggestions(compiler, ERRORS.constant_undefined)
+        }
+        ElementInfo::Bool(_) => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
+        ElementInfo::Arg(_, _, _, _) => {
+            append_error_with_suggestions(compiler, ERRORS.constant_undefined)
+        }
+        ElementInfo::Type(_) => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
+        ElementInfo::Parens => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
+        ElementInfo::Eol => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
+        ElementInfo::Seol => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
+        ElementInfo::Indent => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
+        ElementInfo::Unused => append_error_with_suggestions(compiler, ERRORS.constant_undefined),
         ElementInfo::ConstantRef(_, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.constant_undefined)
+            append_error_with_suggestions(compiler, ERRORS.constant_undefined)
         }
     }
 }
@@ -738,52 +917,41 @@ pub fn error_if_parent_is_invalid_for_assignment(
         ElementInfo::If(_) => Ok(()),
         ElementInfo::Struct(_, _, _) => Ok(()),
 
-        ElementInfo::StructEdit(_, _) => append_error(compiler, 0, 1, ERRORS.struct_edit_error),
-        ElementInfo::List(_) => {
-            append_error(compiler, 0, 1, ERRORS.assignment_cant_be_child_of_list)
-        }
+        ElementInfo::StructEdit(_, _) => append_error(compiler, ERRORS.struct_edit_error),
+        ElementInfo::List(_) => append_error(compiler, ERRORS.assignment_cant_be_child_of_list),
         ElementInfo::Constant(_, _) => {
-            append_error(compiler, 0, 1, ERRORS.assignment_cant_be_child_of_constant)
+            append_error(compiler, ERRORS.assignment_cant_be_child_of_constant)
+        }
+        ElementInfo::InbuiltFunctionCall(_, _, _) => {
+            append_error(compiler, ERRORS.assignment_cant_be_child_of_inbuiltfncall)
         }
-        ElementInfo::InbuiltFunctionCall(_, _, _) => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.assignment_cant_be_child_of_inbuiltfncall,
-        ),
         ElementInfo::FunctionCall(_, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.assignment_cant_be_child_of_fncal)
+            append_error(compiler, ERRORS.assignment_cant_be_child_of_fncal)
+        }
+        ElementInfo::Assignment => {
+            append_error(compiler, ERRORS.assignment_cant_be_child_of_assignment)
+        }
+        ElementInfo::Parens => {
+            append_error(compiler, ERRORS.assignment_cant_be_child_of_parenthesis)
         }
-        ElementInfo::Assignment => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.assignment_cant_be_child_of_assignment,
-        ),
-        ElementInfo::Parens => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.assignment_cant_be_child_of_parenthesis,
-        ),
         // explicitly listing other types rather than using _ to not overlook new types in future.
         ElementInfo::InbuiltFunctionDef(_, _, _, _, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.impossible_error)
+            append_error(compiler, ERRORS.impossible_error)
         }
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Println => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Println => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -810,27 +978,24 @@ pub fn error_if_parent_is_invalid_for_inbuiltfncall(
         ElementInfo::Println => Ok(()),
         ElementInfo::If(_) => Ok(()),
         ElementInfo::Struct(_, _, _) => Ok(()),
-        ElementInfo::Parens => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.inbuiltfncall_cant_be_child_of_parenthesis,
-        ),
+        ElementInfo::Parens => {
+            append_error(compiler, ERRORS.inbuiltfncall_cant_be_child_of_parenthesis)
+        }
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::StructEdit(_, _) => Ok(()), //append_error(compiler, 0, 1, ERRORS.struct_edit_error),
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::StructEdit(_, _) => Ok(()), //append_error(compiler, ERRORS.struct_edit_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -857,24 +1022,22 @@ pub fn error_if_parent_is_invalid_for_fncall(
         ElementInfo::If(_) => Ok(()),
         ElementInfo::Struct(_, _, _) => Ok(()),
         ElementInfo::StructEdit(_, _) => Ok(()),
-        ElementInfo::Parens => {
-            append_error(compiler, 0, 1, ERRORS.fncall_cant_be_child_of_parenthesis)
-        }
+        ElementInfo::Parens => append_error(compiler, ERRORS.fncall_cant_be_child_of_parenthesis),
         // TODO need to allow parens for functionref
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
         ElementInfo::Println => Ok(()),
     }
 }
@@ -895,40 +1058,33 @@ pub fn error_if_parent_is_invalid_for_parenthesis(
         ElementInfo::InbuiltFunctionDef(_, _, _, _, _, _) => Ok(()),
         ElementInfo::LoopForRangeWIP => Ok(()),
         ElementInfo::If(_) => Ok(()),
-        ElementInfo::List(_) => append_error(compiler, 0, 1, ERRORS.list_cant_be_child),
-        ElementInfo::LoopForRange(_, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.loopfor_cant_be_child)
-        }
-        ElementInfo::Parens => {
-            append_error(compiler, 0, 1, ERRORS.fncall_cant_be_child_of_parenthesis)
-        }
-        ElementInfo::Root => append_error(compiler, 0, 1, ERRORS.parenthesis_cant_be_child_of_root),
+        ElementInfo::List(_) => append_error(compiler, ERRORS.list_cant_be_child),
+        ElementInfo::LoopForRange(_, _, _) => append_error(compiler, ERRORS.loopfor_cant_be_child),
+        ElementInfo::Parens => append_error(compiler, ERRORS.fncall_cant_be_child_of_parenthesis),
+        ElementInfo::Root => append_error(compiler, ERRORS.parenthesis_cant_be_child_of_root),
         ElementInfo::Constant(_, _) => {
-            append_error(compiler, 0, 1, ERRORS.parenthesis_cant_be_child_of_constant)
+            append_error(compiler, ERRORS.parenthesis_cant_be_child_of_constant)
+        }
+        ElementInfo::Assignment => {
+            append_error(compiler, ERRORS.parenthesis_cant_be_child_of_assignment)
         }
-        ElementInfo::Assignment => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.parenthesis_cant_be_child_of_assignment,
-        ),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::Struct(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::StructEdit(_, _) => append_error(compiler, 0, 1, ERRORS.struct_edit_error),
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Println => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::Struct(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::StructEdit(_, _) => append_error(compiler, ERRORS.struct_edit_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Println => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -944,35 +1100,33 @@ pub fn error_if_parent_is_invalid_for_loopfor(
         ElementInfo::FunctionDefWIP => Ok(()),
         ElementInfo::FunctionDef(_, _, _, _) => Ok(()),
         ElementInfo::InbuiltFunctionCall(_, _, _) => Ok(()),
-        ElementInfo::List(_) => append_error(compiler, 0, 1, ERRORS.list_cant_be_child),
-        ElementInfo::FunctionCall(_, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.loopfor_cant_be_child)
-        }
+        ElementInfo::List(_) => append_error(compiler, ERRORS.list_cant_be_child),
+        ElementInfo::FunctionCall(_, _, _) => append_error(compiler, ERRORS.loopfor_cant_be_child),
         ElementInfo::InbuiltFunctionDef(_, _, _, _, _, _) => Ok(()),
         ElementInfo::LoopForRangeWIP => Ok(()),
         ElementInfo::LoopForRange(_, _, _) => Ok(()),
         ElementInfo::Root => Ok(()),
         ElementInfo::Println => Ok(()),
         ElementInfo::If(_) => Ok(()),
-        ElementInfo::Parens => append_error(compiler, 0, 1, ERRORS.loopfor_cant_be_child),
-        ElementInfo::Constant(_, _) => append_error(compiler, 0, 1, ERRORS.loopfor_cant_be_child),
-        ElementInfo::Assignment => append_error(compiler, 0, 1, ERRORS.loopfor_cant_be_child),
+        ElementInfo::Parens => append_error(compiler, ERRORS.loopfor_cant_be_child),
+        ElementInfo::Constant(_, _) => append_error(compiler, ERRORS.loopfor_cant_be_child),
+        ElementInfo::Assignment => append_error(compiler, ERRORS.loopfor_cant_be_child),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::StructEdit(_, _) => append_error(compiler, 0, 1, ERRORS.struct_edit_error),
-        ElementInfo::Struct(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::StructEdit(_, _) => append_error(compiler, ERRORS.struct_edit_error),
+        ElementInfo::Struct(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -991,68 +1145,45 @@ pub fn error_if_parent_is_invalid_for_fndefwip(
         ElementInfo::LoopForRangeWIP => Ok(()),
         ElementInfo::Println => Ok(()),
         ElementInfo::If(_) => Ok(()),
-        ElementInfo::List(_) => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.fndefwip_can_only_be_child_of_constant,
-        ),
-        ElementInfo::LoopForRange(_, _, _) => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.fndefwip_can_only_be_child_of_constant,
-        ),
-        ElementInfo::Root => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.fndefwip_can_only_be_child_of_constant,
-        ),
-        ElementInfo::InbuiltFunctionCall(_, _, _) => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.fndefwip_can_only_be_child_of_constant,
-        ),
-        ElementInfo::FunctionCall(_, _, _) => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.fndefwip_can_only_be_child_of_constant,
-        ),
-        ElementInfo::Parens => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.fndefwip_can_only_be_child_of_constant,
-        ),
-        ElementInfo::Assignment => append_error(
-            compiler,
-            0,
-            1,
-            ERRORS.fndefwip_can_only_be_child_of_constant,
-        ),
+        ElementInfo::List(_) => {
+            append_error(compiler, ERRORS.fndefwip_can_only_be_child_of_constant)
+        }
+        ElementInfo::LoopForRange(_, _, _) => {
+            append_error(compiler, ERRORS.fndefwip_can_only_be_child_of_constant)
+        }
+        ElementInfo::Root => append_error(compiler, ERRORS.fndefwip_can_only_be_child_of_constant),
+        ElementInfo::InbuiltFunctionCall(_, _, _) => {
+            append_error(compiler, ERRORS.fndefwip_can_only_be_child_of_constant)
+        }
+        ElementInfo::FunctionCall(_, _, _) => {
+            append_error(compiler, ERRORS.fndefwip_can_only_be_child_of_constant)
+        }
+        ElementInfo::Parens => {
+            append_error(compiler, ERRORS.fndefwip_can_only_be_child_of_constant)
+        }
+        ElementInfo::Assignment => {
+            append_error(compiler, ERRORS.fndefwip_can_only_be_child_of_constant)
+        }
         // explicitly listing other types rather than using _ to not overlook new types in future.
         ElementInfo::InbuiltFunctionDef(_, _, _, _, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.impossible_error)
+            append_error(compiler, ERRORS.impossible_error)
         }
 
-        ElementInfo::StructEdit(_, _) => append_error(compiler, 0, 1, ERRORS.struct_edit_error),
-        ElementInfo::Struct(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::StructEdit(_, _) => append_error(compiler, ERRORS.struct_edit_error),
+        ElementInfo::Struct(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -1072,42 +1203,34 @@ pub fn error_if_parent_is_invalid_for_println(
         ElementInfo::Root => Ok(()),
         ElementInfo::InbuiltFunctionCall(_, _, _) => Ok(()),
         ElementInfo::If(_) => Ok(()),
-        ElementInfo::List(_) => {
-            append_error(compiler, 0, 1, ERRORS.println_cant_be_child_of_element)
-        }
+        ElementInfo::List(_) => append_error(compiler, ERRORS.println_cant_be_child_of_element),
         ElementInfo::FunctionCall(_, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.println_cant_be_child_of_element)
-        }
-        ElementInfo::Parens => {
-            append_error(compiler, 0, 1, ERRORS.println_cant_be_child_of_element)
-        }
-        ElementInfo::Assignment => {
-            append_error(compiler, 0, 1, ERRORS.println_cant_be_child_of_element)
-        }
-        ElementInfo::Println => {
-            append_error(compiler, 0, 1, ERRORS.println_cant_be_child_of_element)
+            append_error(compiler, ERRORS.println_cant_be_child_of_element)
         }
+        ElementInfo::Parens => append_error(compiler, ERRORS.println_cant_be_child_of_element),
+        ElementInfo::Assignment => append_error(compiler, ERRORS.println_cant_be_child_of_element),
+        ElementInfo::Println => append_error(compiler, ERRORS.println_cant_be_child_of_element),
         // explicitly listing other types rather than using _ to not overlook new types in future.
-        ElementInfo::Constant(_, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::Constant(_, _) => append_error(compiler, ERRORS.impossible_error),
         ElementInfo::InbuiltFunctionDef(_, _, _, _, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.impossible_error)
+            append_error(compiler, ERRORS.impossible_error)
         }
 
-        ElementInfo::StructEdit(_, _) => append_error(compiler, 0, 1, ERRORS.struct_edit_error),
-        ElementInfo::Struct(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::StructEdit(_, _) => append_error(compiler, ERRORS.struct_edit_error),
+        ElementInfo::Struct(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -1132,30 +1255,26 @@ pub fn error_if_parent_is_invalid_for_if_expression(
         ElementInfo::Assignment => Ok(()),
         ElementInfo::Constant(_, _) => Ok(()),
         ElementInfo::StructEdit(_, _) => Ok(()),
-        ElementInfo::Parens => {
-            append_error(compiler, 0, 1, ERRORS.println_cant_be_child_of_element)
-        }
-        ElementInfo::Println => {
-            append_error(compiler, 0, 1, ERRORS.println_cant_be_child_of_element)
-        }
+        ElementInfo::Parens => append_error(compiler, ERRORS.println_cant_be_child_of_element),
+        ElementInfo::Println => append_error(compiler, ERRORS.println_cant_be_child_of_element),
         // explicitly listing other types rather than using _ to not overlook new types in future.
         ElementInfo::InbuiltFunctionDef(_, _, _, _, _, _) => {
-            append_error(compiler, 0, 1, ERRORS.impossible_error)
+            append_error(compiler, ERRORS.impossible_error)
         }
-        ElementInfo::Struct(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::CommentSingleLine(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Rust(_, _) => append_error(compiler, 0, 1, ERRORS.rustcode),
-        ElementInfo::Int(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Float(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::String(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Bool(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Arg(_, _, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Type(_) => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Eol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Seol => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Indent => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::Unused => append_error(compiler, 0, 1, ERRORS.impossible_error),
-        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, 0, 1, ERRORS.impossible_error),
+        ElementInfo::Struct(_, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::CommentSingleLine(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Rust(_, _) => append_error(compiler, ERRORS.rustcode),
+        ElementInfo::Int(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Float(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::String(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Bool(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Arg(_, _, _, _) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Type(_) => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Eol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Seol => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Indent => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::Unused => append_error(compiler, ERRORS.impossible_error),
+        ElementInfo::ConstantRef(_, _, _) => append_error(compiler, ERRORS.impossible_error),
     }
 }
 
@@ -1170,7 +1289,7 @@ mod tests {
             let error = &test[0];
             let mut c: Compiler = Default::default();
             c.file.filecontents = input.to_string();
-            match c.run_main_tasks(false) {
+            match c.run_main_tasks() {
                 Ok(_) => {
                     let e = c.error_stack;
                     if e.len() == 0 {
@@ -1181,10 +1300,10 @@ mod tests {
                         }
                     } else {
                         let app_error = e[0].clone();
-                        if error == &"" || app_error.0.len() == 0 {
+                        if error == &"" || app_error.message.len() == 0 {
                             assert_eq!(true, true);
                         } else {
-                            assert!(app_error.0.contains(error));
+                            assert!(app_error.message.contains(error));
                         }
                     }
                 }
@@ -1204,20 +1323,29 @@ mod tests {
     fn test_error_comment_singleline() {
         //comment single line
         let tests = vec![
-            vec![ERRORS.comment_single_line, "/1/comment"],
-            vec![ERRORS.comment_cant_be_child_of_assignment, "= //test"],
-            vec![ERRORS.comment_cant_be_child_of_constant, "= c //test"],
-            vec![ERRORS.comment_cant_be_child_of_inbuiltfncall, "+ //test"],
+            vec![ERRORS.comment_single_line.message, "/1/comment"],
             vec![
-                ERRORS.comment_cant_be_child_of_fncall,
+                ERRORS.comment_cant_be_child_of_assignment.message,
+                "= //test",
+            ],
+            vec![
+                ERRORS.comment_cant_be_child_of_constant.message,
+                "= c //test",
+            ],
+            vec![
+                ERRORS.comment_cant_be_child_of_inbuiltfncall.message,
+                "+ //test",
+            ],
+            vec![
+                ERRORS.comment_cant_be_child_of_fncall.message,
                 "= myfun \\ i64 i64 arg1 => + arg1 123\r\nmyfun //test",
             ],
             vec![
-                ERRORS.comment_cant_be_child_of_parenthesis,
+                ERRORS.comment_cant_be_child_of_parenthesis.message,
                 "= myfun \\ ( i64 i64 // test ) i64 arg1 => arg1 123",
             ],
             vec![
-                ERRORS.comment_cant_be_child_of_parenthesis,
+                ERRORS.comment_cant_be_child_of_parenthesis.message,
                 "= myfun \\ ( i64 i64 ) i64 arg1 => arg1 123\r\nmyfun ( //test ) 123",
             ],
         ];
@@ -1227,22 +1355,22 @@ mod tests {
     #[test]
     fn test_error_int() {
         let tests = vec![
-            vec![ERRORS.int_cant_be_child_of_assignment, "= 123"],
+            vec![ERRORS.int_cant_be_child_of_assignment.message, "= 123"],
             vec![
-                ERRORS.int_cant_be_child_of_parenthesis,
+                ERRORS.int_cant_be_child_of_parenthesis.message,
                 "= myfun \\ ( i64 123 ) i64 arg1 => arg1 123",
             ],
             vec![
-                ERRORS.int_cant_be_child_of_parenthesis,
+                ERRORS.int_cant_be_child_of_parenthesis.message,
                 "= myfun \\ ( i64 i64 ) i64 arg1 => arg1 123\r\nmyfun ( 123 ) 123",
             ],
             //int
-            vec![ERRORS.int, "1a"],
-            vec![ERRORS.int_out_of_bounds, "9223372036854775808"],
+            vec![ERRORS.int.message, "1a"],
+            vec![ERRORS.int_out_of_bounds.message, "9223372036854775808"],
             //
             //int negative
-            vec![ERRORS.int, "-1a"],
-            vec![ERRORS.int_out_of_bounds, "-9223372036854775809"],
+            vec![ERRORS.int.message, "-1a"],
+            vec![ERRORS.int_out_of_bounds.message, "-9223372036854775809"],
         ];
         test_error_scenario(tests);
     }
@@ -1250,22 +1378,25 @@ mod tests {
     #[test]
     fn test_error_float() {
         let tests = vec![
-            vec![ERRORS.float_cant_be_child_of_assignment, "= 123.456"],
             vec![
-                ERRORS.float_cant_be_child_of_parenthesis,
+                ERRORS.float_cant_be_child_of_assignment.message,
+                "= 123.456",
+            ],
+            vec![
+                ERRORS.float_cant_be_child_of_parenthesis.message,
                 "= myfun \\ ( i64 123.456 ) i64 arg1 => arg1 123",
             ],
             vec![
-                ERRORS.float_cant_be_child_of_parenthesis,
+                ERRORS.float_cant_be_child_of_parenthesis.message,
                 "= myfun \\ ( i64 i64 ) i64 arg1 => arg1 123\r\nmyfun ( 123.456 ) 123",
             ],
             //float (errors say int)
-            vec![ERRORS.int, "1.1.1"],
-            vec![ERRORS.int, "1.7976931348623157E+309"],
+            vec![ERRORS.int.message, "1.1.1"],
+            vec![ERRORS.int.message, "1.7976931348623157E+309"],
             //
             //float negative (errors say int)
-            vec![ERRORS.int, "-1.1.1"],
-            vec![ERRORS.int, "-1.7976931348623157E+309"],
+            vec![ERRORS.int.message, "-1.1.1"],
+            vec![ERRORS.int.message, "-1.7976931348623157E+309"],
         ];
         test_error_scenario(tests);
     }
@@ -1273,18 +1404,21 @@ mod tests {
     #[test]
     fn test_error_string() {
         let tests = vec![
-            vec![ERRORS.string_cant_be_child_of_assignment, "= \"string\""],
             vec![
-                ERRORS.string_cant_be_child_of_parenthesis,
+                ERRORS.string_cant_be_child_of_assignment.message,
+                "= \"string\"",
+            ],
+            vec![
+                ERRORS.string_cant_be_child_of_parenthesis.message,
                 "= myfun \\ ( i64 \"string\" ) i64 arg1 => arg1 123",
             ],
             vec![
-                ERRORS.string_cant_be_child_of_parenthesis,
+                ERRORS.string_cant_be_child_of_parenthesis.message,
                 "= myfun \\ ( i64 i64 ) i64 arg1 => arg1 123\r\nmyfun ( \"string\" ) 123",
             ],
             //string
-            vec![ERRORS.string, "\""],
-            vec![ERRORS.string, "\"test\"\"test"],
+            vec![ERRORS.string.message, "\""],
+            vec![ERRORS.string.message, "\"test\"\"test"],
         ];
         test_error_scenario(tests);
     }
@@ -1293,8 +1427,8 @@ mod tests {
     fn test_error_list() {
         let tests = vec![
             //lists - empty must contain type definition
-            vec![ERRORS.list, "[ ]"],
-            vec![ERRORS.list, "[]"],
+            vec![ERRORS.list.message, "[ ]"],
+            vec![ERRORS.list.message, "[]"],
         ];
         test_error_scenario(tests);
     }
@@ -1303,11 +1437,11 @@ mod tests {
     fn test_error_constantref() {
         let tests = vec![
             vec![
-                ERRORS.constantref_cant_be_child_of_parenthesis,
+                ERRORS.constantref_cant_be_child_of_parenthesis.message,
                 "= a 123\r\n= myfun \\ ( i64 a ) i64 arg1 => arg1 123",
             ],
             vec![
-                ERRORS.constantref_cant_be_child_of_parenthesis,
+                ERRORS.constantref_cant_be_child_of_parenthesis.message,
                 "= a 123\r\n= myfun \\ ( i64 i64 ) i64 arg1 => arg1 123\r\nmyfun ( a ) 123",
             ],
         ];
@@ -1317,8 +1451,8 @@ mod tests {
     #[test]
     fn test_error_constant() {
         let tests = vec![
-            vec![ERRORS.constant_undefined, "a"],
-            vec![ERRORS.constants_are_immutable, "= a 123\r\n= a 234"],
+            vec![ERRORS.constant_undefined.message, "a"],
+            vec![ERRORS.constants_are_immutable.message, "= a 123\r\n= a 234"],
         ];
         test_error_scenario(tests);
     }
@@ -1326,19 +1460,22 @@ mod tests {
     #[test]
     fn test_error_assignment() {
         let tests = vec![
-            vec![ERRORS.assignment_cant_be_child_of_constant, "= a ="],
-            vec![ERRORS.assignment_cant_be_child_of_inbuiltfncall, "+ 123 ="],
+            vec![ERRORS.assignment_cant_be_child_of_constant.message, "= a ="],
+            vec![
+                ERRORS.assignment_cant_be_child_of_inbuiltfncall.message,
+                "+ 123 =",
+            ],
             vec![
-                ERRORS.assignment_cant_be_child_of_fncal,
+                ERRORS.assignment_cant_be_child_of_fncal.message,
                 "= myfun \\ i64 i64 arg1 => + arg1 123\r\nmyfun = 123",
             ],
-            vec![ERRORS.assignment_cant_be_child_of_assignment, "= ="],
+            vec![ERRORS.assignment_cant_be_child_of_assignment.message, "= ="],
             vec![
-                ERRORS.assignment_cant_be_child_of_parenthesis,
+                ERRORS.assignment_cant_be_child_of_parenthesis.message,
                 "= a 123\r\n= myfun \\ ( i64 = ) i64 arg1 => arg1 123",
             ],
             vec![
-                ERRORS.assignment_cant_be_child_of_parenthesis,
+                ERRORS.assignment_cant_be_child_of_parenthesis.message,
                 "= a 123\r\n= myfun \\ ( i64 i64 ) i64 arg1 => arg1 123\r\nmyfun ( = ) 123",
             ],
         ];
@@ -1349,11 +1486,11 @@ mod tests {
     fn test_error_inbuiltfncall() {
         let tests = vec![
             vec![
-                ERRORS.inbuiltfncall_cant_be_child_of_parenthesis,
+                ERRORS.inbuiltfncall_cant_be_child_of_parenthesis.message,
                 "= a 123\r\n= myfun \\ ( i64 + ) i64 arg1 => arg1 123",
             ],
             vec![
-                ERRORS.inbuiltfncall_cant_be_child_of_parenthesis,
+                ERRORS.inbuiltfncall_cant_be_child_of_parenthesis.message,
                 "= a 123\r\n= myfun \\ ( i64 i64 ) i64 arg1 => arg1 123\r\nmyfun ( + ) 123",
             ],
         ];
@@ -1365,10 +1502,10 @@ mod tests {
         let tests = vec![
             // fncall_cant_be_child_of_parenthesis
             // but fails with other error funcdef_argtypes_first
-            vec![ERRORS.funcdef_argtypes_first, "= myfun1 \\ i64 i64 arg1 => + arg1 123\r\n= myfun2 \\ ( i64 myfun1 ) i64 arg2 => arg2 123"],
+            vec![ERRORS.funcdef_argtypes_first.message, "= myfun1 \\ i64 i64 arg1 => + arg1 123\r\n= myfun2 \\ ( i64 myfun1 ) i64 arg2 => arg2 123"],
             //but not here
             //[
-            //    ERRORS.fncall_cant_be_child_of_parenthesis,
+            //    ERRORS.fncall_cant_be_child_of_parenthesis.message,
             //    "= myfun1 \\ i64 i64 arg1 => + arg1 123\r\n= myfun2 \\ ( i64 i64 ) i64 arg2 => arg2 123\r\nmyfun2 ( myfun1 ) 123",
             //],
         ];
@@ -1378,10 +1515,13 @@ mod tests {
     #[test]
     fn test_error_parenthesis() {
         let tests = vec![
-            vec![ERRORS.parenthesis_cant_be_child_of_root, "( i64 )"],
-            vec![ERRORS.parenthesis_cant_be_child_of_constant, "= x ( i64 )"],
+            vec![ERRORS.parenthesis_cant_be_child_of_root.message, "( i64 )"],
             vec![
-                ERRORS.parenthesis_cant_be_child_of_assignment,
+                ERRORS.parenthesis_cant_be_child_of_constant.message,
+                "= x ( i64 )",
+            ],
+            vec![
+                ERRORS.parenthesis_cant_be_child_of_assignment.message,
                 "= ( i64 ) 123",
             ],
         ];
@@ -1392,36 +1532,85 @@ mod tests {
     fn test_error_fndefwip() {
         let tests = vec![
             vec![
-                ERRORS.fndefwip_can_only_be_child_of_constant,
+                ERRORS.fndefwip_can_only_be_child_of_constant.message,
                 "\\ i64 => 123",
             ],
-            vec![ERRORS.fndefwip_can_only_be_child_of_constant, "+ 123 \\"],
             vec![
-                ERRORS.fndefwip_can_only_be_child_of_constant,
+                ERRORS.fndefwip_can_only_be_child_of_constant.message,
+                "+ 123 \\",
+            ],
+            vec![
+                ERRORS.fndefwip_can_only_be_child_of_constant.message,
                 "= myfun \\ i64 i64 arg1 => + arg1 123\r\nmyfun \\",
             ],
             // fndefwip_can_only_be_child_of_constant
             // but fails with other error parens_of_assign
             vec![
-                ERRORS.parenthesis_cant_be_child_of_assignment,
+                ERRORS.parenthesis_cant_be_child_of_assignment.message,
                 "= ( \\ ) 123",
             ],
             vec![
-                ERRORS.fndefwip_can_only_be_child_of_constant,
+                ERRORS.fndefwip_can_only_be_child_of_constant.message,
                 "= a 123\r\n= myfun \\ ( \\ i64 ) i64 arg1 => arg1 123\r\nmyfun ( a ) 123",
             ],
-            vec![ERRORS.fndefwip_can_only_be_child_of_constant, "= \\ 123"],
+            vec![
+                ERRORS.fndefwip_can_only_be_child_of_constant.message,
+                "= \\ 123",
+            ],
         ];
         test_error_scenario(tests);
     }
 
+    #[test]
+    fn test_get_edit_distance() {
+        assert_eq!(get_edit_distance("monkey", "monkey"), 0);
+        assert_eq!(get_edit_distance("monkeys", "monkey"), 1);
+        assert_eq!(get_edit_distance("List::mpa", "List::map"), 2);
+        assert_eq!(get_edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_error_suggestions() {
+        let tests = vec![
+            ("= monkey 1\r\n@ monkeys", "T0039", vec!["monkey"]),
+            ("= a 1\r\n= b 2\r\n@ c", "T0039", vec![]),
+            (
+                "= double \\ i64 i64 x => * x 2\r\n@ doubel 2",
+                "T0039",
+                vec!["double"],
+            ),
+            (
+                "= double \\ i64 i64 monkey => * monkey 2\r\n@ monkeys",
+                "T0039",
+                vec![],
+            ),
+            (
+                "= double \\ i64 i64 monkey => * monkeys 2",
+                "T0039",
+                vec!["monkey"],
+            ),
+            (
+                "= list [ 1 2 ]\r\n= list2 List::mpa list",
+                "T0055",
+                vec!["List::map"],
+            ),
+        ];
+        for (input, code, suggestions) in tests {
+            let mut c: Compiler = Default::default();
+            c.file.filecontents = input.to_string();
+            let _ = c.run_main_tasks();
+            assert_eq!(c.error_stack[0].code, code);
+            assert_eq!(c.error_stack[0].suggestions, suggestions);
+        }
+    }
+
     //
     //internalFunctionCalls
-    //[ERRORS.int.to_string(),"+ 1 2.1".to_string()],
-    //[ERRORS.int.to_string(),"- 1.1 2".to_string()],
+    //[ERRORS.int.message.to_string(),"+ 1 2.1".to_string()],
+    //[ERRORS.int.message.to_string(),"- 1.1 2".to_string()],
     //
     //functionDefinitions
-    //[ERRORS.funcdef_args.to_string(), "= a \\ =>".to_string()],
-    //[ERRORS.funcdef_argtypes_first.to_string(),"= a \\ i64 monkey i64  =>".to_string()],
+    //[ERRORS.funcdef_args.message.to_string(), "= a \\ =>".to_string()],
+    //[ERRORS.funcdef_argtypes_first.message.to_string(),"= a \\ i64 monkey i64  =>".to_string()],
     //
 }
@@ -0,0 +1,597 @@
+/*! A tree-walking interpreter for the toylang AST, i.e. `toylang eval`
+
+    Instead of outputting Rust and compiling it with rustc, the Elements of the AST are evaluated directly,
+    so the results are printed instantly.
+
+    ```bash
+    toylang eval test.toy
+    ```
+
+    Inbuilt functions are evaluated with the same meaning as their Rust `Format` templates, e.g. `+` is `arg~1 + arg~2`,
+    and `println` prints values the same way as the compiled output, i.e. lists and structs with their Debug format.
+
+    If something goes wrong while running, e.g. dividing by zero, it is shown as a TOYLANG RUNTIME ERROR
+    at the Token of the Element which caused it.
+*/
+use crate::ast::elements::{self, ElIndex, ElementInfo};
+use crate::ast::output::{get_value_children, is_layout};
+use crate::errors;
+use crate::{parse, Compiler};
+use std::collections::HashMap;
+use std::fmt;
+use std::process;
+
+/// A runtime value
+#[derive(Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Bool(bool),
+    List(Vec<Value>),
+    Struct(String, Vec<(String, Value)>),
+    /// A reference to a FunctionDef (or Arg of a function type), e.g. passed as an argument.
+    /// In the [vm](crate::vm) it is the index of the function in the Program instead
+    Function(ElIndex),
+    /// The value of statements which don't return anything, e.g. assignments and println
+    Unit,
+}
+
+/// Matches Rust's Display, which is what `println!("{}", ...)` uses in the compiled output
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Float(float) => write!(f, "{}", float),
+            Value::String(s) => write!(f, "{}", s),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::List(_) | Value::Struct(_, _) | Value::Function(_) | Value::Unit => {
+                write!(f, "{:?}", self)
+            }
+        }
+    }
+}
+
+/// Matches Rust's Debug, which is what `println!("{:?}", ...)` uses in the compiled output
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(i) => write!(f, "{:?}", i),
+            Value::Float(float) => write!(f, "{:?}", float),
+            Value::String(s) => write!(f, "{:?}", s),
+            Value::Bool(b) => write!(f, "{:?}", b),
+            Value::List(items) => {
+                let items: Vec<String> = items.iter().map(|item| format!("{:?}", item)).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Value::Struct(name, keys) => {
+                let keys: Vec<String> = keys
+                    .iter()
+                    .map(|(key, value)| format!("{}: {:?}", key, value))
+                    .collect();
+                write!(f, "{} {{ {} }}", name, keys.join(", "))
+            }
+            Value::Function(el_index) => write!(f, "<function {}>", el_index),
+            Value::Unit => write!(f, "()"),
+        }
+    }
+}
+
+/// Holds the values of constants while evaluating. The first frame is for the root,
+/// and each function call pushes a frame for its arguments and constants
+pub struct Interpreter {
+    pub frames: Vec<HashMap<String, Value>>,
+    /// print immediately, otherwise only collect what is printed in `printed`
+    pub echo: bool,
+    pub printed: Vec<String>,
+}
+
+impl Interpreter {
+    pub fn new(echo: bool) -> Interpreter {
+        Interpreter {
+            frames: vec![HashMap::new()],
+            echo,
+            printed: vec![],
+        }
+    }
+
+    /// Evaluate the whole program, i.e. the children of Root
+    pub fn run(&mut self, compiler: &Compiler) -> Result<Value, String> {
+        let children = compiler.ast.elements[0].1.clone();
+        self.eval_block(compiler, &children)
+    }
+
+    /// Evaluate each line in turn, returning the value of the last one
+    pub fn eval_block(
+        &mut self,
+        compiler: &Compiler,
+        children: &[ElIndex],
+    ) -> Result<Value, String> {
+        let mut value = Value::Unit;
+        for &child_ref in children {
+            let result = self.eval(compiler, child_ref)?;
+            if !is_layout(&compiler.ast.elements[child_ref].0) {
+                value = result;
+            }
+        }
+        Ok(value)
+    }
+
+    /// Evaluate one Element, and its children
+    pub fn eval(&mut self, compiler: &Compiler, el_index: ElIndex) -> Result<Value, String> {
+        let (el_info, all_children) = compiler.ast.elements[el_index].clone();
+        // e.g. a Constant's value can be preceded by an Indent
+        let children = get_value_children(compiler, &all_children);
+        match el_info {
+            ElementInfo::Root => self.eval_block(compiler, &all_children),
+            ElementInfo::List(_) => {
+                let mut items = vec![];
+                for child_ref in children {
+                    items.push(self.eval(compiler, child_ref)?);
+                }
+                Ok(Value::List(items))
+            }
+            // ints used as return expressions have "as i64" appended for the Rust output
+            ElementInfo::Int(value) => match value.trim_end_matches(" as i64").parse::<i64>() {
+                Ok(i) => Ok(Value::Int(i)),
+                Err(_) => Err(get_error(compiler, el_index, "Not a valid i64")),
+            },
+            ElementInfo::Float(value) => match value.parse::<f64>() {
+                Ok(float) => Ok(Value::Float(float)),
+                Err(_) => Err(get_error(compiler, el_index, "Not a valid f64")),
+            },
+            ElementInfo::String(value) => Ok(Value::String(
+                parse::get_string_value(&value).unwrap_or_default(),
+            )),
+            ElementInfo::Bool(value) => Ok(Value::Bool(value == "true")),
+            ElementInfo::Arg(name, _, _, _) => self.get_value(compiler, el_index, &name),
+            ElementInfo::ConstantRef(name, _, _) => self.get_value(compiler, el_index, &name),
+            ElementInfo::Rust(_, _) => Err(get_error(
+                compiler,
+                el_index,
+                "Rust code can't be evaluated, try `toylang run` instead",
+            )),
+            ElementInfo::Struct(name, _, _) => {
+                let mut keys = vec![];
+                for child_ref in children {
+                    keys.push(self.eval_struct_key(compiler, child_ref)?);
+                }
+                Ok(Value::Struct(name, keys))
+            }
+            ElementInfo::StructEdit(name, _) => {
+                let value = self.eval_first_child(compiler, el_index, &children)?;
+                self.set_struct_key(compiler, el_index, &name, value)?;
+                Ok(Value::Unit)
+            }
+            ElementInfo::Constant(name, _) => {
+                let value = self.eval_first_child(compiler, el_index, &children)?;
+                self.set_value(name, value);
+                Ok(Value::Unit)
+            }
+            ElementInfo::Assignment => {
+                self.eval_first_child(compiler, el_index, &children)?;
+                Ok(Value::Unit)
+            }
+            ElementInfo::InbuiltFunctionCall(name, _, _) => {
+                self.eval_inbuilt_function_call(compiler, el_index, &name, &children)
+            }
+            ElementInfo::FunctionCall(name, skip_args, _) => {
+                let function = self.get_function(compiler, el_index, &name)?;
+                if skip_args {
+                    return Ok(function);
+                }
+                let mut args = vec![];
+                for child_ref in children {
+                    args.push(self.eval(compiler, child_ref)?);
+                }
+                self.call_function(compiler, el_index, &function, args)
+            }
+            ElementInfo::Parens => {
+                if children.len() == 1 {
+                    self.eval(compiler, children[0])
+                } else {
+                    Ok(Value::Unit)
+                }
+            }
+            ElementInfo::LoopForRange(name, from, to) => {
+                for i in from..to {
+                    self.set_value(name.clone(), Value::Int(i as i64));
+                    self.eval_block(compiler, &all_children)?;
+                }
+                Ok(Value::Unit)
+            }
+            ElementInfo::Println => {
+                let value = self.eval_first_child(compiler, el_index, &children)?;
+                let text = value.to_string();
+                if self.echo {
+                    println!("{}", text);
+                }
+                self.printed.push(text);
+                Ok(Value::Unit)
+            }
+            ElementInfo::If(_) => {
+                if children.len() != 3 {
+                    return Err(get_error(compiler, el_index, "If is missing arguments"));
+                }
+                match self.eval(compiler, children[0])? {
+                    Value::Bool(true) => self.eval(compiler, children[1]),
+                    Value::Bool(false) => self.eval(compiler, children[2]),
+                    _ => Err(get_error(compiler, el_index, "If condition is not a bool")),
+                }
+            }
+            // definitions are looked up when they are called
+            ElementInfo::FunctionDef(_, _, _, _) => Ok(Value::Unit),
+            // explicitly listing other types rather than using _ to not overlook new types in future
+            ElementInfo::InbuiltFunctionDef(_, _, _, _, _, _) => Ok(Value::Unit),
+            ElementInfo::CommentSingleLine(_) => Ok(Value::Unit),
+            ElementInfo::Type(_) => Ok(Value::Unit),
+            ElementInfo::Eol => Ok(Value::Unit),
+            ElementInfo::Seol => Ok(Value::Unit),
+            ElementInfo::Indent => Ok(Value::Unit),
+            ElementInfo::Unused => Ok(Value::Unit),
+            ElementInfo::FunctionDefWIP => Ok(Value::Unit),
+            ElementInfo::LoopForRangeWIP => Ok(Value::Unit),
+        }
+    }
+
+    fn eval_first_child(
+        &mut self,
+        compiler: &Compiler,
+        el_index: ElIndex,
+        children: &[ElIndex],
+    ) -> Result<Value, String> {
+        match children.first() {
+            Some(&child_ref) => self.eval(compiler, child_ref),
+            None => Err(get_error(compiler, el_index, "Missing value")),
+        }
+    }
+
+    /// Struct keys are either Assignment > Constant > Value, or a ConstantRef using a constant of the same name
+    fn eval_struct_key(
+        &mut self,
+        compiler: &Compiler,
+        el_index: ElIndex,
+    ) -> Result<(String, Value), String> {
+        let (el_info, children) = compiler.ast.elements[el_index].clone();
+        let children = get_value_children(compiler, &children);
+        match el_info {
+            ElementInfo::Assignment if !children.is_empty() => {
+                self.eval_struct_key(compiler, children[0])
+            }
+            ElementInfo::Constant(name, _) => {
+                let value = self.eval_first_child(compiler, el_index, &children)?;
+                Ok((name, value))
+            }
+            ElementInfo::ConstantRef(name, _, _) => {
+                let value = self.get_value(compiler, el_index, &name)?;
+                Ok((name, value))
+            }
+            _ => Err(get_error(compiler, el_index, "Not a valid struct key")),
+        }
+    }
+
+    fn eval_inbuilt_function_call(
+        &mut self,
+        compiler: &Compiler,
+        el_index: ElIndex,
+        name: &str,
+        children: &[ElIndex],
+    ) -> Result<Value, String> {
+        let mut args = vec![];
+        for &child_ref in children {
+            args.push(self.eval(compiler, child_ref)?);
+        }
+        match (name, args.as_slice()) {
+            ("List::map", [Value::List(list), function]) => {
+                let mut items = vec![];
+                for item in list {
+                    items.push(self.call_function(
+                        compiler,
+                        el_index,
+                        function,
+                        vec![item.clone()],
+                    )?);
+                }
+                Ok(Value::List(items))
+            }
+            ("List::mapindex", [Value::List(list), function]) => {
+                let mut items = vec![];
+                for (index, item) in list.iter().enumerate() {
+                    items.push(self.call_function(
+                        compiler,
+                        el_index,
+                        function,
+                        vec![Value::Int(index as i64), item.clone()],
+                    )?);
+                }
+                Ok(Value::List(items))
+            }
+            _ => apply_inbuilt_function(name, &args)
+                .map_err(|msg| get_error(compiler, el_index, &msg)),
+        }
+    }
+
+    /// Calls a FunctionDef with a new frame for its args
+    fn call_function(
+        &mut self,
+        compiler: &Compiler,
+        el_index: ElIndex,
+        function: &Value,
+        args: Vec<Value>,
+    ) -> Result<Value, String> {
+        let function_index = match function {
+            Value::Function(function_index) => *function_index,
+            _ => return Err(get_error(compiler, el_index, "Not a function")),
+        };
+        let (el_info, children) = compiler.ast.elements[function_index].clone();
+        let argnames = match el_info {
+            ElementInfo::FunctionDef(_, argnames, _, _) => argnames,
+            _ => return Err(get_error(compiler, el_index, "Not a function")),
+        };
+        if argnames.len() != args.len() {
+            return Err(get_error(
+                compiler,
+                el_index,
+                "Wrong number of arguments for function",
+            ));
+        }
+        self.frames.push(argnames.into_iter().zip(args).collect());
+        let result = self.eval_block(compiler, &children);
+        self.frames.pop();
+        result
+    }
+
+    /// Functions are either values of args or constants, e.g. when passed as arguments, or FunctionDefs
+    fn get_function(
+        &self,
+        compiler: &Compiler,
+        el_index: ElIndex,
+        name: &String,
+    ) -> Result<Value, String> {
+        if let Some(value) = self.lookup(name) {
+            return Ok(value.clone());
+        }
+        match elements::get_function_index_by_name(&compiler.ast, name) {
+            Some(function_index) => Ok(Value::Function(function_index)),
+            None => Err(get_error(
+                compiler,
+                el_index,
+                &format!("Function '{}' is not defined", name),
+            )),
+        }
+    }
+
+    /// Constants can also refer to functions, e.g. a function name passed as an argument
+    fn get_value(
+        &self,
+        compiler: &Compiler,
+        el_index: ElIndex,
+        name: &String,
+    ) -> Result<Value, String> {
+        if let Some(value) = self.lookup(name) {
+            return Ok(value.clone());
+        }
+        if let Some(function_index) = elements::get_function_index_by_name(&compiler.ast, name) {
+            return Ok(Value::Function(function_index));
+        }
+        Err(get_error(
+            compiler,
+            el_index,
+            &format!("Constant '{}' has no value", name),
+        ))
+    }
+
+    /// Looks in the current frame, then the root
+    fn lookup(&self, name: &String) -> Option<&Value> {
+        let current = self.frames.last().and_then(|frame| frame.get(name));
+        current.or_else(|| self.frames[0].get(name))
+    }
+
+    fn set_value(&mut self, name: String, value: Value) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.insert(name, value);
+        }
+    }
+
+    /// StructEdit names are "constant.key"
+    fn set_struct_key(
+        &mut self,
+        compiler: &Compiler,
+        el_index: ElIndex,
+        name: &str,
+        value: Value,
+    ) -> Result<(), String> {
+        let (constant, key) = name.split_once('.').unwrap_or((name, ""));
+        let last = self.frames.len() - 1;
+        let frame_index = if self.frames[last].contains_key(constant) {
+            last
+        } else {
+            0
+        };
+        match self.frames[frame_index].get_mut(constant) {
+            Some(Value::Struct(_, keys)) => match keys.iter_mut().find(|(k, _)| k == key) {
+                Some((_, v)) => {
+                    *v = value;
+                    Ok(())
+                }
+                None => Err(get_error(
+                    compiler,
+                    el_index,
+                    &format!("Struct '{}' has no key '{}'", constant, key),
+                )),
+            },
+            _ => Err(get_error(
+                compiler,
+                el_index,
+                &format!("'{}' is not a struct", constant),
+            )),
+        }
+    }
+}
+
+/// Inbuilt functions which don't call another function, shared with the [vm](crate::vm).
+/// Returns the message of the runtime error, without its location
+pub(crate) fn apply_inbuilt_function(name: &str, args: &[Value]) -> Result<Value, String> {
+    match (name, args) {
+        ("true", []) => Ok(Value::Bool(true)),
+        ("false", []) => Ok(Value::Bool(false)),
+        ("+" | "-" | "*" | "/" | "%", [Value::Int(a), Value::Int(b)]) => {
+            let (result, msg) = match name {
+                "+" => (a.checked_add(*b), "attempt to add with overflow"),
+                "-" => (a.checked_sub(*b), "attempt to subtract with overflow"),
+                "*" => (a.checked_mul(*b), "attempt to multiply with overflow"),
+                "/" if *b == 0 => (None, "attempt to divide by zero"),
+                "/" => (a.checked_div(*b), "attempt to divide with overflow"),
+                _ if *b == 0 => (
+                    None,
+                    "attempt to calculate the remainder with a divisor of zero",
+                ),
+                _ => (
+                    a.checked_rem(*b),
+                    "attempt to calculate the remainder with overflow",
+                ),
+            };
+            result.map(Value::Int).ok_or_else(|| msg.to_string())
+        }
+        ("+" | "-" | "*" | "/" | "%", [Value::Float(a), Value::Float(b)]) => {
+            Ok(Value::Float(match name {
+                "+" => a + b,
+                "-" => a - b,
+                "*" => a * b,
+                "/" => a / b,
+                _ => a % b,
+            }))
+        }
+        ("==", [a, b]) => Ok(Value::Bool(a == b)),
+        ("!=", [a, b]) => Ok(Value::Bool(a != b)),
+        ("<" | ">" | "<=" | ">=", [a, b]) => {
+            let ordering = match (a, b) {
+                (Value::Int(a), Value::Int(b)) => a.partial_cmp(b),
+                (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+                (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+                _ => return Err("Can't compare these values".to_string()),
+            };
+            Ok(Value::Bool(match (name, ordering) {
+                (_, None) => false,
+                ("<", Some(ordering)) => ordering.is_lt(),
+                (">", Some(ordering)) => ordering.is_gt(),
+                ("<=", Some(ordering)) => ordering.is_le(),
+                (_, Some(ordering)) => ordering.is_ge(),
+            }))
+        }
+        ("List::len", [Value::List(list)]) => Ok(Value::Int(list.len() as i64)),
+        ("List::reverse", [Value::List(list)]) => {
+            Ok(Value::List(list.iter().rev().cloned().collect()))
+        }
+        ("List::append", [Value::List(list1), Value::List(list2)]) => {
+            Ok(Value::List(list1.iter().chain(list2).cloned().collect()))
+        }
+        _ => Err(format!(
+            "Can't evaluate inbuilt function '{}' with these arguments",
+            name
+        )),
+    }
+}
+
+/// Formats the error at the Token of the Element, if it has one
+fn get_error(compiler: &Compiler, el_index: ElIndex, msg: &str) -> String {
+    match compiler.ast.element_tokens.get(el_index) {
+        Some(token) if !token.0.is_empty() => errors::get_error_for_token(compiler, token, msg),
+        _ => format!("----------\r\n{}", msg),
+    }
+}
+
+/// Compiles the text, then evaluates it. Returns the printed lines, or the compile or runtime errors
+pub fn eval_str(text: &str, echo: bool) -> Result<Vec<String>, Vec<String>> {
+    let mut compiler = Compiler::default();
+    compiler.file.filecontents = text.to_string();
+    let _ = compiler.run_main_tasks();
+    if !compiler.error_stack.is_empty() {
+        return Err(compiler.get_rendered_errors());
+    }
+    let mut interpreter = Interpreter::new(echo);
+    match interpreter.run(&compiler) {
+        Ok(_) => Ok(interpreter.printed),
+        Err(error) => Err(vec![error]),
+    }
+}
+
+/// CLI - compile the toylang file, then evaluate it
+pub fn main(filepath: String) {
+    let mut compiler = Compiler::default();
+    if let Err(e) = compiler.file.get(&filepath, false) {
+        eprintln!("Problem reading file: {}", e);
+        process::exit(1);
+    }
+    let _ = compiler.run_main_tasks();
+    compiler.print_diagnostics(false);
+    if !compiler.error_stack.is_empty() {
+        process::exit(1);
+    }
+    let mut interpreter = Interpreter::new(true);
+    if let Err(error) = interpreter.run(&compiler) {
+        eprintln!("----------\r\n\r\nTOYLANG RUNTIME ERROR:");
+        eprintln!("{}", error);
+        eprintln!("----------\r\n");
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn printed(text: &str) -> Vec<String> {
+        eval_str(text, false).unwrap()
+    }
+
+    #[test]
+    fn test_eval() {
+        let test_case_passes = vec![
+            ("= a \\ i64 => 123\r\n@ a", vec!["123"]),
+            ("@ + 1 2", vec!["3"]),
+            ("= a 1.5\r\n@ * a 2.0", vec!["3"]),
+            ("@ \"hello\"", vec!["hello"]),
+            ("@ == 1 1", vec!["true"]),
+            ("@ < 2 1", vec!["false"]),
+            ("= a ? == 1 2 \"yes\" \"no\"\r\n@ a", vec!["no"]),
+            (
+                "= a \\ i64 i64 i64 arg1 arg2 =>\r\n= b + arg1 123\r\n- b arg2\r\n@ a 1 2",
+                vec!["122"],
+            ),
+            (".. b 0 3\r\n@ b\r\n.", vec!["0", "1", "2"]),
+        ];
+        for (input, expected) in test_case_passes {
+            assert_eq!(printed(input), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_eval_recursion() {
+        let fib = "= fibonacci \\ i64 i64 n => ? < n 2 1 + fibonacci - n 1 fibonacci - n 2\r\n@ fibonacci 10";
+        assert_eq!(printed(fib), vec!["89"]);
+    }
+
+    #[test]
+    fn test_eval_lists_and_structs() {
+        assert_eq!(
+            printed("= list [ 1 2 3 ]\r\n= mapfn \\ i64 i64 i => * i 100\r\n= mapped List::map list ( mapfn )\r\n@ mapped"),
+            vec!["[100, 200, 300]"]
+        );
+        assert_eq!(
+            printed("= list [ 1 2 3 ]\r\n= len List::len list\r\n@ len"),
+            vec!["3"]
+        );
+        assert_eq!(
+            printed("= newstruct { = firstname \"firstname\" = age 21 }\r\n= newstruct.age 99\r\n@ newstruct"),
+            vec!["Newstruct { firstname: \"firstname\", age: 99 }"]
+        );
+    }
+
+    #[test]
+    fn test_eval_runtime_error() {
+        let errors = eval_str("= a 0\r\n@ / 1 a", false).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("attempt to divide by zero"));
+    }
+}
@@ -0,0 +1,482 @@
+/*! Long-form explanations of compile errors, i.e. `toylang explain`, similar to `rustc --explain`
+
+    Every error has a stable code, which is printed with it, e.g. `error[T0038]`. Pass the code to get an explanation,
+    with an example of toylang code which causes the error, and the same code fixed.
+
+    ```bash
+    toylang explain T0038
+    ```
+*/
+use crate::errors::ERRORS;
+use std::process;
+
+/// Why an error happens, and how to fix it
+struct Explanation {
+    code: &'static str,
+    description: &'static str,
+    /// toylang code which causes the error, and the same code fixed.
+    /// None if no toylang code currently causes it, e.g. internal errors
+    example: Option<(&'static str, &'static str)>,
+}
+
+const EXPLANATIONS: &[Explanation] = &[
+    Explanation {
+        code: "T0001",
+        description: "A line starting with a forward slash is a comment, but a comment must start with two forward slashes.",
+        example: Some(("/this is a comment", "// this is a comment")),
+    },
+    Explanation {
+        code: "T0002",
+        description: "A comment was found straight after \"=\", where the name of the constant or function being defined should be.\r\nA comment must be on a line of its own.",
+        example: Some(("= // the answer", "// the answer\r\n= answer 42")),
+    },
+    Explanation {
+        code: "T0003",
+        description: "A comment was found where the value of a constant should be.\r\nA comment must be on a line of its own.",
+        example: Some(("= answer // 42", "// 42\r\n= answer 42")),
+    },
+    Explanation {
+        code: "T0004",
+        description: "A comment was found where an argument of an inbuilt function, like + or List::len, should be.\r\nA comment must be on a line of its own.",
+        example: Some(("= total + 1 // 2", "// 2\r\n= total + 1 2")),
+    },
+    Explanation {
+        code: "T0005",
+        description: "A comment was found where an argument of a function call should be.\r\nA comment must be on a line of its own.",
+        example: Some((
+            "= double \\ i64 i64 x => * x 2\r\n= answer double // 21",
+            "= double \\ i64 i64 x => * x 2\r\n// 21\r\n= answer double 21",
+        )),
+    },
+    Explanation {
+        code: "T0006",
+        description: "A comment was found inside parenthesis. Parenthesis can only contain the types of a function in a function definition, or the name of a function passed as a reference.\r\nA comment must be on a line of its own.",
+        example: Some((
+            "= list [ 1 2 ]\r\n= double \\ i64 i64 x => * x 2\r\n= doubled List::map list ( // double )",
+            "= list [ 1 2 ]\r\n= double \\ i64 i64 x => * x 2\r\n// double\r\n= doubled List::map list ( double )",
+        )),
+    },
+    Explanation {
+        code: "T0007",
+        description: "An int was found straight after \"=\", where the name of the constant or function being defined should be.",
+        example: Some(("= 42", "= answer 42")),
+    },
+    Explanation {
+        code: "T0008",
+        description: "An int was found inside parenthesis. Parenthesis can only contain the types of a function in a function definition, or the name of a function passed as a reference.",
+        example: Some((
+            "= list [ 1 2 ]\r\n= doubled List::map list ( 2 )",
+            "= list [ 1 2 ]\r\n= double \\ i64 i64 x => * x 2\r\n= doubled List::map list ( double )",
+        )),
+    },
+    Explanation {
+        code: "T0009",
+        description: "A float was found straight after \"=\", where the name of the constant or function being defined should be.",
+        example: Some(("= 1.5", "= half 1.5")),
+    },
+    Explanation {
+        code: "T0010",
+        description: "A float was found inside parenthesis. Parenthesis can only contain the types of a function in a function definition, or the name of a function passed as a reference.",
+        example: Some((
+            "= list [ 1.5 2.5 ]\r\n= doubled List::map list ( 2.0 )",
+            "= list [ 1.5 2.5 ]\r\n= double \\ f64 f64 x => * x 2.0\r\n= doubled List::map list ( double )",
+        )),
+    },
+    Explanation {
+        code: "T0011",
+        description: "A string was found straight after \"=\", where the name of the constant or function being defined should be.",
+        example: Some(("= \"hello\"", "= greeting \"hello\"")),
+    },
+    Explanation {
+        code: "T0012",
+        description: "A string was found inside parenthesis. Parenthesis can only contain the types of a function in a function definition, or the name of a function passed as a reference.",
+        example: Some((
+            "= list [ 1 2 ]\r\n= double \\ i64 i64 x => * x 2\r\n= doubled List::map list ( \"double\" )",
+            "= list [ 1 2 ]\r\n= double \\ i64 i64 x => * x 2\r\n= doubled List::map list ( double )",
+        )),
+    },
+    Explanation {
+        code: "T0013",
+        description: "A constant which isn't a function was found inside parenthesis. Parenthesis can only contain the types of a function in a function definition, or the name of a function passed as a reference.",
+        example: Some((
+            "= list [ 1 2 ]\r\n= two 2\r\n= doubled List::map list ( two )",
+            "= list [ 1 2 ]\r\n= double \\ i64 i64 x => * x 2\r\n= doubled List::map list ( double )",
+        )),
+    },
+    Explanation {
+        code: "T0014",
+        description: "\"=\" was found where the value of a constant should be. Each \"=\" defines one constant, so each definition needs its own line.",
+        example: Some(("= a = b 1", "= b 1\r\n= a b")),
+    },
+    Explanation {
+        code: "T0015",
+        description: "\"=\" was found where an argument of an inbuilt function, like + or List::len, should be. A constant can't be defined inside an expression, so define it on the line before.",
+        example: Some(("= total + 1 = b 2", "= b 2\r\n= total + 1 b")),
+    },
+    Explanation {
+        code: "T0016",
+        description: "\"=\" was found where an argument of a function call should be. A constant can't be defined inside an expression, so define it on the line before.",
+        example: Some((
+            "= double \\ i64 i64 x => * x 2\r\n= answer double = b 21",
+            "= double \\ i64 i64 x => * x 2\r\n= b 21\r\n= answer double b",
+        )),
+    },
+    Explanation {
+        code: "T0017",
+        description: "\"=\" was found straight after \"=\", where the name of the constant or function being defined should be.",
+        example: Some(("= = answer 42", "= answer 42")),
+    },
+    Explanation {
+        code: "T0018",
+        description: "\"=\" was found inside parenthesis. Parenthesis can only contain the types of a function in a function definition, or the name of a function passed as a reference.",
+        example: Some((
+            "= list [ 1 2 ]\r\n= doubled List::map list ( = double 2 )",
+            "= list [ 1 2 ]\r\n= double \\ i64 i64 x => * x 2\r\n= doubled List::map list ( double )",
+        )),
+    },
+    Explanation {
+        code: "T0019",
+        description: "\"=\" was found inside a list. The items of a list are values, so define any constants on the lines before.",
+        example: Some(("= list [ = a 1 ]", "= a 1\r\n= list [ a ]")),
+    },
+    Explanation {
+        code: "T0020",
+        description: "An inbuilt function, like + or List::len, was found inside parenthesis. Parenthesis can only contain the types of a function in a function definition, or the name of a function passed as a reference, and inbuilt functions can't be passed as a reference.\r\nDefine a function which calls the inbuilt function, and pass that instead.",
+        example: Some((
+            "= list [ 1 2 ]\r\n= added List::map list ( + )",
+            "= list [ 1 2 ]\r\n= add_one \\ i64 i64 x => + x 1\r\n= added List::map list ( add_one )",
+        )),
+    },
+    Explanation {
+        code: "T0021",
+        description: "A function was called with more or fewer arguments than its definition has.\r\nThis is not currently reported by the toylang compiler.",
+        example: None,
+    },
+    Explanation {
+        code: "T0022",
+        description: "A function was called inside parenthesis. Parenthesis can only contain the types of a function in a function definition, or the name of a function passed as a reference, so the function is passed, not called.",
+        example: None,
+    },
+    Explanation {
+        code: "T0023",
+        description: "A line starts with parenthesis. Parenthesis can only contain the types of a function in a function definition, or the name of a function passed as a reference.",
+        example: Some((
+            "( i64 i64 )",
+            "= apply \\ ( i64 i64 ) i64 i64 f x => f x",
+        )),
+    },
+    Explanation {
+        code: "T0024",
+        description: "Parenthesis were found as the value of a constant. Parenthesis can only contain the types of a function in a function definition, or the name of a function passed as a reference.",
+        example: Some(("= a ( i64 )", "= a 42")),
+    },
+    Explanation {
+        code: "T0025",
+        description: "Parenthesis were found straight after \"=\", where the name of the constant or function being defined should be.",
+        example: Some(("= ( i64 ) 42", "= a 42")),
+    },
+    Explanation {
+        code: "T0026",
+        description: "\"@\" prints its value, and can only be used at the start of a line, or as the value of a constant, or the last line of a function.",
+        example: Some(("= list [ @ 1 ]", "= list [ 1 ]\r\n@ list")),
+    },
+    Explanation {
+        code: "T0027",
+        description: "\"\\\" starts the definition of a function, so it can only be used straight after the name of a constant, i.e. \"= name \\\".\r\nAfter \"\\\" come the types of each argument, then the return type, then the names of each argument, then \"=>\", then the body of the function, which is its return value.\r\nThe body can be on the same line, or on the following lines for a longer function, where the last line is the return value.\r\nA function can't be defined inside an expression, or as an argument, so define it on a line of its own and refer to it by its name.",
+        example: Some((
+            "\\ i64 i64 x => * x 2",
+            "= double \\ i64 i64 x => * x 2\r\n= quadruple \\ i64 i64 x =>\r\n    = twice double x\r\n    double twice\r\n@ quadruple 3",
+        )),
+    },
+    Explanation {
+        code: "T0028",
+        description: "A token starting with a quote mark is a string, which must also end with a quote mark. A quote mark inside it must be escaped with a backslash, i.e. \\\", unless the string is triple-quoted, i.e. \"\"\"...\"\"\", which can also span lines.",
+        example: Some(("= greeting \"hello", "= greeting \"hello\"")),
+    },
+    Explanation {
+        code: "T0029",
+        description: "\"=\" must be followed by a space, then the name of the constant or function being defined.",
+        example: Some(("=answer 42", "= answer 42")),
+    },
+    Explanation {
+        code: "T0030",
+        description: "A list is its items between [ and ], with spaces between each of them. The type of an empty list can't be inferred, so it must contain its type instead, e.g. [ i64 ].",
+        example: Some(("= list [ ]", "= list [ i64 ]")),
+    },
+    Explanation {
+        code: "T0031",
+        description: "A struct is one or more assignments of a value to a key between { and }, with spaces between each of them.",
+        example: Some(("= person { }", "= person { = firstname \"Ann\" }")),
+    },
+    Explanation {
+        code: "T0032",
+        description: "A token starting with a digit is an int, or a float if it has a decimal point, so it can only contain digits, and a single decimal point.\r\nNames of constants must start with a lowercase letter or an underscore.",
+        example: Some(("= 2nd 2", "= second 2")),
+    },
+    Explanation {
+        code: "T0033",
+        description: "Ints are 64 bit (i64), so they must be within the values -9223372036854775808 to 9223372036854775807.",
+        example: Some(("= big 9223372036854775808", "= big 9223372036854775807")),
+    },
+    Explanation {
+        code: "T0034",
+        description: "A token starting with \"-\" followed by another character is a negative int or float, so it must be a digit.\r\nTo subtract, put a space after \"-\".",
+        example: None,
+    },
+    Explanation {
+        code: "T0035",
+        description: "A token starting with a digit and containing a decimal point is a float, which must be digits, then a single decimal point, then digits.",
+        example: None,
+    },
+    Explanation {
+        code: "T0036",
+        description: "A function definition must have a type for each argument, plus the return type, before the names of the arguments.",
+        example: Some((
+            "= add \\ i64 i64 a b => + a b",
+            "= add \\ i64 i64 i64 a b => + a b",
+        )),
+    },
+    Explanation {
+        code: "T0037",
+        description: "In a function definition, the types of all the arguments, and the return type, come before the names of the arguments.",
+        example: Some((
+            "= double \\ x i64 i64 => * x 2",
+            "= double \\ i64 i64 x => * x 2",
+        )),
+    },
+    Explanation {
+        code: "T0038",
+        description: "Constants are immutable, so they can only be defined once, and can't be assigned a new value. Define a new constant instead.",
+        example: Some(("= a 1\r\n= a + a 1", "= a 1\r\n= b + a 1")),
+    },
+    Explanation {
+        code: "T0039",
+        description: "A name was used which hasn't been defined yet. Constants and functions must be defined, with \"=\", before they are used. If a similar name has been defined, it is suggested, in case of a typo.",
+        example: Some(("@ answer", "= answer 42\r\n@ answer")),
+    },
+    Explanation {
+        code: "T0040",
+        description: "A struct can only be used as the value of a constant, e.g. = a { = key 123 }.",
+        example: Some(("@ { = age 21 }", "= person { = age 21 }\r\n@ person")),
+    },
+    Explanation {
+        code: "T0041",
+        description: "A key of a struct can only be changed by assigning a new value to it, e.g. = structname.keyname newvalue",
+        example: Some((
+            "= person { = age 21 }\r\n@ person.age",
+            "= person { = age 21 }\r\n= person.age 22",
+        )),
+    },
+    Explanation {
+        code: "T0042",
+        description: "A line starting with \".\" ends a for loop, so it must be on its own, and a for loop starts with \"..\" followed by a space.",
+        example: Some((
+            ".i 0 3\r\n@ i\r\n.",
+            ".. i 0 3\r\n@ i\r\n.",
+        )),
+    },
+    Explanation {
+        code: "T0043",
+        description: "A for loop must be on a line of its own, in the body of a function or at the top level, since it has no value.",
+        example: Some((
+            "= a .. i 0 3\r\n@ i\r\n.",
+            ".. i 0 3\r\n@ i\r\n.",
+        )),
+    },
+    Explanation {
+        code: "T0044",
+        description: "A line with \".\" ends a for loop, but no for loop has been started with \"..\".",
+        example: Some(("@ 1\r\n.", ".. i 0 3\r\n@ i\r\n.")),
+    },
+    Explanation {
+        code: "T0045",
+        description: "A for loop starts with \"..\" then the name of the loop variable, then the start and end of its range, e.g. .. i 0 3 for 0, 1 and 2.",
+        example: Some((".. i 3\r\n@ i\r\n.", ".. i 0 3\r\n@ i\r\n.")),
+    },
+    Explanation {
+        code: "T0046",
+        description: "A list was found somewhere it can't be used, e.g. inside parenthesis.",
+        example: Some((
+            "= list [ 1 2 ]\r\n= doubled List::map list ( [ 2 ] )",
+            "= list [ 1 2 ]\r\n= double \\ i64 i64 x => * x 2\r\n= doubled List::map list ( double )",
+        )),
+    },
+    Explanation {
+        code: "T0047",
+        description: "A line starting with \"#\" is raw rust code, which is output as it is. \"#\" must be followed by another \"#\" for rust code in the main function, or two more for rust code before the main function, then the code.",
+        example: Some((
+            "#use std::collections::HashMap;",
+            "###use std::collections::HashMap;",
+        )),
+    },
+    Explanation {
+        code: "T0048",
+        description: "An internal error in the toylang compiler, which should be impossible. Please report it, with the toylang code which caused it.",
+        example: None,
+    },
+    Explanation {
+        code: "T0049",
+        description: "There is an issue with the raw rust code on a line starting with \"#\".",
+        example: None,
+    },
+    Explanation {
+        code: "T0050",
+        description: "An internal error in the toylang compiler, where the Rust code output for an element isn't valid Rust, so output.rs isn't saved. Please report it, with the toylang code which caused it.",
+        example: None,
+    },
+    Explanation {
+        code: "T0051",
+        description: "With --target wat, only i64, f64 and bool values, arithmetic, comparisons, ? (If), and functions can be output as WebAssembly. Strings, lists, structs, loops and printing aren't supported.",
+        example: Some(("= a \"hello\"", "= a 1.5")),
+    },
+    Explanation {
+        code: "T0052",
+        description: "With --target c, only i64, f64 and bool values, arithmetic, comparisons, ? (If), .. loops, @ (printing) and functions can be output as C. Strings, lists and structs aren't supported.",
+        example: Some(("= a \"hello\"", "= a 1.5")),
+    },
+    Explanation {
+        code: "T0053",
+        description: "Only used to test the toylang compiler.",
+        example: None,
+    },
+    Explanation {
+        code: "T0054",
+        description: "An internal error in the toylang compiler, where a token couldn't be parsed as anything. Please report it, with the toylang code which caused it.",
+        example: None,
+    },
+    Explanation {
+        code: "T0055",
+        description: "A name starting with an uppercase letter is an inbuilt function, but there isn't one with this name. If a similar inbuilt function exists, it is suggested, in case of a typo.",
+        example: Some((
+            "= list [ 1 2 ]\r\n= len List::lenn list",
+            "= list [ 1 2 ]\r\n= len List::len list",
+        )),
+    },
+    Explanation {
+        code: "T0056",
+        description: "A warning, from the unused_constants lint. A constant was defined, but never used.\r\nRemove it, or start its name with an underscore if it is intended, e.g. = _b 2",
+        example: Some(("= a 1\r\n= b 2\r\n@ a", "= a 1\r\n@ a")),
+    },
+    Explanation {
+        code: "T0057",
+        description: "A warning, from the unused_args lint. A function has an argument which its body never uses.\r\nUse it, or start its name with an underscore if it is intended, e.g. _x",
+        example: Some((
+            "= double \\ i64 i64 x => * 2 2\r\n@ double 1",
+            "= double \\ i64 i64 x => * x 2\r\n@ double 1",
+        )),
+    },
+    Explanation {
+        code: "T0058",
+        description: "A warning, from the unused_functions lint. A function was defined, but never called, or passed to another function.\r\nRemove it, or start its name with an underscore if it is intended.",
+        example: Some((
+            "= double \\ i64 i64 x => * x 2\r\n@ 2",
+            "= double \\ i64 i64 x => * x 2\r\n@ double 1",
+        )),
+    },
+    Explanation {
+        code: "T0059",
+        description: "A warning, from the unreachable_code lint. A function ends at its return expression, i.e. the first line of its body which isn't an assignment.\r\nAny lines after it are no longer part of the function, even if they are indented like its body, so they never run when it is called.\r\nMove them before the return expression.",
+        example: Some((
+            "= f \\ i64 i64 x =>\r\n    = y + x 1\r\n    y\r\n    @ y\r\n@ f 1",
+            "= f \\ i64 i64 x =>\r\n    = y + x 1\r\n    @ y\r\n    y\r\n@ f 1",
+        )),
+    },
+    Explanation {
+        code: "T0060",
+        description: "A backslash in a string starts an escape, for a char which can't be typed in the string as it is.\r\nThe escapes are \\\" for a quote mark, \\\\ for a backslash, \\n for a new line, \\r for a carriage return, \\t for a tab, and \\u{..} for any unicode character, with 1 to 6 hex digits.",
+        example: Some(("@ \"C:\\new\\data\"", "@ \"C:\\\\new\\\\data\"")),
+    },
+    Explanation {
+        code: "T0061",
+        description: "From `toylang run`, where rustc found an error in the Rust code output for the toylang code, e.g. a function using a constant defined outside it.\r\nrustc's own error and its code are in the note, which `rustc --explain <code>` describes.",
+        example: None,
+    },
+];
+
+/// The explanation of the error code, as printed by `toylang explain`
+pub fn get_explanation(code: &str) -> Option<String> {
+    let code = code.to_uppercase();
+    let error = ERRORS.get_by_code(&code)?;
+    let explanation = EXPLANATIONS.iter().find(|e| e.code == code)?;
+    let mut text = format!(
+        "{}: {}\r\n\r\n{}\r\n",
+        error.code, error.message, explanation.description
+    );
+    if let Some((erroneous, fixed)) = explanation.example {
+        text.push_str(&format!(
+            "\r\nErroneous code example:\r\n\r\n{}\r\n\r\nFixed:\r\n\r\n{}\r\n",
+            indent(erroneous),
+            indent(fixed)
+        ));
+    }
+    Some(text)
+}
+
+fn indent(code: &str) -> String {
+    code.split("\r\n")
+        .map(|line| format!("    {}", line))
+        .collect::<Vec<String>>()
+        .join("\r\n")
+}
+
+/// CLI - print the explanation of the error code
+pub fn main(code: String) {
+    match get_explanation(&code) {
+        Some(explanation) => print!("{}", explanation),
+        None => {
+            eprintln!("No error with the code: {}", code);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint::Level;
+    use crate::{compile_str, Options, Target};
+
+    #[test]
+    fn test_every_error_is_explained() {
+        for error in ERRORS.all() {
+            assert!(get_explanation(error.code).is_some(), "{}", error.code);
+        }
+        assert_eq!(EXPLANATIONS.len(), ERRORS.all().len());
+    }
+
+    #[test]
+    fn test_examples() {
+        let lints = [
+            ERRORS.unused_constant,
+            ERRORS.unused_arg,
+            ERRORS.unused_function,
+            ERRORS.unreachable_code,
+        ];
+        for explanation in EXPLANATIONS {
+            if let Some((erroneous, fixed)) = explanation.example {
+                let target = match explanation.code {
+                    "T0051" => Target::Wat,
+                    "T0052" => Target::C,
+                    _ => Target::Js,
+                };
+                let mut options = Options {
+                    target,
+                    ..Options::default()
+                };
+                // warnings only stop the output when their lint is denied
+                if lints.iter().any(|lint| lint.code == explanation.code) {
+                    options.lints.set("warnings", Level::Deny).unwrap();
+                }
+                let errors = compile_str(erroneous, options.clone()).unwrap_err();
+                assert_eq!(errors[0].code, explanation.code, "{}", erroneous);
+                assert!(compile_str(fixed, options).is_ok(), "{}", fixed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_explanation() {
+        assert_eq!(get_explanation("t0029"), Some("T0029: Invalid assignment: There are characters directly after '='. It must be followed by a space\r\n\r\n\"=\" must be followed by a space, then the name of the constant or function being defined.\r\n\r\nErroneous code example:\r\n\r\n    =answer 42\r\n\r\nFixed:\r\n\r\n    = answer 42\r\n".to_string()));
+        assert_eq!(get_explanation("T9999"), None);
+    }
+}
@@ -1,5 +1,10 @@
 /*! Stores information about the input File, for use in the Compiler
  */
+use crate::ast::output::{self, Target};
+use crate::ast::Ast;
+use crate::diagnostic::{Diagnostic, Span};
+use crate::errors::ERRORS;
+use crate::formatting;
 use std::env;
 use std::error::Error;
 use std::fmt;
@@ -49,15 +54,7 @@ impl File {
     }
 
     /// Get filename, path, contents from the users supplied filepath
-    pub fn get(
-        self: &mut Self,
-        filepath: &str,
-        tokens: bool,
-        code: bool,
-    ) -> Result<(), Box<dyn Error>> {
-        if !tokens {
-            println!("INPUT:  {:?}", &filepath);
-        }
+    pub fn get(self: &mut Self, filepath: &str, code: bool) -> Result<(), Box<dyn Error>> {
         self.filepath = filepath.to_string().clone();
         if code {
             self.filename = "".to_string();
@@ -77,10 +74,12 @@ impl File {
         Ok(())
     }
 
-    /// Write the output file, except if nosave is true or if there are any compilation errors
+    /// Write the output file and its source map, except if nosave is true or if there are any compilation errors,
+    /// which includes the output not being valid Rust, see validate_output - since that would stop the main toylang crate compiling next time.
+    /// Other targets are saved as they are, e.g. output.js
     pub fn writefile_or_error(
         self: &Self,
-        output: &String,
+        ast: &Ast,
         outputdir: &String,
         is_error: bool,
         tokens: bool,
@@ -94,10 +93,27 @@ impl File {
                 if !tokens {
                     println!("Error saving - DIDN'T SAVE");
                 }
+            } else if ast.target != Target::Rust {
+                let final_path = env::current_dir()
+                    .unwrap()
+                    .join(outputdir)
+                    .join(ast.target.output_filename());
+                fs::write(&final_path, &ast.output)?;
+                if !tokens {
+                    println!("SAVED to {:?}", final_path);
+                }
             } else {
                 let current_dir = env::current_dir().unwrap();
                 let final_path = current_dir.join(outputdir).join("output.rs");
-                fs::write(&final_path, output)?;
+                let (pretty_output, position_map) =
+                    formatting::pretty_print_with_position_map(&ast.output);
+                let source_map_json =
+                    output::get_source_map_json(ast, &self.filename, &pretty_output, &position_map);
+                fs::write(&final_path, pretty_output)?;
+                fs::write(
+                    current_dir.join(outputdir).join("output.rs.map"),
+                    source_map_json,
+                )?;
                 if !tokens {
                     println!("SAVED to {:?}", final_path);
                 }
@@ -105,4 +121,73 @@ impl File {
         }
         Ok(())
     }
+
+    /// Parse the output as a Rust file with syn, and if it's invalid return an internal error
+    /// at the origin Token of the Element which output the invalid code, with syn's error as a note
+    pub fn validate_output(self: &Self, ast: &Ast) -> Result<(), Diagnostic> {
+        if let Err(syn_error) = syn::parse_file(&ast.output) {
+            let start = syn_error.span().start();
+            let output_line = ast.output.lines().nth(start.line.saturating_sub(1));
+            let output_index = ast
+                .output
+                .split_inclusive('\n')
+                .take(start.line.saturating_sub(1))
+                .map(|line| line.len())
+                .sum::<usize>()
+                + output_line
+                    .unwrap_or("")
+                    .chars()
+                    .take(start.column)
+                    .map(|c| c.len_utf8())
+                    .sum::<usize>();
+            let el_index = output::get_element_index_of_output_index(ast, output_index);
+            let span = match el_index {
+                Some(el_index) => Span::from(&ast.element_tokens[el_index]),
+                None => Span::default(),
+            };
+            let mut error = Diagnostic::error(ERRORS.invalid_rust_output, span);
+            if let Some(el_index) = el_index {
+                error = error.with_note(&format!("output by {:?}", ast.elements[el_index].0));
+            }
+            return Err(error.with_note(&format!(
+                "output.rs:{}:{} {}\r\n{}\r\n{}^",
+                start.line,
+                start.column + 1,
+                syn_error,
+                output_line.unwrap_or(""),
+                " ".repeat(start.column),
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsp::compile;
+
+    #[test]
+    fn test_validate_output() {
+        let file = File::new(false);
+        let valid = compile("= a 123\r\n@ a");
+        assert_eq!(file.validate_output(&valid.ast), Ok(()));
+
+        let invalid = compile("= a 123\r\n## let b = ;\r\n@ a");
+        let error = file.validate_output(&invalid.ast).unwrap_err();
+        assert_eq!(error.code, ERRORS.invalid_rust_output.code);
+        assert_eq!(error.span.line, 1);
+        assert!(error.notes[0].contains("Rust(Main):  let b = ;"));
+        assert!(error.notes[1].contains("output.rs:3:14"));
+    }
+
+    #[test]
+    fn test_validate_output_is_rendered_at_the_line_of_the_file() {
+        // the single line function is split after "=>", so the invalid code is on the 4th row, but the 2nd line of the file
+        let mut compiler = compile("= f \\ i64 i64 x => + x 1\r\n## let b = ;\r\n@ f 1");
+        compiler.validate_output();
+        let errors = compiler.get_rendered_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("----------\r\nerror[T0050] ./src/:2:0"));
+    }
 }
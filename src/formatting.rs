@@ -1,4 +1,4 @@
-/*! Only contains get_formatted_argname_argtype_pairs
+/*! Helpers for formatting the output code, e.g. get_formatted_argname_argtype_pairs, and pretty_print for the final output file
  */
 
 /// Formats three vecs into a single string usually for inserting into the arguments of a function
@@ -47,6 +47,162 @@ pub fn get_formatted_argnames(argnames: &Vec<String>) -> String {
     args
 }
 
+/// placeholder macro name which single line comments are temporarily turned into, since syn doesn't keep comments
+const COMMENT_PLACEHOLDER: &str = "__toylang_comment__!";
+
+/// A line (from 1) and column (from 0) in some code, the same as proc_macro2::LineColumn
+pub type LineColumn = (usize, usize);
+
+/// Pairs of the position of each token in the pretty printed output, with the position of the same token in the original output
+pub type PositionMap = Vec<(LineColumn, LineColumn)>;
+
+/// Pretty prints the final output code with prettyplease, so it's consistently formatted regardless of how it was indented while outputting.
+/// Single line comments are kept, unless one is somewhere a statement can't be, e.g. after the return value of a function,
+/// then all comments are removed. If it still can't be parsed it is returned unchanged
+///
+/// # Example
+/// ```
+/// let output = "fn main() {\r\n// comment\r\nlet a: i64 =             1;\r\n}\r\n";
+/// let pretty = toylang::formatting::pretty_print(output);
+/// assert_eq!(pretty, "fn main() {\r\n    // comment\r\n    let a: i64 = 1;\r\n}\r\n");
+/// ```
+pub fn pretty_print(output: &str) -> String {
+    pretty_print_with_position_map(output).0
+}
+
+/// The same as pretty_print, but also returns a PositionMap, so positions in the pretty printed output,
+/// e.g. from rustc errors, can be traced back to the original output, and from there to the Elements which output them
+pub fn pretty_print_with_position_map(output: &str) -> (String, PositionMap) {
+    let with_placeholders = replace_comments(output, |comment| {
+        format!("{}({:?});", COMMENT_PLACEHOLDER, comment)
+    });
+    // Comments become empty lines, so the positions in the original output are unchanged
+    let without_comments = replace_comments(output, |_| "".to_string());
+    unparse_with_position_map(&with_placeholders)
+        .or_else(|| unparse_with_position_map(&without_comments))
+        .unwrap_or_else(|| (output.to_string(), vec![]))
+}
+
+fn replace_comments(output: &str, replace: impl Fn(&str) -> String) -> String {
+    output
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim();
+            if is_single_line_comment(trimmed) {
+                replace(trimmed)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\r\n")
+}
+
+fn unparse_with_position_map(code: &str) -> Option<(String, PositionMap)> {
+    let file = syn::parse_file(code).ok()?;
+    let unparsed = prettyplease::unparse(&file);
+    let position_map =
+        get_position_map(&get_token_positions(&unparsed), &get_token_positions(code));
+    let pretty = unparsed
+        .lines()
+        .map(replace_comment_placeholder)
+        .map(|line| format!("{}\r\n", line))
+        .collect();
+    Some((pretty, position_map))
+}
+
+/// The position in the original output of the nearest token at or before this position in the pretty printed output
+pub fn get_original_position(position_map: &PositionMap, pretty: LineColumn) -> Option<LineColumn> {
+    position_map
+        .iter()
+        .rev()
+        .find(|(pretty_position, _)| *pretty_position <= pretty)
+        .map(|(_, original_position)| *original_position)
+}
+
+/// Flattened list of each token's text and position. Groups are listed as their open and close delimiters, around their contents
+fn get_token_positions(code: &str) -> Vec<(String, LineColumn)> {
+    let mut tokens = vec![];
+    if let Ok(token_stream) = code.parse::<proc_macro2::TokenStream>() {
+        push_token_positions(&mut tokens, token_stream);
+    }
+    tokens
+}
+
+fn push_token_positions(
+    tokens: &mut Vec<(String, LineColumn)>,
+    token_stream: proc_macro2::TokenStream,
+) {
+    for token_tree in token_stream {
+        match token_tree {
+            proc_macro2::TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    proc_macro2::Delimiter::Parenthesis => ("(", ")"),
+                    proc_macro2::Delimiter::Brace => ("{", "}"),
+                    proc_macro2::Delimiter::Bracket => ("[", "]"),
+                    proc_macro2::Delimiter::None => ("", ""),
+                };
+                let start = group.span_open().start();
+                tokens.push((open.to_string(), (start.line, start.column)));
+                push_token_positions(tokens, group.stream());
+                let end = group.span_close().start();
+                tokens.push((close.to_string(), (end.line, end.column)));
+            }
+            other => {
+                let start = other.span().start();
+                tokens.push((other.to_string(), (start.line, start.column)));
+            }
+        }
+    }
+}
+
+/// Matches up the same tokens in both lists. The pretty printer occasionally adds or removes a token, e.g. a trailing comma,
+/// so if they differ, look ahead a little in the original for the pretty token, otherwise skip it
+fn get_position_map(
+    pretty_tokens: &[(String, LineColumn)],
+    original_tokens: &[(String, LineColumn)],
+) -> PositionMap {
+    let lookahead = 16;
+    let mut position_map = vec![];
+    let mut original_index = 0;
+    for (text, pretty_position) in pretty_tokens {
+        let found = original_tokens
+            .iter()
+            .enumerate()
+            .skip(original_index)
+            .take(lookahead)
+            .find(|(_, (original_text, _))| original_text == text);
+        if let Some((index, (_, original_position))) = found {
+            position_map.push((*pretty_position, *original_position));
+            original_index = index + 1;
+        }
+    }
+    position_map
+}
+
+/// Doc comments are already kept by syn as attributes, so only plain "//" comments need placeholders
+fn is_single_line_comment(trimmed_line: &str) -> bool {
+    trimmed_line.starts_with("//")
+        && !trimmed_line.starts_with("///")
+        && !trimmed_line.starts_with("//!")
+}
+
+/// Turns a comment placeholder line back into the original comment, at the pretty printed indent
+fn replace_comment_placeholder(line: &str) -> String {
+    let trimmed = line.trim_start();
+    if let Some(literal) = trimmed
+        .strip_prefix(COMMENT_PLACEHOLDER)
+        .and_then(|rest| rest.strip_prefix('('))
+        .and_then(|rest| rest.strip_suffix(");"))
+    {
+        if let Ok(comment) = syn::parse_str::<syn::LitStr>(literal) {
+            let indent = &line[..line.len() - trimmed.len()];
+            return format!("{}{}", indent, comment.value());
+        }
+    }
+    line.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +258,52 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_pretty_print() {
+        let test_case_passes = vec![
+            (
+                "fn main() {\r\n    let a: i64 =             if true {\r\n1\r\n} else {\r\n2\r\n};\r\n}\r\n",
+                "fn main() {\r\n    let a: i64 = if true { 1 } else { 2 };\r\n}\r\n",
+            ),
+            (
+                "fn main() {\r\n// a \"quoted\" comment\r\n    fn a() -> i64 {\r\n    // nested\r\n123\r\n    }\r\n}\r\n",
+                "fn main() {\r\n    // a \"quoted\" comment\r\n    fn a() -> i64 {\r\n        // nested\r\n        123\r\n    }\r\n}\r\n",
+            ),
+            ("fn main() {\r\n    let a = ;\r\n}\r\n", "fn main() {\r\n    let a = ;\r\n}\r\n"),
+        ];
+        for (input, expected) in test_case_passes {
+            assert_eq!(pretty_print(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_pretty_print_with_position_map() {
+        let output = "fn main() {\r\n// comment\r\n    let a: i64 =\r\n 1;\r\n@\r\n}\r\n";
+        let (_, position_map) = pretty_print_with_position_map(output);
+        assert_eq!(position_map, vec![]);
+
+        let output = "fn main() {\r\n// comment\r\n    let a: i64 =\r\n 1;\r\n}\r\n";
+        let (pretty, position_map) = pretty_print_with_position_map(output);
+        assert_eq!(
+            pretty,
+            "fn main() {\r\n    // comment\r\n    let a: i64 = 1;\r\n}\r\n"
+        );
+        // "1" on line 3 col 17 of the pretty output was originally on line 4 col 1
+        assert_eq!(get_original_position(&position_map, (3, 17)), Some((4, 1)));
+        // ";" after it
+        assert_eq!(get_original_position(&position_map, (3, 18)), Some((4, 2)));
+        // "}" of main
+        assert_eq!(get_original_position(&position_map, (4, 0)), Some((5, 0)));
+
+        // a comment after the return value of a function can't be a placeholder statement, so all comments are removed
+        let output = "fn main() {\r\nfn a() -> i64 {\r\n// first\r\n123\r\n// last\r\n}\r\n}\r\n";
+        let (pretty, position_map) = pretty_print_with_position_map(output);
+        assert_eq!(
+            pretty,
+            "fn main() {\r\n    fn a() -> i64 {\r\n        123\r\n    }\r\n}\r\n"
+        );
+        // "123" on line 3 col 8 of the pretty output was originally on line 4 col 0
+        assert_eq!(get_original_position(&position_map, (3, 8)), Some((4, 0)));
+    }
 }
@@ -0,0 +1,302 @@
+/*! The lexer, which splits each line of chars into Tokens, and finds the TokenKind of each, for the parser and for syntax highlighting
+
+    Tokens are separated by whitespace, except inside a string, and a `//` comment or `##` rust code is the whole line.
+    A string ends at its closing quote, not at an escaped quote like `\"`, and a triple-quoted string `"""..."""` can span lines,
+    so its line breaks are kept inside its Token, and one line of chars can cover several lines of the source.
+    Like lines_of_chars, the start and end of each Token are relative to the line after its leading whitespace.
+
+    With the `--tokens` flag each Token is printed as `[text, row, start, end, kind]`, e.g.
+
+    ```json
+    [[["=",0,0,0,"Symbol"],["a",0,2,2,"Ident"],["List::len",0,4,12,"Path"],["list",0,14,17,"Ident"]]]
+    ```
+*/
+use crate::parse;
+use crate::{CharPosition, Row, Tokens};
+use serde::Serialize;
+
+/// What a Token is, decided by its first characters
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum TokenKind {
+    /// e.g. 123 or -123
+    IntLit,
+    /// e.g. 1.23 or -1.23
+    FloatLit,
+    /// e.g. "string"
+    StringLit,
+    /// the name of a constant, argument, function or type, e.g. a, double, i64, or a key of a struct, e.g. person.age
+    Ident,
+    /// an inbuilt function in a module, which starts with the uppercase name of the module, e.g. List::map
+    Path,
+    /// e.g. = + [ \ @
+    Symbol,
+    /// e.g. // comment
+    Comment,
+    /// e.g. ##let a = 1;
+    RustCode,
+    /// =>, the end of the arguments of a function
+    Arrow,
+}
+
+/// Split a line of chars into Tokens, with the kind of each
+pub fn lex_line(line_of_chars: &[CharPosition], row: Row) -> (Tokens, Vec<TokenKind>) {
+    let line = line_of_chars.iter().map(|p| p.0).collect::<String>();
+    let line = parse::strip_trailing_whitespace(&parse::strip_leading_whitespace(&line));
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens: Tokens = vec![];
+    if line.starts_with("//") || line.starts_with("##") {
+        if !chars.is_empty() {
+            tokens.push((line, row, 0, chars.len() - 1));
+        }
+    } else {
+        let mut index = 0;
+        while index < chars.len() {
+            if chars[index].is_whitespace() {
+                index += 1;
+                continue;
+            }
+            let start = index;
+            while index < chars.len() && !chars[index].is_whitespace() {
+                if chars[index] == '"' {
+                    index = get_end_of_string(&chars, index);
+                } else {
+                    index += 1;
+                }
+            }
+            let text = chars[start..index].iter().collect::<String>();
+            tokens.push((text, row, start, index - 1));
+        }
+    }
+    let kinds = tokens
+        .iter()
+        .map(|token| get_token_kind(&token.0))
+        .collect();
+    (tokens, kinds)
+}
+
+/// The index after the end of the string which starts at a quote, i.e. after its closing quote, or else where it is unterminated.
+/// A backslash escapes the next char, and a string which isn't triple-quoted also ends at a line break
+pub fn get_end_of_string(chars: &[char], start: usize) -> usize {
+    let is_triple_quoted = chars[start..].starts_with(&['"', '"', '"']);
+    let mut index = start + if is_triple_quoted { 3 } else { 1 };
+    while index < chars.len() {
+        match chars[index] {
+            '\\' if !matches!(chars.get(index + 1), None | Some('\r') | Some('\n')) => index += 2,
+            '"' if !is_triple_quoted => return index + 1,
+            '"' if chars[index..].starts_with(&['"', '"', '"']) => return index + 3,
+            '\r' | '\n' if !is_triple_quoted => return index,
+            _ => index += 1,
+        }
+    }
+    chars.len()
+}
+
+/// Whether each char of the source is inside a string, ignoring quotes in `//` comments and `##` rust code,
+/// so that the source isn't split into lines at the line breaks of a multi-line string, or at a "=>" in a string
+pub fn get_chars_inside_strings(chars: &[char]) -> Vec<bool> {
+    let mut inside_strings = vec![false; chars.len()];
+    let mut is_start_of_line = true;
+    let mut is_comment_or_rustcode = false;
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        if c == '\r' || c == '\n' {
+            is_start_of_line = true;
+            is_comment_or_rustcode = false;
+        } else if is_start_of_line && !c.is_whitespace() {
+            is_start_of_line = false;
+            is_comment_or_rustcode =
+                chars[index..].starts_with(&['/', '/']) || chars[index..].starts_with(&['#', '#']);
+        }
+        if c == '"' && !is_comment_or_rustcode {
+            let end = get_end_of_string(chars, index);
+            inside_strings[index..end].fill(true);
+            index = end;
+        } else {
+            index += 1;
+        }
+    }
+    inside_strings
+}
+
+/// The kind of the text of a Token. Invalid tokens are still given the kind they were meant to be, e.g. 1a is an IntLit,
+/// so the parser can explain what is wrong with them
+pub fn get_token_kind(text: &str) -> TokenKind {
+    let mut chars = text.chars();
+    let first_char = chars.next();
+    let second_char = chars.next();
+    match (first_char, second_char) {
+        (Some('/'), Some('/')) => TokenKind::Comment,
+        (Some('#'), Some('#')) => TokenKind::RustCode,
+        (Some('='), Some('>')) => TokenKind::Arrow,
+        (Some('"'), _) => TokenKind::StringLit,
+        (Some(first_char), _) if first_char.is_ascii_digit() => get_number_kind(text),
+        // a single - is subtraction
+        (Some('-'), Some(_)) => get_number_kind(text),
+        (Some(first_char), _) if first_char.is_alphabetic() || first_char == '_' => {
+            if first_char.is_uppercase() && text.contains("::") {
+                TokenKind::Path
+            } else {
+                TokenKind::Ident
+            }
+        }
+        _ => TokenKind::Symbol,
+    }
+}
+
+fn get_number_kind(text: &str) -> TokenKind {
+    if parse::is_float(&text.to_string()) {
+        TokenKind::FloatLit
+    } else {
+        TokenKind::IntLit
+    }
+}
+
+/// The Token with its kind, as printed by `--tokens`
+pub type TokenJson<'a> = (&'a str, Row, usize, usize, TokenKind);
+
+/// Pairs each Token with its kind, for the `--tokens` JSON
+pub fn get_tokens_json<'a>(
+    lines_of_tokens: &'a [Tokens],
+    lines_of_token_kinds: &[Vec<TokenKind>],
+) -> Vec<Vec<TokenJson<'a>>> {
+    lines_of_tokens
+        .iter()
+        .zip(lines_of_token_kinds)
+        .map(|(tokens, kinds)| {
+            tokens
+                .iter()
+                .zip(kinds)
+                .map(|(token, kind)| (token.0.as_str(), token.1, token.2, token.3, *kind))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex(line: &str) -> Vec<(String, usize, usize, TokenKind)> {
+        let chars: Vec<CharPosition> = line.chars().enumerate().map(|(i, c)| (c, i)).collect();
+        let (tokens, kinds) = lex_line(&chars, 0);
+        tokens
+            .into_iter()
+            .zip(kinds)
+            .map(|(token, kind)| (token.0, token.2, token.3, kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_lex_line() {
+        let test_case_passes = vec![
+            (
+                "= len List::len list",
+                vec![
+                    ("=", 0, 0, TokenKind::Symbol),
+                    ("len", 2, 4, TokenKind::Ident),
+                    ("List::len", 6, 14, TokenKind::Path),
+                    ("list", 16, 19, TokenKind::Ident),
+                ],
+            ),
+            (
+                "  @ \"hello world\"  -1.5",
+                vec![
+                    ("@", 0, 0, TokenKind::Symbol),
+                    ("\"hello world\"", 2, 14, TokenKind::StringLit),
+                    ("-1.5", 17, 20, TokenKind::FloatLit),
+                ],
+            ),
+            (
+                "= double \\ i64 i64 x =>",
+                vec![
+                    ("=", 0, 0, TokenKind::Symbol),
+                    ("double", 2, 7, TokenKind::Ident),
+                    ("\\", 9, 9, TokenKind::Symbol),
+                    ("i64", 11, 13, TokenKind::Ident),
+                    ("i64", 15, 17, TokenKind::Ident),
+                    ("x", 19, 19, TokenKind::Ident),
+                    ("=>", 21, 22, TokenKind::Arrow),
+                ],
+            ),
+            (
+                "// a comment",
+                vec![("// a comment", 0, 11, TokenKind::Comment)],
+            ),
+            (
+                "##let a = 1;",
+                vec![("##let a = 1;", 0, 11, TokenKind::RustCode)],
+            ),
+            (
+                "= a.b 1a",
+                vec![
+                    ("=", 0, 0, TokenKind::Symbol),
+                    ("a.b", 2, 4, TokenKind::Ident),
+                    ("1a", 6, 7, TokenKind::IntLit),
+                ],
+            ),
+            (
+                "@ \"say \\\"hi there\\\"\" 1",
+                vec![
+                    ("@", 0, 0, TokenKind::Symbol),
+                    ("\"say \\\"hi there\\\"\"", 2, 19, TokenKind::StringLit),
+                    ("1", 21, 21, TokenKind::IntLit),
+                ],
+            ),
+            (
+                "= a \"\"\"line 1\r\n\"line\" 2\"\"\" 3",
+                vec![
+                    ("=", 0, 0, TokenKind::Symbol),
+                    ("a", 2, 2, TokenKind::Ident),
+                    (
+                        "\"\"\"line 1\r\n\"line\" 2\"\"\"",
+                        4,
+                        25,
+                        TokenKind::StringLit,
+                    ),
+                    ("3", 27, 27, TokenKind::IntLit),
+                ],
+            ),
+            ("", vec![]),
+        ];
+        for (line, expected) in test_case_passes {
+            let expected: Vec<(String, usize, usize, TokenKind)> = expected
+                .into_iter()
+                .map(|(text, start, end, kind)| (text.to_string(), start, end, kind))
+                .collect();
+            assert_eq!(lex(line), expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_get_chars_inside_strings() {
+        let test_cases = [
+            ("@ \"a\" b", "..___.."),
+            ("@ \"a\\\"b\" c", "..______.."),
+            ("@ \"a\r\n@ b", "..__....."),
+            ("@ \"\"\"a\r\nb\"\"\" c", "..__________.."),
+            ("// \"\"\"a\r\n@ \"b\"", "...........___"),
+        ];
+        for (source, expected) in test_cases {
+            let chars: Vec<char> = source.chars().collect();
+            let inside: String = get_chars_inside_strings(&chars)
+                .iter()
+                .map(|&is_inside| if is_inside { '_' } else { '.' })
+                .collect();
+            assert_eq!(inside, expected, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_get_tokens_json() {
+        let chars: Vec<CharPosition> = "= a List::len list"
+            .chars()
+            .enumerate()
+            .map(|(i, c)| (c, i))
+            .collect();
+        let (tokens, kinds) = lex_line(&chars, 0);
+        let lines_of_tokens = vec![tokens];
+        let json = get_tokens_json(&lines_of_tokens, &[kinds]);
+        assert_eq!(serde_json::to_string(&json).unwrap(), "[[[\"=\",0,0,0,\"Symbol\"],[\"a\",0,2,2,\"Ident\"],[\"List::len\",0,4,12,\"Path\"],[\"list\",0,14,17,\"Ident\"]]]");
+    }
+}
@@ -24,16 +24,30 @@
 mod ast;
 pub mod compiler_runner;
 pub mod debug_window_derive;
+pub mod diagnostic;
 mod errors;
+pub mod eval;
+pub mod explain;
 mod file;
 pub mod formatting;
 pub mod integration_tests;
+pub mod lexer;
+pub mod lint;
+pub mod lsp;
 mod parse;
+pub mod repl;
+pub mod rustc_runner;
 pub mod server;
+pub mod source_formatter;
+pub mod vm;
 use ast::elements;
 use ast::output;
+pub use ast::output::Target;
 use ast::Ast;
+pub use diagnostic::Diagnostic;
+use diagnostic::SourceLine;
 use file::File;
+use lexer::TokenKind;
 use serde::Serialize;
 use std::error::Error;
 use std::fmt;
@@ -49,18 +63,12 @@ pub type Token = (String, Row, Start, End);
 pub type Tokens = Vec<Token>;
 type LinesOfTokens = Vec<Tokens>;
 
-type ErrorStack = Vec<(String, Token)>;
+type ErrorStack = Vec<Diagnostic>;
 
 #[derive(Serialize)]
 struct ErrorStackJson {
     errors: ErrorStack,
-}
-
-fn rem_first_and_last(value: &str) -> String {
-    let mut chars = value.chars();
-    chars.next();
-    chars.next_back();
-    chars.as_str().to_string()
+    warnings: ErrorStack,
 }
 
 pub struct DebugErrorStack<'a>(&'a ErrorStack);
@@ -70,19 +78,13 @@ impl<'a> fmt::Debug for DebugErrorStack<'a> {
         let mut debug = "".to_string();
         for el in 0..self.0.len() {
             let spaces = left_pad(self.0.len(), el);
-            debug = format!(
-                "{}\r\n  {}{}: {},",
-                debug,
-                spaces,
-                el,
-                rem_first_and_last(&self.0[el].0)
-            );
+            debug = format!("{}\r\n  {}{}: {},", debug, spaces, el, &self.0[el]);
         }
         write!(f, "Custom Debug of ErrorStack [{}\r\n]", debug)
     }
 }
 
-pub struct DebugLogs<'a>(&'a ErrorStack);
+pub struct DebugLogs<'a>(&'a [(String, Token)]);
 
 impl<'a> fmt::Debug for DebugLogs<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -151,10 +153,15 @@ pub struct Compiler {
     pub outputdir: String,
     pub lines_of_chars: LinesOfChars,
     pub lines_of_tokens: Vec<Tokens>,
+    /// the kind of each Token in lines_of_tokens, from the lexer
+    pub lines_of_token_kinds: Vec<Vec<TokenKind>>,
     pub output: String,
     pub current_line: usize,
     pub current_line_token: usize,
     pub error_stack: ErrorStack,
+    /// found by the lint pass, which don't stop the output being saved
+    pub warnings: ErrorStack,
+    pub lints: lint::LintLevels,
     pub ast: Ast,
 }
 
@@ -165,14 +172,7 @@ impl Compiler {
         debug: bool,
         option_outputdir: Option<String>,
         nosave: bool,
-        tokens: bool,
     ) -> Result<Compiler, String> {
-        if !tokens {
-            println!("\r\nOUTPUT: {:?}", &option_outputdir);
-        }
-        if debug {
-            println!("DEBUG:  true");
-        }
         let debug_step = 0;
         let debug_line = 0 as usize;
 
@@ -184,10 +184,13 @@ impl Compiler {
         let file = File::new(nosave);
         let lines_of_chars = vec![];
         let lines_of_tokens = vec![];
+        let lines_of_token_kinds = vec![];
         let output = "".to_string();
         let current_line = 0;
         let current_line_token = 0;
         let error_stack = vec![];
+        let warnings = vec![];
+        let lints = lint::LintLevels::default();
         let ast = Ast::new(debug);
         //let logs = vec![format!(
         //    "lib::new {:?} {:?} {:?}",
@@ -202,26 +205,32 @@ impl Compiler {
             outputdir,
             lines_of_chars,
             lines_of_tokens,
+            lines_of_token_kinds,
             output,
             current_line,
             current_line_token,
             error_stack,
+            warnings,
+            lints,
             ast,
         })
     }
 
-    /// Begins running the compiler, run_main_tasks, write_file_or_error
+    /// Begins running the compiler, run_main_tasks, write_file_or_error.
+    /// Prints the progress for the CLI, unless the tokens flag is set
     pub fn run(self: &mut Self, tokens: bool, code: bool) -> Result<(), Box<dyn Error>> {
         self.ast.log(format!("lib::run {:?}", ""));
-        match self.file.get(&self.filepath, tokens, code) {
+        if !tokens {
+            println!("INPUT:  {:?}", &self.filepath);
+        }
+        match self.file.get(&self.filepath, code) {
             Ok(_) => {
-                match self.run_main_tasks(tokens) {
-                    Ok(_) => (),
-                    Err(_e) => (),
-                }
+                let _ = self.run_main_tasks();
+                self.validate_output();
+                self.print_result(tokens);
                 self.print_lines_of_tokens(tokens);
                 self.file.writefile_or_error(
-                    &self.ast.output,
+                    &self.ast,
                     &self.outputdir,
                     self.error_stack.len() > 0,
                     tokens,
@@ -234,13 +243,11 @@ impl Compiler {
         }
     }
 
-    /// If tokens cli flag is true - this will print the lines_of_tokens as basic JSON for use with VS Code extension
+    /// If tokens cli flag is true - this will print the lines_of_tokens, with the kind of each, as JSON for use with VS Code extension
     pub fn print_lines_of_tokens(self: &mut Self, tokens: bool) {
         if tokens {
-            let output = format!("{:?}", &self.lines_of_tokens)
-                .replace("(", "[")
-                .replace(")", "]");
-            println!("{}", output);
+            let json = lexer::get_tokens_json(&self.lines_of_tokens, &self.lines_of_token_kinds);
+            println!("{}", serde_json::to_string(&json).unwrap());
         }
     }
 
@@ -253,7 +260,7 @@ impl Compiler {
 
         if self.debug_step == 1 as usize {
             //dbg!("1");
-            let _result = self.file.get(&self.filepath, false, false);
+            let _result = self.file.get(&self.filepath, false);
         }
 
         if self.debug_step == 2 as usize {
@@ -317,57 +324,129 @@ impl Compiler {
         */
     }
 
-    /// The main tasks run by the compiler, set lines_of_chars, lines_of_tokens, run_main_loop
-    pub fn run_main_tasks(self: &mut Self, tokens: bool) -> Result<(), ()> {
+    /// The main tasks run by the compiler, set lines_of_chars, lines_of_tokens, run_main_loop.
+    /// Nothing is printed, any errors are in the error_stack, see print_result
+    pub fn run_main_tasks(self: &mut Self) -> Result<(), ()> {
         self.ast.log(format!("lib::run_main_tasks {:?}", ""));
         self.set_lines_of_chars();
         self.set_lines_of_tokens();
-        self.run_main_loop(tokens)
+        self.run_main_loop()
+    }
+
+    /// Adds an error if the Rust output isn't valid Rust, see File::validate_output.
+    /// Only once it compiled without errors, and other targets aren't checked
+    pub fn validate_output(self: &mut Self) {
+        if self.ast.target == Target::Rust && self.error_stack.is_empty() {
+            if let Err(error) = self.file.validate_output(&self.ast) {
+                self.error_stack.push(error);
+            }
+        }
+    }
+
+    /// Prints the compile errors, or any warnings and that it compiled successfully, for the CLI.
+    /// With the tokens flag, the errors and warnings are printed as JSON instead for the VS Code extension
+    pub fn print_result(&self, tokens: bool) {
+        self.print_diagnostics(tokens);
+        if self.error_stack.is_empty() && !tokens {
+            println!("\r\nToylang compiled successfully:\r\n----------\r\n");
+            if self.debug {
+                println!("{:?}\r\n----------\r\n", self.ast);
+            }
+        }
+    }
+
+    /// Prints the compile errors, or any warnings, to stderr.
+    /// Used instead of print_result where stdout is the output of the program, e.g. `toylang run` or `toylang eval`
+    pub fn print_diagnostics(&self, tokens: bool) {
+        if tokens && !(self.error_stack.is_empty() && self.warnings.is_empty()) {
+            let e = ErrorStackJson {
+                errors: self.error_stack.clone(),
+                warnings: self.warnings.clone(),
+            };
+            let j = serde_json::to_string(&e).unwrap();
+            eprintln!("{}", j);
+            return;
+        }
+        if !self.error_stack.is_empty() {
+            if self.debug {
+                eprintln!("{:?}", &self.ast);
+            }
+            eprintln!("----------\r\n\r\nTOYLANG COMPILE ERROR:");
+            for error in self.get_rendered_errors() {
+                eprintln!("{}", error);
+            }
+            eprintln!("----------\r\n");
+            eprintln!("For more information about an error, try `toylang explain <code>`\r\n");
+        } else if !tokens && !self.warnings.is_empty() {
+            eprintln!("----------\r\n\r\nTOYLANG COMPILE WARNING:");
+            for warning in self.get_rendered_warnings() {
+                eprintln!("{}", warning);
+            }
+            eprintln!("----------\r\n");
+        }
+    }
+
+    /// The errors in the error_stack, rendered as text with the lines they were found in
+    pub fn get_rendered_errors(&self) -> Vec<String> {
+        self.render(&self.error_stack)
+    }
+
+    /// The same as get_rendered_errors, for the warnings
+    pub fn get_rendered_warnings(&self) -> Vec<String> {
+        self.render(&self.warnings)
+    }
+
+    fn render(&self, diagnostics: &[Diagnostic]) -> Vec<String> {
+        let lines = self.get_source_lines();
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.render(&self.file.filename, &lines))
+            .collect()
+    }
+
+    /// The lines_of_chars as text, with the line of the file each one starts on, to show where errors are
+    pub fn get_source_lines(&self) -> Vec<SourceLine> {
+        let mut file_line_of_chars = vec![];
+        let mut file_line = 0;
+        for c in self.file.filecontents.chars() {
+            file_line_of_chars.push(file_line);
+            if c == '\n' {
+                file_line += 1;
+            }
+        }
+        let mut previous_file_line = 0;
+        self.lines_of_chars
+            .iter()
+            .map(|line| {
+                // an empty line has no chars to find it by, and never has errors
+                let file_line = line
+                    .first()
+                    .and_then(|(_, index)| file_line_of_chars.get(*index).copied())
+                    .unwrap_or(previous_file_line);
+                previous_file_line = file_line;
+                SourceLine {
+                    text: line.iter().map(|p| p.0).collect(),
+                    file_line,
+                }
+            })
+            .collect()
     }
 
     /// Calling the main loop where the lines_of_tokens are parsed and compiler errors are generated
-    fn run_main_loop(self: &mut Self, tokens: bool) -> Result<(), ()> {
+    fn run_main_loop(self: &mut Self) -> Result<(), ()> {
         self.ast.log(format!("lib::run_main_loop {:?}", ""));
         // ref: https://doc.rust-lang.org/reference/tokens.html
         // ref: https://elm-lang.org/docs/syntax
 
-        match self.main_loop_over_lines_of_tokens() {
-            Ok(_) => {
-                ////dbg!(&self.ast);
-                if self.error_stack.len() > 0 {
-                    eprintln!("{:?}", &self.ast);
-                    eprintln!("----------\r\n\r\nTOYLANG COMPILE ERROR:");
-                    for error in &self.error_stack {
-                        eprintln!("{}", error.0);
-                    }
-                    eprintln!("----------\r\n");
-                } else {
-                    output::set_output(self);
-                    if !tokens {
-                        println!("\r\nToylang compiled successfully:\r\n----------\r\n");
-                    }
-                    if self.debug {
-                        println!("{:?}\r\n----------\r\n", self.ast);
-                    }
-                }
+        // the errors of every line are in the error_stack
+        if self.main_loop_over_lines_of_tokens().is_ok() && self.error_stack.is_empty() {
+            // denied lints are added to the error_stack
+            lint::check(self);
+            if self.error_stack.is_empty() {
+                // some targets can only output part of the language, and add errors of their own, e.g. wat
+                output::set_output(self);
             }
-            Err(_e) => {
-                if tokens {
-                    let e = ErrorStackJson {
-                        errors: self.error_stack.clone(),
-                    };
-                    let j = serde_json::to_string(&e).unwrap();
-                    eprintln!("{}", j);
-                } else {
-                    eprintln!("{:?}", &self.ast);
-                    eprintln!("----------\r\n\r\nTOYLANG COMPILE ERROR:");
-                    for error in &self.error_stack {
-                        eprintln!("{:?}", error);
-                    }
-                    eprintln!("----------\r\n");
-                }
-            }
-        };
+        }
         Ok(())
     }
 
@@ -380,13 +459,33 @@ impl Compiler {
             let line = self.debug_line;
             self.parse_one_line(line)?;
         } else {
+            // after an error, resynchronise at the next top-level line, so one bad line doesn't hide errors in the rest
+            let mut result = Ok(());
+            let mut recovering = false;
             for line in 0..self.lines_of_tokens.len() {
-                self.parse_one_line(line)?;
+                if recovering && !self.is_top_level_line(line) {
+                    continue;
+                }
+                recovering = false;
+                if self.parse_one_line(line).is_err() {
+                    self.ast.parents = vec![0];
+                    recovering = true;
+                    result = Err(());
+                }
             }
+            return result;
         }
         Ok(())
     }
 
+    /// A line which isn't indented, i.e. not the body of a function, or the rest of a line after "=>"
+    fn is_top_level_line(&self, line: usize) -> bool {
+        match self.lines_of_chars.get(line).and_then(|chars| chars.first()) {
+            Some((c, _)) => !c.is_whitespace(),
+            None => false,
+        }
+    }
+
     /// Parse a single line of tokens
     fn parse_one_line(self: &mut Self, line: usize) -> Result<(), ()> {
         self.ast.log(format!("lib::parse_one_line {:?}", line));
@@ -405,6 +504,7 @@ impl Compiler {
         let mut index_from: usize = 0;
         let mut index_to: usize = 0;
         let char_vec: Vec<char> = self.file.filecontents.chars().collect();
+        let inside_strings = lexer::get_chars_inside_strings(&char_vec);
         while index_to < char_vec.len() {
             let c = char_vec[index_to];
             let d = if index_to + 1 < char_vec.len() {
@@ -430,9 +530,14 @@ impl Compiler {
             let is_a_rustcode_line = this_line_so_far.len() > 1
                 && this_line_so_far[0] == '#'
                 && this_line_so_far[1] == '#';
-            let is_marker_for_singlelinefunction =
-                c == '=' && d == '>' && !is_a_comment_line && !is_a_rustcode_line;
-            if c == '\r' || c == '\n' || eof || is_marker_for_singlelinefunction {
+            let is_marker_for_singlelinefunction = c == '='
+                && d == '>'
+                && !is_a_comment_line
+                && !is_a_rustcode_line
+                && !inside_strings[index_to];
+            // a multi-line string keeps its line breaks, so the whole string is in one line
+            let is_line_break = (c == '\r' || c == '\n') && !inside_strings[index_to];
+            if is_line_break || eof || is_marker_for_singlelinefunction {
                 let start = index_from;
                 let end = index_to
                     + (if is_marker_for_singlelinefunction {
@@ -460,60 +565,83 @@ impl Compiler {
         }
     }
 
-    /// Initially generate lines_of_tokens based on lines_of_chars
+    /// Initially generate lines_of_tokens, and the kind of each Token, based on lines_of_chars
     fn set_lines_of_tokens(self: &mut Self) {
         self.ast.log(format!("lib::set_lines_of_tokens {:?}", ""));
-        for line in 0..self.lines_of_chars.len() {
-            let mut index_from = 0;
-            let mut index_to = 0;
-            let mut count_quotes = 0;
-
-            let char_vec_initial: &Vec<CharPosition> = &self.lines_of_chars[line];
-            let char_as_string = char_vec_initial.iter().map(|p| p.0).collect::<String>();
-            let removed_leading_whitespace = parse::strip_leading_whitespace(&char_as_string);
-            let removed_trailing_whitespace =
-                parse::strip_trailing_whitespace(&removed_leading_whitespace);
-            let char_vec: Vec<char> = removed_trailing_whitespace.chars().collect();
-
-            let mut inside_quotes = false;
-            let mut line_of_tokens: Tokens = vec![];
-            while index_to < char_vec.len() {
-                let c = char_vec[index_to];
-                let eof = index_to == char_vec.len() - 1;
-                if c == '"' {
-                    if inside_quotes {
-                        inside_quotes = false;
-                        count_quotes = count_quotes - 1;
-                    } else {
-                        inside_quotes = true;
-                        count_quotes = count_quotes + 1;
-                    }
-                };
-                let is_comment = char_vec.len() > 1 && char_vec[0] == '/' && char_vec[1] == '/';
-                let is_rustcode = char_vec.len() > 1 && char_vec[0] == '#' && char_vec[1] == '#';
-                if (c.is_whitespace()
-                    && index_to != 0
-                    && !inside_quotes
-                    && !is_comment
-                    && !is_rustcode)
-                    || eof
-                    || count_quotes == 2
-                {
-                    let start = index_from;
-                    let end = index_to + (if eof || count_quotes == 2 { 1 } else { 0 });
-                    let token_chars = char_vec[start..end].iter().collect::<String>();
-                    line_of_tokens.push((token_chars, line, start, end - 1));
-                    index_from = index_to + 1;
-                    inside_quotes = false;
-                    count_quotes = 0;
-                }
-                index_to = index_to + 1;
-            }
+        (self.lines_of_tokens, self.lines_of_token_kinds) = self
+            .lines_of_chars
+            .iter()
+            .enumerate()
+            .map(|(row, line_of_chars)| lexer::lex_line(line_of_chars, row))
+            .unzip();
+    }
+}
 
-            self.lines_of_tokens.push(line_of_tokens);
-        }
-        //dbg!(&self.lines_of_tokens);
+/// Options for [compile_str]
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    pub target: Target,
+    /// shown in the location of errors, e.g. "test.toy"
+    pub filename: String,
+    /// whether each lint is allowed, a warning, or an error
+    pub lints: lint::LintLevels,
+}
+
+/// The result of [compile_str]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompileOutput {
+    /// the code in the language of the target, pretty printed if it is Rust
+    pub code: String,
+    /// the JSON source map, as saved in output.rs.map, which is only for Rust
+    pub source_map: Option<String>,
+    /// found by the lint pass, which don't stop the output
+    pub warnings: Vec<Diagnostic>,
+}
+
+/// Runs the main tasks on toylang source in memory, with the options, e.g. for tests.
+/// Any errors are in the Compiler's error_stack, and the output is not validated
+pub(crate) fn compile(source: &str, options: Options) -> Compiler {
+    let mut compiler = Compiler::default();
+    compiler.ast.target = options.target;
+    compiler.file.filename = options.filename;
+    compiler.lints = options.lints;
+    compiler.file.filecontents = source.to_string();
+    let _ = compiler.run_main_tasks();
+    compiler
+}
+
+/// Compiles toylang source in memory, without reading or writing any files, or printing anything.
+/// Returns the output with any warnings, or the compile errors
+///
+/// ```
+/// let output = toylang::compile_str("@ + 1 2", toylang::Options::default()).unwrap();
+/// assert_eq!(output.code, "fn main() {\r\n    println!(\"{}\", 1 + 2);\r\n}\r\n");
+/// ```
+pub fn compile_str(source: &str, options: Options) -> Result<CompileOutput, Vec<Diagnostic>> {
+    let mut compiler = compile(source, options);
+    if !compiler.error_stack.is_empty() {
+        return Err(compiler.error_stack);
+    }
+    if compiler.ast.target != Target::Rust {
+        return Ok(CompileOutput {
+            code: compiler.ast.output,
+            source_map: None,
+            warnings: compiler.warnings,
+        });
     }
+    // the same as the saved output.rs, which must be valid Rust
+    compiler.validate_output();
+    if !compiler.error_stack.is_empty() {
+        return Err(compiler.error_stack);
+    }
+    let (code, position_map) = formatting::pretty_print_with_position_map(&compiler.ast.output);
+    let source_map =
+        output::get_source_map_json(&compiler.ast, &compiler.file.filename, &code, &position_map);
+    Ok(CompileOutput {
+        code,
+        source_map: Some(source_map),
+        warnings: compiler.warnings,
+    })
 }
 
 #[cfg(test)]
@@ -569,4 +697,98 @@ mod tests {
         elements::append::append(&mut ast, el8);
         assert!(true);
     }
+
+    #[test]
+    fn test_error_recovery() {
+        let mut compiler = Compiler::default();
+        compiler.file.filecontents = "= a 1\r\n= a 2\r\n= b \\ i64 i64 x =>\r\n    = y 1a\r\n    + x y\r\n@ b a\r\n=c 3\r\n@ a".to_string();
+        assert!(compiler.run_main_tasks().is_ok());
+        let errors: Vec<(&str, Row)> = compiler
+            .error_stack
+            .iter()
+            .map(|error| (error.code, error.span.line))
+            .collect();
+        assert_eq!(errors, vec![("T0038", 1), ("T0032", 4), ("T0029", 7)]);
+        assert_eq!(compiler.ast.output, "");
+    }
+
+    #[test]
+    fn test_compile_str() {
+        let output = compile_str("= a 123\r\n@ a", Options::default()).unwrap();
+        assert_eq!(
+            output.code,
+            "fn main() {\r\n    let a: i64 = 123;\r\n    println!(\"{}\", a.clone());\r\n}\r\n"
+        );
+        assert!(output.source_map.is_some());
+
+        let options = Options {
+            target: Target::Js,
+            ..Options::default()
+        };
+        let output = compile_str("= a 123\r\n@ a", options).unwrap();
+        assert_eq!(output.code, "function main() {\r\n    const a = 123;\r\n    console.log(a);\r\n}\r\n\r\nmain();\r\n");
+        assert_eq!(output.source_map, None);
+        assert!(output.warnings.is_empty());
+
+        let output = compile_str("= a 123", Options::default()).unwrap();
+        assert_eq!(output.warnings[0].code, "T0056");
+        assert_eq!(output.warnings[0].severity, diagnostic::Severity::Warning);
+    }
+
+    #[test]
+    fn test_compile_str_duplicate_struct() {
+        // reusing an identical struct used to be printed with dbg!, but compile_str must not do any I/O
+        let source = "= firstname \"a\"\r\n= sa { firstname }\r\n= sb { firstname }";
+        let output = compile_str(source, Options::default()).unwrap();
+        assert_eq!(output.code.matches("pub struct").count(), 1);
+        assert!(output
+            .code
+            .contains("let mut sb: Sa = Sa::new(firstname.clone());"));
+    }
+
+    #[test]
+    fn test_compile_str_errors() {
+        let options = Options {
+            filename: "test.toy".to_string(),
+            ..Options::default()
+        };
+        let diagnostics = compile_str("= a 123\r\n= a 456", options).unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "T0038");
+        assert_eq!(diagnostics[0].severity, diagnostic::Severity::Error);
+        assert_eq!(diagnostics[0].labels[0].message, "first defined here");
+        assert_eq!(diagnostics[0].labels[0].span.line, 0);
+        assert_eq!(
+            diagnostics[0].span,
+            diagnostic::Span {
+                line: 1,
+                start: 2,
+                end: 2
+            }
+        );
+
+        // an invalid escape is found at just the escape, in the line of chars of its multi-line string
+        let source = "= a \"\"\"line 1\r\nbad \\q\"\"\"\r\n@ a";
+        let diagnostics = compile_str(source, Options::default()).unwrap_err();
+        assert_eq!(diagnostics[0].code, "T0060");
+        assert_eq!(
+            diagnostics[0].span,
+            diagnostic::Span {
+                line: 0,
+                start: 19,
+                end: 20
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_rendered_errors_after_multi_line_string() {
+        let mut compiler = Compiler::default();
+        compiler.file.filename = "test.toy".to_string();
+        compiler.file.filecontents = "= a \"\"\"line 1\r\nline 2\"\"\"\r\n@ a\r\n@ zz".to_string();
+        let _ = compiler.run_main_tasks();
+        assert_eq!(compiler.error_stack[0].span.line, 2);
+        assert!(compiler.get_rendered_errors()[0]
+            .starts_with("----------\r\nerror[T0039] ./src/test.toy:4:0\r\n@ zz\r\n  ^^ "));
+    }
 }
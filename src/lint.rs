@@ -0,0 +1,348 @@
+/*! Warnings, found by a lint pass over the finished AST, once every line has parsed without errors
+
+    Unlike compile errors, warnings don't stop the output being saved. Each lint can be set to `allow`, `warn` (the default) or `deny`
+    from the CLI, where denied lints are reported as errors instead. `warnings` sets every lint at once, and `--deny` wins over `--warn` and `--allow`
+
+    ```bash
+    toylang -i test.toy --allow unused_constants --deny unreachable_code
+    toylang -i test.toy --deny warnings
+    ```
+
+    - `unused_constants` - a constant which is never used
+    - `unused_args` - an argument which is never used in the body of its function
+    - `unused_functions` - a function which is never called, or passed to another function
+    - `unreachable_code` - lines after the return expression of a function, which are indented like its body
+
+    A name starting with an underscore, e.g. `= _a 123`, is never reported as unused
+*/
+use crate::ast::elements::{self, ElIndex, ElementInfo};
+use crate::ast::parents;
+use crate::diagnostic::{Diagnostic, Span};
+use crate::errors::{ErrorMessage, ERRORS};
+use crate::Compiler;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Level {
+    Allow,
+    #[default]
+    Warn,
+    Deny,
+}
+
+/// The Level of each lint, i.e. whether it is ignored, a warning, or an error
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LintLevels {
+    pub unused_constants: Level,
+    pub unused_args: Level,
+    pub unused_functions: Level,
+    pub unreachable_code: Level,
+}
+
+impl LintLevels {
+    /// Set the Level of a lint by its name, or of every lint with "warnings"
+    pub fn set(&mut self, name: &str, level: Level) -> Result<(), String> {
+        match name {
+            "unused_constants" => self.unused_constants = level,
+            "unused_args" => self.unused_args = level,
+            "unused_functions" => self.unused_functions = level,
+            "unreachable_code" => self.unreachable_code = level,
+            "warnings" => {
+                *self = LintLevels {
+                    unused_constants: level,
+                    unused_args: level,
+                    unused_functions: level,
+                    unreachable_code: level,
+                }
+            }
+            _ => {
+                let expected =
+                    "unused_constants, unused_args, unused_functions, unreachable_code, warnings";
+                return Err(format!(
+                    "Unknown lint '{}', expected one of: {}",
+                    name, expected
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// From the lint names passed to --allow, --warn and --deny on the CLI, in that order, so deny wins
+    pub fn from_cli(
+        allow: &[String],
+        warn: &[String],
+        deny: &[String],
+    ) -> Result<LintLevels, String> {
+        let mut lints = LintLevels::default();
+        for (names, level) in [
+            (allow, Level::Allow),
+            (warn, Level::Warn),
+            (deny, Level::Deny),
+        ] {
+            for name in names {
+                lints.set(name, level)?;
+            }
+        }
+        Ok(lints)
+    }
+}
+
+/// Run every lint which isn't allowed. Warnings are added to the compiler's warnings, and denied lints to its error_stack
+pub fn check(compiler: &mut Compiler) {
+    compiler.ast.log(format!("lint::check {:?}", ""));
+    let used = get_used_definitions(compiler);
+    let mut found: Vec<(ErrorMessage, &str, Level, ElIndex)> = vec![];
+    for el_index in 0..compiler.ast.elements.len() {
+        // internal elements, e.g. inbuilt functions, have no Token
+        if compiler.ast.element_tokens[el_index].0.is_empty() {
+            continue;
+        }
+        let lints = &compiler.lints;
+        match &compiler.ast.elements[el_index].0 {
+            ElementInfo::Constant(name, _)
+                if !used.contains(&el_index)
+                    && !name.starts_with('_')
+                    && is_constant_definition(compiler, el_index) =>
+            {
+                found.push((
+                    ERRORS.unused_constant,
+                    "unused_constants",
+                    lints.unused_constants,
+                    el_index,
+                ))
+            }
+            ElementInfo::Arg(name, _, _, _)
+                if !used.contains(&el_index) && !name.starts_with('_') =>
+            {
+                found.push((
+                    ERRORS.unused_arg,
+                    "unused_args",
+                    lints.unused_args,
+                    el_index,
+                ))
+            }
+            ElementInfo::FunctionDef(name, _, _, _) => {
+                if !used.contains(&el_index) && !name.starts_with('_') {
+                    found.push((
+                        ERRORS.unused_function,
+                        "unused_functions",
+                        lints.unused_functions,
+                        el_index,
+                    ))
+                }
+                if let Some(unreachable) = get_first_unreachable_element(compiler, el_index) {
+                    found.push((
+                        ERRORS.unreachable_code,
+                        "unreachable_code",
+                        lints.unreachable_code,
+                        unreachable,
+                    ))
+                }
+            }
+            _ => (),
+        }
+    }
+    for (error, name, level, el_index) in found {
+        let span = Span::from(&compiler.ast.element_tokens[el_index]);
+        // a function passed as an argument is copied, with the same Token, so only report it once
+        let is_duplicate = compiler
+            .warnings
+            .iter()
+            .chain(compiler.error_stack.iter())
+            .any(|diagnostic| diagnostic.code == error.code && diagnostic.span == span);
+        match level {
+            Level::Allow => (),
+            _ if is_duplicate => (),
+            Level::Warn => compiler.warnings.push(
+                Diagnostic::warning(error, span)
+                    .with_note(&format!("the `{}` lint is set to warn", name)),
+            ),
+            Level::Deny => compiler.error_stack.push(
+                Diagnostic::error(error, span)
+                    .with_note(&format!("the `{}` lint is set to deny", name)),
+            ),
+        }
+    }
+}
+
+/// The indexes of every Constant, Arg and FunctionDef which is referred to by another Element
+fn get_used_definitions(compiler: &Compiler) -> Vec<ElIndex> {
+    let ast = &compiler.ast;
+    let mut used = vec![];
+    for el_index in 0..ast.elements.len() {
+        let name = match &ast.elements[el_index].0 {
+            ElementInfo::ConstantRef(_, _, refname) => refname,
+            ElementInfo::FunctionCall(name, _, _) => name,
+            ElementInfo::StructEdit(name, _) => name,
+            _ => continue,
+        };
+        // e.g. the struct of a StructEdit, s.age
+        let name = name.split('.').next().unwrap_or_default().to_string();
+        if let Some(definition) = elements::get_definition_index_by_name(ast, &name, el_index) {
+            used.push(definition);
+        }
+    }
+    for (el_index, (elinfo, _)) in ast.elements.iter().enumerate() {
+        let is_used_by_rust_code = match elinfo {
+            ElementInfo::Constant(name, _) | ElementInfo::FunctionDef(name, _, _, _) => {
+                ast.elements.iter().any(|(other, _)| match other {
+                    ElementInfo::Rust(code, _) => contains_identifier(code, name),
+                    _ => false,
+                })
+            }
+            _ => false,
+        };
+        // a function passed as an argument is used by its copy, e.g. mapfn_for_list_map, which has the same Token
+        let token = &ast.element_tokens[el_index];
+        let is_used_by_copy = !token.0.is_empty()
+            && used
+                .iter()
+                .any(|&definition| ast.element_tokens[definition] == *token);
+        if is_used_by_rust_code || is_used_by_copy {
+            used.push(el_index);
+        }
+    }
+    used
+}
+
+/// A Constant which is assigned, rather than the key of a struct, or the variable of a loop
+fn is_constant_definition(compiler: &Compiler, el_index: ElIndex) -> bool {
+    let ast = &compiler.ast;
+    let parent = parents::get_current_parent_ref_from_element_children_search(ast, el_index);
+    let grandparent = parent.and_then(|parent| {
+        parents::get_current_parent_ref_from_element_children_search(ast, parent)
+    });
+    let is_assigned = matches!(
+        parent.map(|p| &ast.elements[p].0),
+        Some(ElementInfo::Assignment)
+    );
+    let is_struct_key = matches!(
+        grandparent.map(|g| &ast.elements[g].0),
+        Some(ElementInfo::Struct(_, _, _))
+    );
+    is_assigned && !is_struct_key
+}
+
+/// A function ends at its return expression, so any following lines which are indented more than its definition
+/// are parsed as siblings of the function instead, and can never run as part of it
+fn get_first_unreachable_element(compiler: &Compiler, el_index: ElIndex) -> Option<ElIndex> {
+    let ast = &compiler.ast;
+    let parent = parents::get_current_parent_ref_from_element_children_search(ast, el_index)?;
+    let indent = get_indent_of_line(compiler, ast.element_tokens[el_index].1);
+    let siblings = &ast.elements[parent].1;
+    let position = siblings.iter().position(|&sibling| sibling == el_index)?;
+    for &sibling in &siblings[position + 1..] {
+        let line = ast.element_tokens[sibling].1;
+        if line <= ast.element_tokens[el_index].1 {
+            continue;
+        }
+        if get_indent_of_line(compiler, line) <= indent {
+            return None;
+        }
+        match ast.elements[sibling].0 {
+            ElementInfo::Indent
+            | ElementInfo::Seol
+            | ElementInfo::Eol
+            | ElementInfo::Unused
+            | ElementInfo::CommentSingleLine(_) => (),
+            _ => return Some(sibling),
+        }
+    }
+    None
+}
+
+/// Whether the name is a whole identifier in the Rust code, e.g. `a` is not in `let ab = 1;`
+fn contains_identifier(code: &str, name: &str) -> bool {
+    code.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|identifier| identifier == name)
+}
+
+fn get_indent_of_line(compiler: &Compiler, line: usize) -> usize {
+    compiler
+        .lines_of_chars
+        .get(line)
+        .map(|chars| chars.iter().take_while(|(c, _)| c.is_whitespace()).count())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compile, Options};
+
+    fn get_warnings(source: &str, lints: LintLevels) -> Vec<(&'static str, usize)> {
+        let compiler = compile(
+            source,
+            Options {
+                lints,
+                ..Options::default()
+            },
+        );
+        compiler
+            .warnings
+            .iter()
+            .chain(compiler.error_stack.iter())
+            .map(|diagnostic| (diagnostic.code, diagnostic.span.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_lints() {
+        let test_case_passes = vec![
+            ("= a 1\r\n= b 2\r\n@ a", vec![("T0056", 1)]),
+            ("= _a 1", vec![]),
+            ("= a 1\r\n## println!(\"{}\", a);", vec![]),
+            ("= a 1\r\n## let ab = 1;", vec![("T0056", 0)]),
+            ("= s { = age 21 }\r\n= s.age 99", vec![]),
+            (
+                "= double \\ i64 i64 x => * x 2\r\n= unused \\ i64 i64 y => 3\r\n@ double 2",
+                vec![("T0058", 2), ("T0057", 2)],
+            ),
+            (
+                "= list [ 1 ]\r\n= mapfn \\ i64 i64 i => * i 100\r\n= mapped List::map list ( mapfn )\r\n@ mapped",
+                vec![],
+            ),
+            (
+                "= f \\ i64 i64 x =>\r\n    = y + x 1\r\n    y\r\n    @ y\r\n@ f 1",
+                vec![("T0059", 4)],
+            ),
+            (
+                "= f \\ i64 i64 x =>\r\n    = y + x 1\r\n    @ y\r\n    y\r\n@ f 1",
+                vec![],
+            ),
+            // an unused function can also have unreachable code
+            (
+                "= f \\ i64 i64 x =>\r\n    = y + x 1\r\n    y\r\n    @ y",
+                vec![("T0058", 0), ("T0059", 4)],
+            ),
+        ];
+        for (source, expected) in test_case_passes {
+            assert_eq!(
+                get_warnings(source, LintLevels::default()),
+                expected,
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_lint_levels() {
+        let source = "= a 1\r\n= f \\ i64 i64 x => 1\r\n@ f 2";
+        let allowed = LintLevels::from_cli(&["warnings".to_string()], &[], &[]).unwrap();
+        assert_eq!(get_warnings(source, allowed), vec![]);
+
+        let denied = LintLevels::from_cli(&[], &[], &["unused_args".to_string()]).unwrap();
+        let compiler = compile(
+            source,
+            Options {
+                lints: denied,
+                ..Options::default()
+            },
+        );
+        assert_eq!(compiler.warnings[0].code, "T0056");
+        assert_eq!(compiler.error_stack[0].code, "T0057");
+        assert_eq!(compiler.ast.output, "");
+
+        assert!(LintLevels::from_cli(&["unused".to_string()], &[], &[]).is_err());
+    }
+}
@@ -0,0 +1,363 @@
+/*! Language Server Protocol mode, for use with any LSP capable editor
+
+    Start it with the lsp flag (-l or --lsp). It then speaks LSP (JSON-RPC) over stdin/stdout, so there is no port to configure.
+
+    ```bash
+    toylang --lsp
+    ```
+
+    Each time a document is opened or changed the whole document is recompiled in memory (nothing is saved)
+    and the ErrorStack is published back to the editor as diagnostics.
+
+    Hovering shows the infered type of constants, args and functions, and inlay hints show the infered type of each new constant, see [hover]
+
+    Go to definition and find references work for constants, args and functions, see [definition]
+
+    Completion suggests inbuilt functions, and the functions, constants and args in scope, see [completion]
+*/
+pub mod completion;
+pub mod definition;
+pub mod hover;
+
+use crate::ast::output;
+use crate::diagnostic::{Severity, Span};
+use crate::{Compiler, Options, Token};
+use lsp_server::{Connection, ExtractError, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as NotificationTrait, PublishDiagnostics,
+};
+use lsp_types::request::{
+    Completion, GotoDefinition, HoverRequest, InlayHintRequest, References, Request as RequestTrait,
+};
+use lsp_types::{
+    CompletionOptions, CompletionResponse, Diagnostic, DiagnosticSeverity, GotoDefinitionResponse,
+    HoverProviderCapability, InitializeParams, Location, NumberOrString, OneOf, Position,
+    PublishDiagnosticsParams, Range, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use std::collections::HashMap;
+use std::error::Error;
+
+type LspResult<T> = Result<T, Box<dyn Error + Sync + Send>>;
+
+/// Open documents, keyed by uri, with their latest full text
+type Documents = HashMap<Url, String>;
+
+/// Only public function for lsp - runs the language server until the client asks it to exit
+pub fn main() {
+    if let Err(e) = run() {
+        eprintln!("Application error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Initialise the connection over stdio, then handle messages until shutdown
+fn run() -> LspResult<()> {
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = serde_json::to_value(&ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        inlay_hint_provider: Some(OneOf::Left(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions::default()),
+        ..ServerCapabilities::default()
+    })?;
+    let initialization_params = connection.initialize(capabilities)?;
+    let _params: InitializeParams = serde_json::from_value(initialization_params)?;
+    // main_loop takes the connection so it is dropped before joining, otherwise the io threads never finish
+    main_loop(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Dispatch each incoming message to its handler
+fn main_loop(connection: Connection) -> LspResult<()> {
+    let mut documents: Documents = HashMap::new();
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                handle_request(&connection, &documents, request)?;
+            }
+            Message::Notification(notification) => {
+                handle_notification(&connection, &mut documents, notification)?;
+            }
+            Message::Response(_) => (),
+        }
+    }
+    Ok(())
+}
+
+/// Reply to each request by compiling the latest text of its document, or with a MethodNotFound error if unsupported
+fn handle_request(
+    connection: &Connection,
+    documents: &Documents,
+    request: Request,
+) -> LspResult<()> {
+    let response = match request.method.as_str() {
+        HoverRequest::METHOD => {
+            let (id, params) = extract_request::<HoverRequest>(request)?;
+            let uri = params.text_document_position_params.text_document.uri;
+            let position = params.text_document_position_params.position;
+            let hover = get_compiled_document(documents, &uri)
+                .and_then(|compiler| hover::get_hover(&compiler, position));
+            Response::new_ok(id, hover)
+        }
+        InlayHintRequest::METHOD => {
+            let (id, params) = extract_request::<InlayHintRequest>(request)?;
+            let hints = get_compiled_document(documents, &params.text_document.uri)
+                .map(|compiler| hover::get_inlay_hints(&compiler));
+            Response::new_ok(id, hints)
+        }
+        Completion::METHOD => {
+            let (id, params) = extract_request::<Completion>(request)?;
+            let uri = params.text_document_position.text_document.uri;
+            let position = params.text_document_position.position;
+            let completions = get_compiled_document(documents, &uri).map(|compiler| {
+                CompletionResponse::Array(completion::get_completions(&compiler, position))
+            });
+            Response::new_ok(id, completions)
+        }
+        GotoDefinition::METHOD => {
+            let (id, params) = extract_request::<GotoDefinition>(request)?;
+            let uri = params.text_document_position_params.text_document.uri;
+            let position = params.text_document_position_params.position;
+            let definition = get_compiled_document(documents, &uri)
+                .and_then(|compiler| definition::get_definition(&compiler, position))
+                .map(|range| GotoDefinitionResponse::Scalar(Location::new(uri, range)));
+            Response::new_ok(id, definition)
+        }
+        References::METHOD => {
+            let (id, params) = extract_request::<References>(request)?;
+            let uri = params.text_document_position.text_document.uri;
+            let position = params.text_document_position.position;
+            let include_declaration = params.context.include_declaration;
+            let locations: Option<Vec<Location>> =
+                get_compiled_document(documents, &uri).map(|compiler| {
+                    definition::get_references(&compiler, position, include_declaration)
+                        .into_iter()
+                        .map(|range| Location::new(uri.clone(), range))
+                        .collect()
+                });
+            Response::new_ok(id, locations)
+        }
+        _ => Response::new_err(
+            request.id,
+            lsp_server::ErrorCode::MethodNotFound as i32,
+            format!("toylang lsp - unsupported request: {}", request.method),
+        ),
+    };
+    connection.sender.send(Message::Response(response))?;
+    Ok(())
+}
+
+fn extract_request<R>(request: Request) -> LspResult<(lsp_server::RequestId, R::Params)>
+where
+    R: RequestTrait,
+    R::Params: serde::de::DeserializeOwned,
+{
+    request
+        .extract(R::METHOD)
+        .map_err(|e: ExtractError<Request>| format!("{:?}", e).into())
+}
+
+/// Keep documents up to date and republish their diagnostics after every change
+fn handle_notification(
+    connection: &Connection,
+    documents: &mut Documents,
+    notification: Notification,
+) -> LspResult<()> {
+    match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params = extract_notification::<DidOpenTextDocument>(notification)?;
+            let uri = params.text_document.uri;
+            documents.insert(uri.clone(), params.text_document.text);
+            publish_diagnostics(connection, documents, uri)?;
+        }
+        DidChangeTextDocument::METHOD => {
+            let params = extract_notification::<DidChangeTextDocument>(notification)?;
+            let uri = params.text_document.uri;
+            // Only FULL sync is advertised, so the last change is always the whole document
+            if let Some(change) = params.content_changes.into_iter().last() {
+                documents.insert(uri.clone(), change.text);
+            }
+            publish_diagnostics(connection, documents, uri)?;
+        }
+        DidCloseTextDocument::METHOD => {
+            let params = extract_notification::<DidCloseTextDocument>(notification)?;
+            let uri = params.text_document.uri;
+            documents.remove(&uri);
+            send_diagnostics(connection, uri, vec![])?;
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+fn extract_notification<N>(notification: Notification) -> LspResult<N::Params>
+where
+    N: NotificationTrait,
+    N::Params: serde::de::DeserializeOwned,
+{
+    notification
+        .extract(N::METHOD)
+        .map_err(|e: ExtractError<Notification>| format!("{:?}", e).into())
+}
+
+fn get_compiled_document(documents: &Documents, uri: &Url) -> Option<Compiler> {
+    documents.get(uri).map(|text| compile(text))
+}
+
+/// Compile the latest text of a document and send its diagnostics
+fn publish_diagnostics(connection: &Connection, documents: &Documents, uri: Url) -> LspResult<()> {
+    let diagnostics = match documents.get(&uri) {
+        Some(text) => get_diagnostics(&compile(text)),
+        None => vec![],
+    };
+    send_diagnostics(connection, uri, diagnostics)
+}
+
+fn send_diagnostics(
+    connection: &Connection,
+    uri: Url,
+    diagnostics: Vec<Diagnostic>,
+) -> LspResult<()> {
+    let params = PublishDiagnosticsParams {
+        uri,
+        diagnostics,
+        version: None,
+    };
+    let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+    connection
+        .sender
+        .send(Message::Notification(notification))?;
+    Ok(())
+}
+
+/// Compile the text in memory only. Nothing is printed, which keeps stdout clear, since that is the LSP channel
+pub fn compile(text: &str) -> Compiler {
+    let mut compiler = crate::compile(text, Options::default());
+    if !compiler.error_stack.is_empty() {
+        // set_output is skipped when there are errors, but the types of the valid parts are still useful
+        output::replace_any_unknown_types(&mut compiler.ast);
+    }
+    compiler
+}
+
+/// Convert the compiler's ErrorStack and warnings into LSP diagnostics, with any notes and suggestions after the message
+pub fn get_diagnostics(compiler: &Compiler) -> Vec<Diagnostic> {
+    compiler
+        .error_stack
+        .iter()
+        .chain(compiler.warnings.iter())
+        .map(|error| Diagnostic {
+            range: get_range_of_span(compiler, &error.span),
+            severity: Some(match error.severity {
+                Severity::Error => DiagnosticSeverity::ERROR,
+                Severity::Warning => DiagnosticSeverity::WARNING,
+            }),
+            code: Some(NumberOrString::String(error.code.to_string())),
+            source: Some("toylang".to_string()),
+            message: [vec![error.message.clone()], error.notes.clone()]
+                .concat()
+                .into_iter()
+                .chain(error.get_help())
+                .collect::<Vec<String>>()
+                .join("\r\n"),
+            ..Diagnostic::default()
+        })
+        .collect()
+}
+
+/// Token positions are relative to the line of tokens, after leading whitespace has been stripped,
+/// and lines of tokens are also split after "=>", so use the lines of chars to find each Token's real position in the document
+pub fn get_range_of_token(compiler: &Compiler, token: &Token) -> Range {
+    get_range_of_span(compiler, &Span::from(token))
+}
+
+/// The same as get_range_of_token, for a Diagnostic
+pub fn get_range_of_span(compiler: &Compiler, span: &Span) -> Range {
+    match compiler.lines_of_chars.get(span.line) {
+        Some(line) => match line.iter().find(|(c, _)| !c.is_whitespace()) {
+            Some((_, first_non_whitespace)) => {
+                let start_index = first_non_whitespace + span.start;
+                let end_index = first_non_whitespace + span.end + 1;
+                Range::new(
+                    get_position_of_char_index(&compiler.file.filecontents, start_index),
+                    get_position_of_char_index(&compiler.file.filecontents, end_index),
+                )
+            }
+            None => Range::default(),
+        },
+        None => Range::default(),
+    }
+}
+
+/// Find the Token under the cursor, including when the cursor is just after its last char
+pub fn get_token_at_position(compiler: &Compiler, position: Position) -> Option<Token> {
+    compiler
+        .lines_of_tokens
+        .iter()
+        .flatten()
+        .find(|token| {
+            let range = get_range_of_token(compiler, token);
+            range.start <= position && position <= range.end
+        })
+        .cloned()
+}
+
+/// Convert a char index of the whole document into an LSP line and character
+pub fn get_position_of_char_index(text: &str, char_index: usize) -> Position {
+    let mut line = 0;
+    let mut character = 0;
+    for c in text.chars().take(char_index) {
+        if c == '\n' {
+            line += 1;
+            character = 0;
+        } else if c != '\r' {
+            character += c.len_utf16() as u32;
+        }
+    }
+    Position::new(line, character)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_diagnostics() {
+        let compiler = compile("= a 123\r\n  monkey");
+        let diagnostics = get_diagnostics(&compiler);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].range,
+            Range::new(Position::new(1, 2), Position::new(1, 8))
+        );
+        assert!(diagnostics[0]
+            .message
+            .starts_with("Invalid Constant Definition"));
+        assert!(get_diagnostics(&compile("= a 123\r\n@ a")).is_empty());
+        let warnings = get_diagnostics(&compile("= a 123"));
+        assert_eq!(warnings[0].severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(
+            warnings[0].code,
+            Some(NumberOrString::String("T0056".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_get_range_of_token_after_single_line_function_split() {
+        let compiler = compile("= a \\ i64 => 123\r\n= b a");
+        let token = compiler.lines_of_tokens[1][0].clone();
+        assert_eq!(token.0, "123");
+        assert_eq!(
+            get_range_of_token(&compiler, &token),
+            Range::new(Position::new(0, 13), Position::new(0, 16))
+        );
+    }
+}
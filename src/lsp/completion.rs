@@ -0,0 +1,208 @@
+/*! Completion items for inbuilt functions, and the functions, constants and args in scope at the cursor
+ */
+use crate::ast::elements::{append, ElIndex, ElementInfo};
+use crate::ast::parents;
+use crate::lsp;
+use crate::Compiler;
+use lsp_types::{CompletionItem, CompletionItemKind, Position, Range};
+
+/// All the names which could be used at this position, with their arg types and return type as detail
+pub fn get_completions(compiler: &Compiler, position: Position) -> Vec<CompletionItem> {
+    let enclosing_functions = get_enclosing_function_refs(compiler, position);
+    let mut items: Vec<CompletionItem> = vec![];
+    for el_index in 0..compiler.ast.elements.len() {
+        let item = match &compiler.ast.elements[el_index].0 {
+            ElementInfo::InbuiltFunctionDef(name, _, argtypes, _, returntype, _) => Some(get_item(
+                name,
+                CompletionItemKind::FUNCTION,
+                get_fn_detail(argtypes, returntype),
+            )),
+            ElementInfo::FunctionDef(name, _, argtypes, returntype) => {
+                // skip duplicates of functions made for List::map etc. since they can't be typed by name
+                let token = &compiler.ast.element_tokens[el_index];
+                if &token.0 == name && is_defined_before(compiler, el_index, position) {
+                    let detail = get_fn_detail(argtypes, returntype);
+                    Some(get_item(name, CompletionItemKind::FUNCTION, detail))
+                } else {
+                    None
+                }
+            }
+            ElementInfo::Constant(name, returntype) => {
+                let scope = get_scope_of_element(compiler, el_index);
+                if (scope == 0 || enclosing_functions.contains(&scope))
+                    && is_defined_before(compiler, el_index, position)
+                {
+                    let detail = returntype.clone();
+                    Some(get_item(name, CompletionItemKind::CONSTANT, detail))
+                } else {
+                    None
+                }
+            }
+            ElementInfo::Arg(name, scope, _, returntype) => {
+                if enclosing_functions.contains(scope) {
+                    let detail = returntype.clone();
+                    Some(get_item(name, CompletionItemKind::VARIABLE, detail))
+                } else {
+                    None
+                }
+            }
+            // explicitly listing other types rather than using _ to not overlook new types in future
+            ElementInfo::List(_) => None,
+            ElementInfo::CommentSingleLine(_) => None,
+            ElementInfo::Int(_) => None,
+            ElementInfo::Float(_) => None,
+            ElementInfo::String(_) => None,
+            ElementInfo::Bool(_) => None,
+            ElementInfo::Type(_) => None,
+            ElementInfo::Eol => None,
+            ElementInfo::Seol => None,
+            ElementInfo::Indent => None,
+            ElementInfo::Unused => None,
+            ElementInfo::ConstantRef(_, _, _) => None,
+            ElementInfo::Rust(_, _) => None,
+            ElementInfo::Struct(_, _, _) => None,
+            ElementInfo::StructEdit(_, _) => None,
+            ElementInfo::Assignment => None,
+            ElementInfo::InbuiltFunctionCall(_, _, _) => None,
+            ElementInfo::FunctionDefWIP => None,
+            ElementInfo::FunctionCall(_, _, _) => None,
+            ElementInfo::Parens => None,
+            ElementInfo::LoopForRangeWIP => None,
+            ElementInfo::LoopForRange(_, _, _) => None,
+            ElementInfo::Println => None,
+            ElementInfo::If(_) => None,
+            ElementInfo::Root => None,
+        };
+        if let Some(item) = item {
+            if !items.iter().any(|existing| existing.label == item.label) {
+                items.push(item);
+            }
+        }
+    }
+    items
+}
+
+fn get_item(name: &str, kind: CompletionItemKind, detail: String) -> CompletionItem {
+    CompletionItem {
+        label: name.to_string(),
+        kind: Some(kind),
+        detail: Some(detail),
+        ..CompletionItem::default()
+    }
+}
+
+/// e.g. "(i64, i64) -> i64"
+fn get_fn_detail(argtypes: &[String], returntype: &String) -> String {
+    format!("({}) -> {}", argtypes.join(", "), returntype)
+}
+
+/// Constants and functions can only be used after they are defined
+fn is_defined_before(compiler: &Compiler, el_index: ElIndex, position: Position) -> bool {
+    let token = &compiler.ast.element_tokens[el_index];
+    lsp::get_range_of_token(compiler, token).end <= position
+}
+
+/// The nearest FunctionDef containing this element, or 0 for Root
+fn get_scope_of_element(compiler: &Compiler, el_index: ElIndex) -> ElIndex {
+    let mut child_ref = el_index;
+    while let Some(parent_ref) =
+        parents::get_current_parent_ref_from_element_children_search(&compiler.ast, child_ref)
+    {
+        if let ElementInfo::FunctionDef(_, _, _, _) = compiler.ast.elements[parent_ref].0 {
+            return parent_ref;
+        }
+        child_ref = parent_ref;
+    }
+    0
+}
+
+/// Functions whose body contains the position. A function which is still open at the end of parsing,
+/// e.g. because its return expression hasn't been typed yet, contains everything after its start
+fn get_enclosing_function_refs(compiler: &Compiler, position: Position) -> Vec<ElIndex> {
+    let mut enclosing = vec![];
+    for el_index in 0..compiler.ast.elements.len() {
+        if let ElementInfo::FunctionDef(_, _, _, _) = compiler.ast.elements[el_index].0 {
+            let range = get_range_of_function(compiler, el_index);
+            let is_open = is_unfinished_function(compiler, el_index);
+            if range.start <= position && (position <= range.end || is_open) {
+                enclosing.push(el_index);
+            }
+        }
+    }
+    enclosing
+}
+
+/// The last line of a function body is its return expression, so if it's something else then more lines are still to come
+fn is_unfinished_function(compiler: &Compiler, el_index: ElIndex) -> bool {
+    let last_child_ref = compiler.ast.elements[el_index]
+        .1
+        .iter()
+        .rev()
+        .find(|&&child_ref| {
+            !matches!(
+                compiler.ast.elements[child_ref].0,
+                ElementInfo::Indent | ElementInfo::Seol | ElementInfo::Eol
+            )
+        });
+    match last_child_ref {
+        Some(&child_ref) => !append::is_return_expression(&compiler.ast.elements[child_ref].0),
+        None => true,
+    }
+}
+
+/// From the function name, to the last token of its body
+fn get_range_of_function(compiler: &Compiler, el_index: ElIndex) -> Range {
+    let mut range = lsp::get_range_of_token(compiler, &compiler.ast.element_tokens[el_index]);
+    let mut stack = compiler.ast.elements[el_index].1.clone();
+    while let Some(child_ref) = stack.pop() {
+        let child_range =
+            lsp::get_range_of_token(compiler, &compiler.ast.element_tokens[child_ref]);
+        if child_range.end > range.end {
+            range.end = child_range.end;
+        }
+        stack.extend(compiler.ast.elements[child_ref].1.clone());
+    }
+    range
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_details(compiler: &Compiler, position: Position) -> Vec<(String, String)> {
+        get_completions(compiler, position)
+            .into_iter()
+            .map(|item| (item.label, item.detail.unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn test_get_completions() {
+        let compiler =
+            lsp::compile("= a 123\r\n= double \\ i64 i64 x => * x 2\r\n= b double a\r\n");
+        let in_fn_body = get_details(&compiler, Position::new(1, 26));
+        let after_fn = get_details(&compiler, Position::new(3, 0));
+        let inbuilt = ("List::mapindex".to_string(), "(Vec<i64>|Vec<f64>|Vec<String>, Vec<i64>|Vec<f64>|Vec<String>) -> Vec<i64>|Vec<f64>|Vec<String>".to_string());
+        let a = ("a".to_string(), "i64".to_string());
+        let x = ("x".to_string(), "i64".to_string());
+        let double = ("double".to_string(), "(i64) -> i64".to_string());
+        let b = ("b".to_string(), "i64".to_string());
+        assert!(in_fn_body.contains(&inbuilt));
+        assert!(in_fn_body.contains(&a));
+        assert!(in_fn_body.contains(&x));
+        assert!(in_fn_body.contains(&double));
+        assert!(!in_fn_body.contains(&b));
+        assert!(after_fn.contains(&a));
+        assert!(!after_fn.contains(&x));
+        assert!(after_fn.contains(&double));
+        assert!(after_fn.contains(&b));
+    }
+
+    #[test]
+    fn test_get_completions_in_unfinished_function() {
+        let compiler = lsp::compile("= double \\ i64 i64 x =>\r\n= y * x 2\r\n");
+        let details = get_details(&compiler, Position::new(2, 0));
+        assert!(details.contains(&("x".to_string(), "i64".to_string())));
+        assert!(details.contains(&("y".to_string(), "i64".to_string())));
+    }
+}
@@ -0,0 +1,158 @@
+/*! Go to definition and find references, for constants, args and functions
+
+    Also available from the CLI, with 1-based line and column numbers, printing each location as `filepath:line:col`
+
+    ```bash
+    toylang definition test.toy 3 5
+    toylang references test.toy 1 3
+    ```
+*/
+use crate::ast::elements::{self, ElIndex};
+use crate::lsp;
+use crate::{Compiler, Token};
+use lsp_types::{Position, Range};
+use std::fs;
+use std::process;
+
+/// The range of the name of the Constant, Arg or FunctionDef which the Token at this position refers to
+pub fn get_definition(compiler: &Compiler, position: Position) -> Option<Range> {
+    let el_index = get_element_index_at_position(compiler, position)?;
+    let definition_index = elements::get_definition_index_of_element(&compiler.ast, el_index)?;
+    get_range_of_element(compiler, definition_index)
+}
+
+/// The ranges of every reference to the same definition as the Token at this position
+pub fn get_references(
+    compiler: &Compiler,
+    position: Position,
+    include_declaration: bool,
+) -> Vec<Range> {
+    let definition_index = match get_element_index_at_position(compiler, position)
+        .and_then(|el_index| elements::get_definition_index_of_element(&compiler.ast, el_index))
+    {
+        Some(definition_index) => definition_index,
+        None => return vec![],
+    };
+    let mut ranges = vec![];
+    for el_index in 0..compiler.ast.elements.len() {
+        let is_declaration = el_index == definition_index;
+        if (include_declaration || !is_declaration)
+            && elements::get_definition_index_of_element(&compiler.ast, el_index)
+                == Some(definition_index)
+        {
+            if let Some(range) = get_range_of_element(compiler, el_index) {
+                if !ranges.contains(&range) {
+                    ranges.push(range);
+                }
+            }
+        }
+    }
+    ranges
+}
+
+fn get_element_index_at_position(compiler: &Compiler, position: Position) -> Option<ElIndex> {
+    let token = lsp::get_token_at_position(compiler, position)?;
+    get_element_index_of_token(compiler, &token)
+}
+
+/// Several elements can share a Token, e.g. an Indent, so only find the one which can refer to a definition
+pub fn get_element_index_of_token(compiler: &Compiler, token: &Token) -> Option<ElIndex> {
+    (0..compiler.ast.element_tokens.len()).find(|&el_index| {
+        compiler.ast.element_tokens[el_index] == *token
+            && elements::get_definition_index_of_element(&compiler.ast, el_index).is_some()
+    })
+}
+
+/// Internal elements have no Token, so have no range
+fn get_range_of_element(compiler: &Compiler, el_index: ElIndex) -> Option<Range> {
+    let token = compiler.ast.element_tokens.get(el_index)?;
+    if token.0.is_empty() {
+        None
+    } else {
+        Some(lsp::get_range_of_token(compiler, token))
+    }
+}
+
+/// CLI - print the definition, or references, of whatever is at the 1-based line and col of the file
+pub fn main(filepath: String, line: u32, col: u32, references: bool) {
+    let text = fs::read_to_string(&filepath).unwrap_or_else(|err| {
+        eprintln!("Problem reading file: {}", err);
+        process::exit(1);
+    });
+    let compiler = lsp::compile(&text);
+    let position = Position::new(line.saturating_sub(1), col.saturating_sub(1));
+    let ranges = if references {
+        get_references(&compiler, position, true)
+    } else {
+        get_definition(&compiler, position).into_iter().collect()
+    };
+    if ranges.is_empty() {
+        eprintln!("Nothing found at {}:{}:{}", filepath, line, col);
+        process::exit(1);
+    }
+    for range in ranges {
+        println!(
+            "{}:{}:{}",
+            filepath,
+            range.start.line + 1,
+            range.start.character + 1
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_definition() {
+        let compiler =
+            lsp::compile("= a 123\r\n= double \\ i64 i64 x => * x 2\r\n= b double a\r\n@ b");
+        let test_case_passes = vec![
+            // b in println -> b constant
+            (
+                Position::new(3, 2),
+                Some(Range::new(Position::new(2, 2), Position::new(2, 3))),
+            ),
+            // double fn call -> fn name
+            (
+                Position::new(2, 5),
+                Some(Range::new(Position::new(1, 2), Position::new(1, 8))),
+            ),
+            // x in fn body -> x arg
+            (
+                Position::new(1, 26),
+                Some(Range::new(Position::new(1, 19), Position::new(1, 20))),
+            ),
+            // a constantref outside fn -> a constant
+            (
+                Position::new(2, 11),
+                Some(Range::new(Position::new(0, 2), Position::new(0, 3))),
+            ),
+            // inbuilt fn has no definition
+            (Position::new(1, 24), None),
+        ];
+        for (position, expected) in test_case_passes {
+            assert_eq!(get_definition(&compiler, position), expected);
+        }
+    }
+
+    #[test]
+    fn test_get_references() {
+        let compiler = lsp::compile("= a 123\r\n= b + a a\r\n@ a");
+        let expected = vec![
+            Range::new(Position::new(0, 2), Position::new(0, 3)),
+            Range::new(Position::new(1, 6), Position::new(1, 7)),
+            Range::new(Position::new(1, 8), Position::new(1, 9)),
+            Range::new(Position::new(2, 2), Position::new(2, 3)),
+        ];
+        assert_eq!(
+            get_references(&compiler, Position::new(2, 2), true),
+            expected
+        );
+        assert_eq!(
+            get_references(&compiler, Position::new(0, 2), false),
+            expected[1..].to_vec()
+        );
+    }
+}
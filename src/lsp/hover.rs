@@ -0,0 +1,175 @@
+/*! Hover text and inlay type hints, using the types infered by output::replace_any_unknown_types
+ */
+use crate::ast::elements::{self, ElementInfo};
+use crate::formatting;
+use crate::lsp;
+use crate::lsp::definition;
+use crate::{Compiler, Token};
+use lsp_types::{
+    Hover, HoverContents, InlayHint, InlayHintKind, InlayHintLabel, MarkedString, Position,
+};
+
+/// Hover text for the Token at this position, e.g. "a: f64" for a constant, or the signature of a function
+pub fn get_hover(compiler: &Compiler, position: Position) -> Option<Hover> {
+    let token = lsp::get_token_at_position(compiler, position)?;
+    let type_info = get_type_info_of_token(compiler, &token)?;
+    Some(Hover {
+        contents: HoverContents::Scalar(MarkedString::LanguageString(lsp_types::LanguageString {
+            language: "rust".to_string(),
+            value: type_info,
+        })),
+        range: Some(lsp::get_range_of_token(compiler, &token)),
+    })
+}
+
+/// Inlay type hints after the name of each new constant, e.g. "= a + 1.1 2.2" shows "a: f64"
+pub fn get_inlay_hints(compiler: &Compiler) -> Vec<InlayHint> {
+    let mut hints = vec![];
+    for line_of_tokens in &compiler.lines_of_tokens {
+        if line_of_tokens.len() > 1 && line_of_tokens[0].0 == "=" {
+            let token = &line_of_tokens[1];
+            if let Some(ElementInfo::Constant(_, returntype)) =
+                definition::get_element_index_of_token(compiler, token)
+                    .map(|el_index| compiler.ast.elements[el_index].0.clone())
+            {
+                if is_known_type(&returntype) {
+                    hints.push(InlayHint {
+                        position: lsp::get_range_of_token(compiler, token).end,
+                        label: InlayHintLabel::String(format!(": {}", returntype)),
+                        kind: Some(InlayHintKind::TYPE),
+                        text_edits: None,
+                        tooltip: None,
+                        padding_left: None,
+                        padding_right: None,
+                        data: None,
+                    });
+                }
+            }
+        }
+    }
+    hints
+}
+
+/// The infered type of whatever the Token refers to, formatted like rust
+pub fn get_type_info_of_token(compiler: &Compiler, token: &Token) -> Option<String> {
+    let el_info = match definition::get_element_index_of_token(compiler, token)
+        .and_then(|el_index| elements::get_definition_index_of_element(&compiler.ast, el_index))
+    {
+        Some(definition_index) => compiler.ast.elements[definition_index].0.clone(),
+        // inbuilt functions, structs and types have no definition in the code, so are found by name
+        None => elements::get_element_by_name(&compiler.ast, &token.0)?.0,
+    };
+    match el_info {
+        ElementInfo::Constant(name, returntype) => Some(format!("{}: {}", name, returntype)),
+        ElementInfo::ConstantRef(name, returntype, _) => Some(format!("{}: {}", name, returntype)),
+        ElementInfo::Arg(name, _, _, returntype) => Some(format!("{}: {}", name, returntype)),
+        ElementInfo::FunctionDef(name, argnames, argtypes, returntype) => {
+            let argmodifiers = vec!["".to_string(); argnames.len()];
+            let args = formatting::get_formatted_argname_argtype_pairs(
+                &argnames,
+                &argtypes,
+                &argmodifiers,
+            );
+            Some(format!("fn {}({}) -> {}", name, args, returntype))
+        }
+        ElementInfo::InbuiltFunctionDef(name, _, argtypes, _, returntype, _) => {
+            // inbuilt argnames are only placeholders, e.g. "arg~1", so just show the types
+            Some(format!(
+                "{}({}) -> {}",
+                name,
+                argtypes.join(", "),
+                returntype
+            ))
+        }
+        ElementInfo::Struct(name, _, _) => Some(format!("struct {}", name)),
+        ElementInfo::Type(name) => Some(name),
+        // explicitly listing other types rather than using _ to not overlook new types in future.
+        // neither the definition nor get_element_by_name will be any of these
+        ElementInfo::List(_) => None,
+        ElementInfo::CommentSingleLine(_) => None,
+        ElementInfo::Int(_) => None,
+        ElementInfo::Float(_) => None,
+        ElementInfo::String(_) => None,
+        ElementInfo::Bool(_) => None,
+        ElementInfo::Eol => None,
+        ElementInfo::Seol => None,
+        ElementInfo::Indent => None,
+        ElementInfo::Unused => None,
+        ElementInfo::Rust(_, _) => None,
+        ElementInfo::StructEdit(_, _) => None,
+        ElementInfo::Assignment => None,
+        ElementInfo::InbuiltFunctionCall(_, _, _) => None,
+        ElementInfo::FunctionDefWIP => None,
+        ElementInfo::FunctionCall(_, _, _) => None,
+        ElementInfo::Parens => None,
+        ElementInfo::LoopForRangeWIP => None,
+        ElementInfo::LoopForRange(_, _, _) => None,
+        ElementInfo::Println => None,
+        ElementInfo::If(_) => None,
+        ElementInfo::Root => None,
+    }
+}
+
+/// Types which still couldn't be infered aren't worth showing
+fn is_known_type(returntype: &str) -> bool {
+    !(returntype.contains("Undefined") || returntype.contains("None") || returntype.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_inlay_hints() {
+        let compiler = lsp::compile("= a + 1.1 2.2\r\n= b 123\r\n= c \"string\"");
+        let labels: Vec<String> = get_inlay_hints(&compiler)
+            .iter()
+            .map(|hint| match &hint.label {
+                InlayHintLabel::String(label) => label.clone(),
+                InlayHintLabel::LabelParts(_) => "".to_string(),
+            })
+            .collect();
+        assert_eq!(labels, vec![": f64", ": i64", ": String"]);
+        assert_eq!(get_inlay_hints(&compiler)[0].position, Position::new(0, 3));
+    }
+
+    #[test]
+    fn test_get_type_info_of_token() {
+        let compiler = lsp::compile(
+            "= a + 1.1 2.2\r\n= double \\ i64 i64 arg1 => * arg1 2\r\n= b double 3\r\n@ a",
+        );
+        let test_case_passes = vec![
+            (Position::new(3, 2), Some("a: f64")),
+            (Position::new(2, 5), Some("fn double(arg1: i64) -> i64")),
+            (Position::new(1, 30), Some("arg1: i64")),
+            (Position::new(0, 4), Some("+(i64|f64, i64|f64) -> i64|f64")),
+            (Position::new(0, 7), None),
+        ];
+        for (position, expected) in test_case_passes {
+            let hover = get_hover(&compiler, position);
+            let value = hover.map(|h| match h.contents {
+                HoverContents::Scalar(MarkedString::LanguageString(s)) => s.value,
+                _ => "".to_string(),
+            });
+            assert_eq!(value.as_deref(), expected);
+        }
+    }
+
+    #[test]
+    fn test_get_type_info_of_token_in_scope() {
+        // both functions have an arg x, so each x is the type of the arg of its own function
+        let compiler = lsp::compile("= f \\ i64 i64 x => + x 1\r\n= g \\ f64 f64 x => * x 2.0");
+        let test_case_passes = vec![
+            (Position::new(0, 21), Some("x: i64")),
+            (Position::new(1, 21), Some("x: f64")),
+        ];
+        for (position, expected) in test_case_passes {
+            let hover = get_hover(&compiler, position);
+            let value = hover.map(|h| match h.contents {
+                HoverContents::Scalar(MarkedString::LanguageString(s)) => s.value,
+                _ => "".to_string(),
+            });
+            assert_eq!(value.as_deref(), expected);
+        }
+    }
+}
@@ -3,17 +3,28 @@
 
 extern crate toylang_macros;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use toylang::compiler_runner;
 use toylang::debug_window_derive;
+use toylang::eval;
+use toylang::explain;
+use toylang::lint::LintLevels;
+use toylang::lsp;
+use toylang::repl;
+use toylang::rustc_runner;
 use toylang::server;
+use toylang::source_formatter;
+use toylang::Target;
 
 #[derive(Parser)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
     /// input filepath relative to the toylang executable
-    #[arg(short, long)]
-    input: String,
+    #[arg(short, long, required_unless_present = "lsp")]
+    input: Option<String>,
 
     /// optional - if true, the input is expected to be the raw toylang code, encoded as base64, instead of a filename. Primarily for VS Code Extension to use
     #[arg(short, long)]
@@ -31,30 +42,119 @@ struct Cli {
     #[arg(short, long)]
     nosave: bool,
 
-    /// optional - lines of tokens flag. If true it will print the "lines of tokens" containing positional info as JSON to stdout. Experimental for use with Toylang VS Code extension. Default is false.
+    /// optional - lines of tokens flag. If true it will print the "lines of tokens" containing positional info and the kind of each token, e.g. Ident, as JSON to stdout. Experimental for use with Toylang VS Code extension. Default is false.
     #[arg(short, long)]
     tokens: bool,
 
     /// optional - turn on server for use with VS Code extension
     #[arg(short, long)]
     server: bool,
+
+    /// optional - run as a Language Server (LSP) over stdio, for use with any LSP capable editor. No input is needed
+    #[arg(short, long)]
+    lsp: bool,
+
+    /// optional - the language of the output: rust, js, py, wat or c. Default is rust
+    #[arg(long, default_value = "rust")]
+    target: Target,
+
+    /// optional - lints to ignore: unused_constants, unused_args, unused_functions, unreachable_code, or warnings for all of them
+    #[arg(short = 'A', long, value_name = "LINT")]
+    allow: Vec<String>,
+
+    /// optional - lints to print as warnings, which don't stop the output being saved. This is the default for every lint
+    #[arg(short = 'W', long, value_name = "LINT")]
+    warn: Vec<String>,
+
+    /// optional - lints to treat as compile errors, which wins over --allow and --warn, e.g. --deny warnings
+    #[arg(short = 'D', long, value_name = "LINT")]
+    deny: Vec<String>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// print the location of the definition of the constant, arg or function at the line and col of the file
+    Definition {
+        /// input filepath
+        input: String,
+        /// line number, starting from 1
+        line: u32,
+        /// column number, starting from 1
+        col: u32,
+    },
+    /// print the locations of all references to the constant, arg or function at the line and col of the file
+    References {
+        /// input filepath
+        input: String,
+        /// line number, starting from 1
+        line: u32,
+        /// column number, starting from 1
+        col: u32,
+    },
+    /// compile the toylang file, build the output with rustc in a temporary directory, then run it
+    Run {
+        /// input filepath
+        input: String,
+    },
+    /// compile the toylang file, then evaluate it directly, without rustc
+    Eval {
+        /// input filepath
+        input: String,
+    },
+    /// start an interactive session, evaluating each line as it is entered
+    Repl,
+    /// format the toylang file in place
+    Fmt {
+        /// input filepath
+        input: String,
+        /// don't write the file, just exit with an error if it isn't already formatted, e.g. for CI
+        #[arg(long)]
+        check: bool,
+    },
+    /// print a long-form explanation of the error with the code, e.g. T0038, with an example and its fix
+    Explain {
+        /// error code
+        code: String,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
-    let input = cli.input;
+    match cli.command {
+        Some(Commands::Definition { input, line, col }) => {
+            return lsp::definition::main(input, line, col, false)
+        }
+        Some(Commands::References { input, line, col }) => {
+            return lsp::definition::main(input, line, col, true)
+        }
+        Some(Commands::Run { input }) => return rustc_runner::main(input),
+        Some(Commands::Eval { input }) => return eval::main(input),
+        Some(Commands::Repl) => return repl::main(),
+        Some(Commands::Fmt { input, check }) => return source_formatter::main(input, check),
+        Some(Commands::Explain { code }) => return explain::main(code),
+        None => (),
+    }
+    let input = cli.input.unwrap_or_default();
     let code = cli.code;
     let debug = cli.debug;
     let output = cli.output;
     let nosave = cli.nosave;
     let tokens = cli.tokens;
     let server = cli.server;
+    let lsp = cli.lsp;
+    let target = cli.target;
+    let lints = LintLevels::from_cli(&cli.allow, &cli.warn, &cli.deny).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    });
 
-    if debug {
+    if lsp {
+        lsp::main();
+    } else if debug {
         debug_window_derive::run(input, debug, output);
     } else if server {
         server::main();
     } else {
-        compiler_runner::main(input, debug, output, nosave, tokens, code);
+        compiler_runner::main(input, debug, output, nosave, tokens, code, target, lints);
     }
 }
@@ -4,8 +4,9 @@ use crate::ast::output;
 use crate::ast::parents;
 use crate::elements;
 use crate::elements::{Element, ElementInfo};
-use crate::errors::ERRORS;
 use crate::errors::{self, append_error};
+use crate::errors::{ErrorMessage, ERRORS};
+use crate::lexer::TokenKind;
 use crate::Compiler;
 use crate::Tokens;
 
@@ -38,10 +39,8 @@ pub fn current_token(compiler: &mut Compiler, tokens: &Tokens) -> Result<(), ()>
         .ast
         .log(format!("parse::current_token {:?}", &tokens));
     let current_token = &tokens[compiler.current_line_token];
-    let current_token_vec: &Vec<char> = &tokens[compiler.current_line_token].0.chars().collect();
-    if current_token_vec.len() == 0 {
-        return Ok(());
-    }
+    compiler.ast.current_token = current_token.clone();
+    let kind = compiler.lines_of_token_kinds[compiler.current_line][compiler.current_line_token];
 
     match elements::get_inbuilt_function_index_by_name(&mut compiler.ast, &current_token.0) {
         Some(index_of_function) => {
@@ -58,30 +57,69 @@ pub fn current_token(compiler: &mut Compiler, tokens: &Tokens) -> Result<(), ()>
                     if returntype.contains("&dyn Fn") {
                         function_call(compiler, &current_token.0, index_of_function)
                     } else {
-                        token_by_first_chars(compiler, &current_token.0, &current_token_vec)
+                        token_by_kind(compiler, &current_token.0, kind)
                     }
                 }
-                _ => token_by_first_chars(compiler, &current_token.0, &current_token_vec),
+                _ => token_by_kind(compiler, &current_token.0, kind),
             }
         }
         _ => match elements::get_inbuilt_type_index_by_name(&mut compiler.ast, &current_token.0) {
             Some(index_of_type) => elements::append::types(compiler, index_of_type),
-            _ => token_by_first_chars(compiler, &current_token.0, &current_token_vec),
+            _ => token_by_kind(compiler, &current_token.0, kind),
         },
     }
 }
 
-/// Parses something based on it's first character, if it's not been added as an inbuiltFn yet
+/// Parses something based on its TokenKind from the lexer, if it's not been added as an inbuiltFn yet
 ///
 /// The idea being to reduce this over time to make as many tokens as inbuiltFns, except new constant refs or values like ints, strings etc
-pub fn token_by_first_chars(
+pub fn token_by_kind(
     compiler: &mut Compiler,
     current_token: &String,
+    kind: TokenKind,
+) -> Result<(), ()> {
+    compiler.ast.log(format!(
+        "parse::token_by_kind {:?} {:?}",
+        &current_token, kind
+    ));
+    let current_token_vec: Vec<char> = current_token.chars().collect();
+    match kind {
+        TokenKind::Comment => comment_single_line(compiler, &current_token_vec),
+        TokenKind::RustCode => rustcode(compiler, &current_token_vec),
+        TokenKind::Arrow => function_definition_end(compiler),
+        TokenKind::StringLit => string(compiler, current_token),
+        TokenKind::IntLit => int(compiler, current_token),
+        TokenKind::FloatLit => float(compiler, current_token),
+        TokenKind::Ident => match current_token_vec[0] {
+            first_char if "abcdefghijklmnopqrstuvwxyz_".contains(first_char) => {
+                //check if contains a dot, so could be a struct edit, e.g. structname.key
+                if current_token.contains('.') {
+                    struct_edit(compiler, current_token)
+                } else {
+                    constant(compiler, current_token)
+                }
+            }
+            // inbuilt functions are already found by name, e.g. List::map
+            first_char if first_char.is_uppercase() => {
+                errors::append_error_with_suggestions(compiler, ERRORS.inbuilt_undefined)
+            }
+            _ => errors::append_error(compiler, ERRORS.parser_unknown),
+        },
+        TokenKind::Path => {
+            errors::append_error_with_suggestions(compiler, ERRORS.inbuilt_undefined)
+        }
+        TokenKind::Symbol => symbol_by_first_chars(compiler, &current_token_vec),
+    }
+}
+
+/// Parses a Symbol based on its first characters, e.g. = [ ( @
+pub fn symbol_by_first_chars(
+    compiler: &mut Compiler,
     current_token_vec: &Vec<char>,
 ) -> Result<(), ()> {
     compiler.ast.log(format!(
-        "parse::token_by_first_chars {:?} {:?}",
-        &current_token, current_token_vec
+        "parse::symbol_by_first_chars {:?}",
+        current_token_vec
     ));
     let first_char = current_token_vec[0];
     let second_char = if current_token_vec.len() > 1 {
@@ -92,7 +130,7 @@ pub fn token_by_first_chars(
     match first_char {
         '{' => match second_char {
             Some(_second) => {
-                return errors::append_error(compiler, 0, 1, ERRORS.a_struct);
+                return errors::append_error(compiler, ERRORS.a_struct);
             }
             None => struct_start(compiler),
         },
@@ -102,7 +140,7 @@ pub fn token_by_first_chars(
                 if second == ']' {
                     list_empty(compiler)
                 } else {
-                    return errors::append_error(compiler, 0, 1, ERRORS.list);
+                    return errors::append_error(compiler, ERRORS.list);
                 }
             }
             None => list_start(compiler),
@@ -116,12 +154,8 @@ pub fn token_by_first_chars(
         '@' => println(compiler),
         '?' => if_expression(compiler),
         '=' => match second_char {
-            Some(second) => {
-                if second == '>' {
-                    return function_definition_end(compiler);
-                } else {
-                    return errors::append_error(compiler, 0, 1, ERRORS.assign);
-                }
+            Some(_second) => {
+                return errors::append_error(compiler, ERRORS.assign);
             }
             _ => assignment(compiler),
         },
@@ -130,52 +164,14 @@ pub fn token_by_first_chars(
                 if second == '.' {
                     return loop_for_range_start(compiler);
                 } else {
-                    return errors::append_error(compiler, 0, 1, ERRORS.loop_for);
+                    return errors::append_error(compiler, ERRORS.loop_for);
                 }
             }
             _ => loop_end(compiler),
         },
-        '"' => string(compiler, &current_token),
-        //positive numbers
-        first_char if is_integer(&first_char.to_string()) => {
-            if is_float(&current_token) {
-                float(compiler, &current_token)
-            } else {
-                int(compiler, &current_token)
-            }
-        }
-        //negative numbers
-        '-' => match second_char {
-            Some(_digit) => {
-                if is_float(&current_token) {
-                    float(compiler, &current_token)
-                } else {
-                    int(compiler, &current_token)
-                }
-            }
-            None => {
-                return errors::append_error(compiler, 0, 1, ERRORS.int_negative);
-            }
-        },
-        /*
-        first_char if "IFS".contains(&first_char.to_string()) => {
-            //dbg!("Int Float or String", first_char);
-            match current_token {
-                "Int" =>
-            }
-        }*/
-        first_char if "abcdefghijklmnopqrstuvwxyz_".contains(&first_char.to_string()) => {
-            //dbg!("constant or constantRef", first_char);
-
-            //check if contains a dot, so could be a struct edit, e.g. structname.key
-            let token_split: Vec<&str> = current_token.split(".").into_iter().collect();
-            if token_split.len() > 1 {
-                struct_edit(compiler, &current_token)
-            } else {
-                constant(compiler, &current_token)
-            }
-        }
-        _ => return errors::append_error(compiler, 0, 1, "parser - unknown error"),
+        // a single -, since negative numbers are IntLit or FloatLit
+        '-' => errors::append_error(compiler, ERRORS.int_negative),
+        _ => return errors::append_error(compiler, ERRORS.parser_unknown),
     }
 }
 
@@ -189,7 +185,7 @@ pub fn comment_single_line(
         current_token_vec
     ));
     if current_token_vec.len() < 2 || current_token_vec[1] != '/' {
-        return errors::append_error(compiler, 0, 1, ERRORS.comment_single_line);
+        return errors::append_error(compiler, ERRORS.comment_single_line);
     }
     let val = concatenate_vec_strings(&compiler.lines_of_tokens[compiler.current_line]);
     elements::append::comment_single_line(compiler, val)
@@ -202,7 +198,7 @@ pub fn rustcode(compiler: &mut Compiler, current_token_vec: &Vec<char>) -> Resul
         .log(format!("parse::rustcode {:?}", current_token_vec));
 
     if current_token_vec.len() < 3 || current_token_vec[1] != '#' {
-        return errors::append_error(compiler, 0, 1, ERRORS.rustcode);
+        return errors::append_error(compiler, ERRORS.rustcode);
     }
     let is_premain = current_token_vec[2] == '#';
     let mut val = concatenate_vec_strings(&compiler.lines_of_tokens[compiler.current_line]);
@@ -233,11 +229,10 @@ pub fn string(compiler: &mut Compiler, current_token: &String) -> Result<(), ()>
     compiler
         .ast
         .log(format!("parse::string {:?}", current_token));
-    if is_string(&current_token) {
-        elements::append::string(compiler, current_token)
-    } else {
-        //dbg!(&self.lines_of_tokens);
-        errors::append_error(compiler, 0, 1, ERRORS.string)
+    match get_string_value(current_token) {
+        Ok(value) => elements::append::string(compiler, &get_string_literal(&value)),
+        // e.g. an invalid escape, at just the chars of the escape
+        Err((error, start, end)) => errors::append_error_in_token(compiler, error, start, end),
     }
 }
 
@@ -255,11 +250,11 @@ pub fn int(compiler: &mut Compiler, current_token: &String) -> Result<(), ()> {
     if (!first_char_is_negative_sign && !all_chars_are_numeric)
         || is_negative_all_other_chars_are_not_numeric
     {
-        errors::append_error(compiler, 0, current_token.len(), ERRORS.int)?;
+        errors::append_error(compiler, ERRORS.int)?;
     }
     match current_token.parse::<i64>() {
         Ok(_) => (),
-        Err(_) => errors::append_error(compiler, 0, current_token.len(), ERRORS.int_out_of_bounds)?,
+        Err(_) => errors::append_error(compiler, ERRORS.int_out_of_bounds)?,
     }
     elements::append::int(compiler, current_token)
     //errors::error_if_parent_is_invalid(compiler)
@@ -273,7 +268,7 @@ pub fn float(compiler: &mut Compiler, current_token: &String) -> Result<(), ()>
     if current_token.len() > 0 && is_float(current_token) {
         elements::append::float(compiler, current_token)
     } else {
-        return errors::append_error(compiler, 0, 1, ERRORS.float);
+        return errors::append_error(compiler, ERRORS.float);
     }
 }
 
@@ -417,7 +412,7 @@ pub fn struct_end(compiler: &mut Compiler) -> Result<(), ()> {
     let a_struct = parents::get_current_parent_element_from_parents(&compiler.ast);
     if let (ElementInfo::Struct(_, _, _), children) = a_struct {
         if children.len() == 0 {
-            return append_error(compiler, 0, 1, ERRORS.a_struct);
+            return append_error(compiler, ERRORS.a_struct);
         }
         if let Some(existing_struct_ref) =
             output::get_existing_identical_struct_el_ref(&mut compiler.ast.clone(), children)
@@ -429,7 +424,10 @@ pub fn struct_end(compiler: &mut Compiler) -> Result<(), ()> {
                 // and replace the Constant's reference to it, to use the existing struct ref instead
                 let mut struct_and_children = compiler.ast.elements[this_struct_el_ref].1.clone();
                 struct_and_children.push(this_struct_el_ref);
-                dbg!(&struct_and_children);
+                compiler.ast.log(format!(
+                    "parse::struct_end reuse existing struct {:?}",
+                    &struct_and_children
+                ));
                 for i in 0..struct_and_children.len() as usize {
                     compiler.ast.elements[struct_and_children[i]] = (ElementInfo::Unused, vec![]);
                 }
@@ -444,8 +442,6 @@ pub fn struct_end(compiler: &mut Compiler) -> Result<(), ()> {
                         compiler.ast.elements[structs_parent_ref].1 = vec![existing_struct_ref];
                     }
                 }
-
-                dbg!("got copy", struct_and_children);
             }
         }
     }
@@ -470,7 +466,7 @@ pub fn list_end(compiler: &mut Compiler) -> Result<(), ()> {
         (ElementInfo::List(returntype), children) => {
             if returntype == "Undefined".to_string() {
                 if children.len() == 0 {
-                    return append_error(compiler, 0, 1, ERRORS.list);
+                    return append_error(compiler, ERRORS.list);
                 } else {
                     // may as well get type now if child is a list - removes an error if it is a nested list as an arg for a func def
                     if let ElementInfo::List(list_type) =
@@ -549,12 +545,12 @@ pub fn loop_end(compiler: &mut Compiler) -> Result<(), ()> {
         }
     }
     if loopforrangewip_ref == 0 {
-        return append_error(compiler, 0, 1, ERRORS.loopfor_end_but_no_start);
+        return append_error(compiler, ERRORS.loopfor_end_but_no_start);
     }
     let loopforrangewip = compiler.ast.elements[loopforrangewip_ref].clone();
     //check it has two children, 1. let (with its child first int) 2. second int
     if loopforrangewip.1.len() < 3 {
-        return append_error(compiler, 0, 1, ERRORS.loopfor_malformed);
+        return append_error(compiler, ERRORS.loopfor_malformed);
     }
     let first_child = compiler.ast.elements[loopforrangewip.1[0]].clone();
     let second_child = compiler.ast.elements[loopforrangewip.1[1]].clone();
@@ -580,15 +576,24 @@ pub fn loop_end(compiler: &mut Compiler) -> Result<(), ()> {
                             parents::vec_remove_head(&new_loopforrange_children);
                         compiler.ast.elements[loopforrangewip_ref] =
                             (new_loopforrange, new_loopforrange_children);
+                        // the loop is finished, so outdent back to the parent of the loop
+                        if let Some(position) = compiler
+                            .ast
+                            .parents
+                            .iter()
+                            .position(|&parent_ref| parent_ref == loopforrangewip_ref)
+                        {
+                            compiler.ast.parents.truncate(position.max(1));
+                        }
                         Ok(())
                     }
-                    _ => append_error(compiler, 0, 1, ERRORS.loopfor_malformed),
+                    _ => append_error(compiler, ERRORS.loopfor_malformed),
                 }
             } else {
-                append_error(compiler, 0, 1, ERRORS.loopfor_malformed)
+                append_error(compiler, ERRORS.loopfor_malformed)
             }
         }
-        _ => append_error(compiler, 0, 1, ERRORS.loopfor_malformed),
+        _ => append_error(compiler, ERRORS.loopfor_malformed),
     }
 }
 
@@ -630,7 +635,7 @@ pub fn function_definition_end(compiler: &mut Compiler) -> Result<(), ()> {
 
     //error if count is NOT odd (argtypes + returntype + argnames)
     if children.len() % 2 == 0 || children.len() == 0 {
-        return errors::append_error(compiler, 0, 1, ERRORS.funcdef_args);
+        return errors::append_error(compiler, ERRORS.funcdef_args);
     }
 
     //TODO deal with brackets later (i.e. for type signature containing argument(s) which are fns)
@@ -643,7 +648,7 @@ pub fn function_definition_end(compiler: &mut Compiler) -> Result<(), ()> {
         ElementInfo::Type(_) => (),
         ElementInfo::Parens => (),
         ElementInfo::List(_) => (),
-        _ => return errors::append_error(compiler, 0, 1, ERRORS.funcdef_argtypes_first),
+        _ => return errors::append_error(compiler, ERRORS.funcdef_argtypes_first),
     }
 
     // now change any top level List items into Types
@@ -678,6 +683,10 @@ pub fn function_definition_end(compiler: &mut Compiler) -> Result<(), ()> {
                                 func_def_ref,
                             );
 
+                            // the function is defined by the constant's name, rather than the "\"
+                            compiler.ast.element_tokens[func_def_ref] =
+                                compiler.ast.element_tokens[constant_ref].clone();
+
                             // replace assignment with unused
                             compiler.ast.elements[assignment_ref] = (ElementInfo::Unused, vec![]);
 
@@ -738,30 +747,6 @@ pub fn functiontypesig_or_functionreference_end(compiler: &mut Compiler) -> Resu
     elements::append::functiontypesig_or_functionreference_end(compiler)
 }
 
-/// Checks if an Int
-pub fn is_integer(text: &String) -> bool {
-    let mut is_valid = true;
-    let all_chars_are_numeric = text.chars().into_iter().all(|c| c.is_numeric());
-    let text_chars: Vec<char> = text.chars().collect();
-    let first_char_is_negative_sign = text_chars[0] == '-';
-
-    let is_negative_all_other_chars_are_numeric = first_char_is_negative_sign
-        && text_chars.len() > 1
-        && text_chars[1..text_chars.len()]
-            .into_iter()
-            .all(|c| c.is_numeric());
-
-    if !all_chars_are_numeric && !is_negative_all_other_chars_are_numeric {
-        is_valid = false;
-    }
-
-    match text.parse::<i64>() {
-        Ok(_) => (),
-        Err(_) => is_valid = false,
-    }
-    is_valid
-}
-
 /// Checks if a Float
 pub fn is_float(text: &String) -> bool {
     let mut is_valid = true;
@@ -802,12 +787,96 @@ pub fn is_float(text: &String) -> bool {
 
 /// Checks if a string
 pub fn is_string(text: &String) -> bool {
-    let mut is_valid = true;
     let char_vec: Vec<char> = text.chars().collect();
-    if text.len() < 2 || char_vec[0] != '"' || char_vec[text.len() - 1] != '"' {
-        is_valid = false;
+    if text.starts_with("\"\"\"") {
+        char_vec.len() >= 6 && text.ends_with("\"\"\"")
+    } else {
+        char_vec.len() >= 2 && char_vec[0] == '"' && char_vec[char_vec.len() - 1] == '"'
+    }
+}
+
+/// Gets the value of a string, replacing its escapes `\"` `\\` `\n` `\r` `\t` and `\u{..}`, e.g. `"a\tb"` is a, a tab, then b.
+/// A triple-quoted string, e.g. `"""say "hi""""`, can span lines, and can contain quotes without escaping them.
+/// Otherwise returns the error, and the start and end of the invalid part of the string
+pub fn get_string_value(text: &String) -> Result<String, (ErrorMessage, usize, usize)> {
+    let char_vec: Vec<char> = text.chars().collect();
+    let whole_string = (ERRORS.string, 0, char_vec.len().saturating_sub(1));
+    if !is_string(text) {
+        return Err(whole_string);
+    }
+    let is_triple_quoted = text.starts_with("\"\"\"");
+    let quotes = if is_triple_quoted { 3 } else { 1 };
+    let mut value = "".to_string();
+    let mut index = quotes;
+    while index < char_vec.len() - quotes {
+        let escape = if index + 1 < char_vec.len() - quotes {
+            Some(char_vec[index + 1])
+        } else {
+            None
+        };
+        match char_vec[index] {
+            '\\' => {
+                let (c, length) = match escape {
+                    Some('"') => (Some('"'), 2),
+                    Some('\\') => (Some('\\'), 2),
+                    Some('n') => (Some('\n'), 2),
+                    Some('r') => (Some('\r'), 2),
+                    Some('t') => (Some('\t'), 2),
+                    Some('u') => get_unicode_escape(&char_vec[index..char_vec.len() - quotes]),
+                    _ => (None, 2),
+                };
+                match c {
+                    Some(c) => value.push(c),
+                    // the backslash escapes the closing quote, so the string is unterminated
+                    None if escape.is_none() => return Err(whole_string),
+                    None => return Err((ERRORS.string_escape, index, index + length - 1)),
+                }
+                index += length;
+            }
+            '"' if !is_triple_quoted => return Err((ERRORS.string, index, index)),
+            c => {
+                value.push(c);
+                index += 1;
+            }
+        }
+    }
+    Ok(value)
+}
+
+/// Gets the char of a `\u{..}` escape at the start of the chars, with 1 to 6 hex digits, and the length of the escape
+fn get_unicode_escape(chars: &[char]) -> (Option<char>, usize) {
+    if chars.get(2) != Some(&'{') {
+        return (None, 2);
+    }
+    match chars.iter().position(|&c| c == '}') {
+        Some(close) => {
+            let hex: String = chars[3..close].iter().collect();
+            let is_valid =
+                (1..=6).contains(&hex.len()) && hex.chars().all(|c| c.is_ascii_hexdigit());
+            let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+            (if is_valid { c } else { None }, close + 1)
+        }
+        None => (None, 3),
     }
-    is_valid
+}
+
+/// Gets a string as one line in double quotes, which is the same in Rust and JavaScript,
+/// escaping quotes, backslashes, line breaks, tabs, and any other control chars as `\u{..}`
+pub fn get_string_literal(value: &str) -> String {
+    let mut literal = "\"".to_string();
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() => literal.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
 }
 
 /// Gets args from a Dyn Fn, e.g.
@@ -872,29 +941,6 @@ pub fn strip_trailing_whitespace(input: &String) -> String {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_is_integer() {
-        let test_case_passes = [
-            "1",
-            "123",
-            "1234567890",
-            "9223372036854775807",
-            "-1",
-            "-123",
-            "-1234567890",
-            "-9223372036854775808",
-        ];
-        for test in test_case_passes {
-            let input = &test.to_string();
-            assert!(is_integer(input));
-        }
-        let test_case_fails = ["1a", "9223372036854775808", "-1a", "-9223372036854775809"];
-        for test in test_case_fails {
-            let input = &test.to_string();
-            assert!(!is_integer(input));
-        }
-    }
-
     #[test]
     fn test_is_float() {
         let test_case_passes = [
@@ -937,18 +983,86 @@ mod tests {
             "\"-123\"",
             "\"-1234567890\"",
             "\"-9223372036854775808\"",
+            "\"é\"",
+            "\"\"\"a\r\nb\"\"\"",
         ];
         for test in test_case_passes {
             let input = &test.to_string();
             assert!(is_string(input));
         }
-        let test_case_fails = ["\"1a", "9223372036854775808\"", "'-1a'", ""];
+        let test_case_fails = [
+            "\"1a",
+            "9223372036854775808\"",
+            "'-1a'",
+            "",
+            "\"",
+            "\"\"\"\"",
+            "\"\"\"a\"",
+        ];
         for test in test_case_fails {
             let input = &test.to_string();
             assert!(!is_string(input));
         }
     }
 
+    #[test]
+    fn test_get_string_value() {
+        let test_case_passes = [
+            ("\"abc\"", "abc"),
+            ("\"\"", ""),
+            ("\"say \\\"hi\\\"\"", "say \"hi\""),
+            ("\"a\\\\b\\nc\\rd\\te\"", "a\\b\nc\rd\te"),
+            ("\"\\u{1F600}\\u{e9}\"", "😀é"),
+            ("\"\"\"say \"hi\"\r\nagain\"\"\"", "say \"hi\"\r\nagain"),
+            ("\"\"\"\"\"\"", ""),
+        ];
+        for (input, expected) in test_case_passes {
+            assert_eq!(
+                get_string_value(&input.to_string()),
+                Ok(expected.to_string()),
+                "{}",
+                input
+            );
+        }
+        let test_case_fails = [
+            ("\"abc", ERRORS.string, 0, 3),
+            ("\"abc\\\"", ERRORS.string, 0, 5),
+            ("\"a\"b\"", ERRORS.string, 2, 2),
+            ("\"a\\qb\"", ERRORS.string_escape, 2, 3),
+            ("\"\\u1F600\"", ERRORS.string_escape, 1, 2),
+            ("\"\\u{1F600\"", ERRORS.string_escape, 1, 3),
+            ("\"\\u{}\"", ERRORS.string_escape, 1, 4),
+            ("\"\\u{D800}\"", ERRORS.string_escape, 1, 8),
+            ("\"\\u{1000000}\"", ERRORS.string_escape, 1, 11),
+            ("\"\"\"a\r\n\\q\"\"\"", ERRORS.string_escape, 6, 7),
+        ];
+        for (input, error, start, end) in test_case_fails {
+            assert_eq!(
+                get_string_value(&input.to_string()),
+                Err((error, start, end)),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_string_literal() {
+        let test_cases = [
+            ("abc", "\"abc\""),
+            ("say \"hi\"", "\"say \\\"hi\\\"\""),
+            ("a\\b\r\nc\td", "\"a\\\\b\\r\\nc\\td\""),
+            ("é\u{1b}", "\"é\\u{1b}\""),
+        ];
+        for (value, expected) in test_cases {
+            assert_eq!(get_string_literal(value), expected);
+            assert_eq!(
+                get_string_value(&expected.to_string()),
+                Ok(value.to_string())
+            );
+        }
+    }
+
     #[test]
     fn test_get_args_from_dyn_fn() {
         let test_cases = ["()", "(i64", "(i64, i64)"];
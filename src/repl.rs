@@ -0,0 +1,300 @@
+/*! An interactive REPL, i.e. `toylang repl`
+
+    One Compiler is kept alive for the whole session, and each line entered is parsed with `parse_one_line`,
+    so constants and functions defined earlier stay in scope. Each line shows the infered type and value of what it defined,
+    using the tree-walking interpreter, or the generated Rust if it can't be evaluated, e.g. `##` rust code.
+
+    ```text
+    > = a + 1 2
+    a: i64 = 3
+    > = double \ i64 i64 x =>
+    ... * x 2
+    fn double(x: i64) -> i64
+    > double a
+    6: i64
+    ```
+
+    A line with an error is discarded, leaving the session as it was.
+
+    - `:rust` shows the Rust output of the whole session so far
+    - `:ast` shows the elements of the AST
+    - `:reset` starts over with an empty session
+*/
+use crate::ast::elements::{self, DebugElements, ElIndex, ElementInfo};
+use crate::ast::output;
+use crate::eval::{Interpreter, Value};
+use crate::formatting;
+use crate::Compiler;
+use std::collections::HashSet;
+use std::io::{self, BufRead, Write};
+
+pub struct Repl {
+    pub compiler: Compiler,
+    pub interpreter: Interpreter,
+    /// the children of Root which have been evaluated, i.e. before the current input.
+    /// Not just a count, since e.g. List::map inserts a copy of its function before the end of Root's children
+    evaluated: HashSet<ElIndex>,
+    /// the Rust output after the last complete input, to find the Rust generated by the next one
+    output: String,
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        Repl::new()
+    }
+}
+
+impl Repl {
+    pub fn new() -> Repl {
+        let compiler = Compiler::default();
+        let mut with_output = compiler.clone();
+        output::set_output(&mut with_output);
+        Repl {
+            compiler,
+            interpreter: Interpreter::new(false),
+            evaluated: HashSet::new(),
+            output: with_output.ast.output,
+        }
+    }
+
+    /// A multi-line function or loop is still open, so the next line continues it
+    pub fn is_continuing(&self) -> bool {
+        self.compiler.ast.parents.len() > 1
+    }
+
+    /// Handles one line of input, either a command or toylang code.
+    /// Returns what to show, or the errors, in which case the line is discarded
+    pub fn eval_line(&mut self, line: &str) -> Result<String, String> {
+        match line.trim() {
+            ":rust" => return Ok(self.get_output_of_session()),
+            ":ast" => return Ok(format!("{:?}", DebugElements(&self.compiler.ast.elements))),
+            ":reset" => {
+                *self = Repl::new();
+                return Ok("".to_string());
+            }
+            "" => return Ok("".to_string()),
+            _ => (),
+        }
+        let previous_compiler = self.compiler.clone();
+        let previous_frames = self.interpreter.frames.clone();
+        let result = self.parse_line(line).and_then(|_| {
+            if self.is_continuing() {
+                Ok("".to_string())
+            } else {
+                self.eval_new_root_children()
+            }
+        });
+        if result.is_err() {
+            self.compiler = previous_compiler;
+            self.interpreter.frames = previous_frames;
+            self.interpreter.printed = vec![];
+        }
+        result
+    }
+
+    /// Appends the line to the file contents, then re-tokenizes and parses only the new rows.
+    /// The earlier rows don't change, since lines are only ever added after them
+    fn parse_line(&mut self, line: &str) -> Result<(), String> {
+        let first_new_row = self.compiler.lines_of_tokens.len();
+        if !self.compiler.file.filecontents.is_empty() {
+            self.compiler.file.filecontents.push_str("\r\n");
+        }
+        self.compiler.file.filecontents.push_str(line);
+        self.compiler.lines_of_chars = vec![];
+        self.compiler.lines_of_tokens = vec![];
+        self.compiler.set_lines_of_chars();
+        self.compiler.set_lines_of_tokens();
+        for row in first_new_row..self.compiler.lines_of_tokens.len() {
+            let result = self.compiler.parse_one_line(row);
+            if result.is_err() || !self.compiler.error_stack.is_empty() {
+                return Err(self.compiler.get_rendered_errors().join("\r\n"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluates the children of Root added by this input, and describes each of them
+    fn eval_new_root_children(&mut self) -> Result<String, String> {
+        // set_output infers the remaining types, but also resets the parents, so is only used on a copy
+        let mut with_output = self.compiler.clone();
+        output::set_output(&mut with_output);
+
+        let mut lines: Vec<String> = vec![];
+        let root_children = self.compiler.ast.elements[0].1.clone();
+        for &el_index in &root_children {
+            if self.evaluated.contains(&el_index) {
+                continue;
+            }
+            if let ElementInfo::Rust(_, _) = self.compiler.ast.elements[el_index].0 {
+                lines.push(get_new_lines(&self.output, &with_output.ast.output));
+                continue;
+            }
+            let value = self.interpreter.eval(&self.compiler, el_index)?;
+            lines.append(&mut self.interpreter.printed);
+            if let Some(description) = self.describe(&with_output, el_index, &value) {
+                lines.push(description);
+            }
+        }
+        self.evaluated.extend(root_children);
+        self.output = with_output.ast.output;
+        Ok(lines.join("\r\n"))
+    }
+
+    /// e.g. "a: i64 = 3" for a constant, "3: i64" for an expression, or the signature of a function.
+    /// Uses the types infered by set_output
+    fn describe(&self, with_output: &Compiler, el_index: ElIndex, value: &Value) -> Option<String> {
+        let (el_info, children) = &with_output.ast.elements[el_index];
+        match el_info {
+            ElementInfo::Assignment => match &with_output.ast.elements[*children.first()?].0 {
+                ElementInfo::Constant(name, returntype) => {
+                    let value = self.interpreter.frames[0].get(name)?;
+                    Some(format!("{}: {} = {:?}", name, returntype, value))
+                }
+                _ => None,
+            },
+            ElementInfo::FunctionDef(name, argnames, argtypes, returntype) => {
+                let argmodifiers = vec!["".to_string(); argnames.len()];
+                let args = formatting::get_formatted_argname_argtype_pairs(
+                    argnames,
+                    argtypes,
+                    &argmodifiers,
+                );
+                Some(format!("fn {}({}) -> {}", name, args, returntype))
+            }
+            ElementInfo::Println
+            | ElementInfo::LoopForRange(_, _, _)
+            | ElementInfo::CommentSingleLine(_)
+            | ElementInfo::Indent
+            | ElementInfo::Seol
+            | ElementInfo::Eol
+            | ElementInfo::Unused => None,
+            _ => Some(format!(
+                "{:?}: {}",
+                value,
+                elements::get_elementinfo_type(&with_output.ast, el_info)
+            )),
+        }
+    }
+
+    fn get_output_of_session(&self) -> String {
+        let mut with_output = self.compiler.clone();
+        output::set_output(&mut with_output);
+        formatting::pretty_print(&with_output.ast.output)
+    }
+}
+
+/// The lines of the new output which weren't in the old output, i.e. between their common start and end
+fn get_new_lines(old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let start = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+    let end = old_lines[start..]
+        .iter()
+        .rev()
+        .zip(new_lines[start..].iter().rev())
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+    new_lines[start..new_lines.len() - end]
+        .iter()
+        .map(|line| line.trim())
+        .collect::<Vec<&str>>()
+        .join("\r\n")
+}
+
+/// CLI - read lines from stdin until it closes
+pub fn main() {
+    let mut repl = Repl::new();
+    println!("toylang repl - :rust, :ast, :reset");
+    let stdin = io::stdin();
+    loop {
+        print!("{}", if repl.is_continuing() { "... " } else { "> " });
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => (),
+        }
+        match repl.eval_line(line.trim_end_matches(['\r', '\n'])) {
+            Ok(text) if text.is_empty() => (),
+            Ok(text) => println!("{}", text),
+            Err(error) => eprintln!("{}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval_line() {
+        let mut repl = Repl::new();
+        let test_case_passes = vec![
+            ("= a + 1 2", "a: i64 = 3"),
+            ("= double \\ i64 i64 x =>", ""),
+            ("* x 2", "fn double(x: i64) -> i64"),
+            ("double a", "6: i64"),
+            ("@ \"hello\"", "hello"),
+            ("= b \"string\"", "b: String = \"string\""),
+        ];
+        for (line, expected) in test_case_passes {
+            assert_eq!(repl.eval_line(line), Ok(expected.to_string()), "{}", line);
+        }
+        // lines with compile or runtime errors are discarded
+        assert!(repl.eval_line("monkey").is_err());
+        assert!(repl.eval_line("+ a 0.5").is_err());
+        assert_eq!(repl.eval_line("a"), Ok("3: i64".to_string()));
+        assert!(!repl.is_continuing());
+    }
+
+    #[test]
+    fn test_eval_line_continuing() {
+        let mut repl = Repl::new();
+        assert_eq!(repl.eval_line("= f \\ i64 i64 x =>"), Ok("".to_string()));
+        assert!(repl.is_continuing());
+        assert_eq!(
+            repl.eval_line("+ x 1"),
+            Ok("fn f(x: i64) -> i64".to_string())
+        );
+        assert!(!repl.is_continuing());
+        assert_eq!(repl.eval_line(".. i 0 2"), Ok("".to_string()));
+        assert_eq!(repl.eval_line("@ f i"), Ok("".to_string()));
+        assert_eq!(repl.eval_line("."), Ok("1\r\n2".to_string()));
+    }
+
+    #[test]
+    fn test_eval_line_list_map() {
+        let mut repl = Repl::new();
+        let _ = repl.eval_line("= l [ 1 ]");
+        let _ = repl.eval_line("= f \\ i64 i64 x => * x 2");
+        assert_eq!(repl.eval_line("@ \"hi\""), Ok("hi".to_string()));
+        // the copy of f for List::map is inserted before the new line, but the earlier lines aren't evaluated again
+        assert_eq!(
+            repl.eval_line("= m List::map l ( f )"),
+            Ok("fn f_for_list_map(x: &i64) -> i64\r\nm: Vec<i64> = [2]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_eval_line_commands() {
+        let mut repl = Repl::new();
+        let _ = repl.eval_line("= a 123");
+        assert!(repl
+            .eval_line(":rust")
+            .unwrap()
+            .contains("let a: i64 = 123;"));
+        assert!(repl.eval_line(":ast").unwrap().contains("Constant: a"));
+        assert_eq!(repl.eval_line(":reset"), Ok("".to_string()));
+        assert!(repl.eval_line("@ a").is_err());
+    }
+
+    #[test]
+    fn test_eval_line_rust_code() {
+        let mut repl = Repl::new();
+        assert_eq!(repl.eval_line("##let x = 1;"), Ok("let x = 1;".to_string()));
+    }
+}
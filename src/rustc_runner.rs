@@ -0,0 +1,191 @@
+/*! Compile and run a toylang file in one step, i.e. `toylang run`
+
+    Instead of saving `output.rs` into a cargo project, the output is saved to a temporary directory,
+    compiled with `rustc`, then run. The program's stdout and stderr are streamed to the console, and toylang exits with the program's exit code.
+
+    ```bash
+    toylang run test.toy
+    ```
+
+    `rustc` must be on the PATH, or set the `RUSTC` environment variable to use another one.
+
+    If rustc finds errors in the output, they are traced back to the toylang code which output them, using the source map,
+    and shown as a TOYLANG COMPILE ERROR T0061, with rustc's own error as a note, e.g.
+
+    ```text
+    ----------
+    error[T0061] ./src/test.toy:2:0
+     + x a
+     ^ rustc found an error in the Rust code output for this element, so it can't be run - see the note for rustc's error
+    note: rustc error[E0434]: can't capture dynamic environment in a fn item
+    ```
+*/
+use crate::ast::output;
+use crate::diagnostic::{Diagnostic, Span};
+use crate::errors::ERRORS;
+use crate::formatting::{self, PositionMap};
+use crate::Compiler;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+/// CLI - compile the toylang file, build the output with rustc, then run it
+pub fn main(filepath: String) {
+    let mut compiler = Compiler::default();
+    if let Err(e) = compiler.file.get(&filepath, false) {
+        eprintln!("Problem reading file: {}", e);
+        process::exit(1);
+    }
+    let _ = compiler.run_main_tasks();
+    compiler.validate_output();
+    compiler.print_diagnostics(false);
+    if !compiler.error_stack.is_empty() {
+        process::exit(1);
+    }
+
+    let dir = env::temp_dir().join(format!("toylang_run_{}", process::id()));
+    let exit_code = match build(&compiler, &dir) {
+        Ok(binary) => run(&binary),
+        Err(errors) => {
+            eprintln!("----------\r\n\r\nTOYLANG COMPILE ERROR:");
+            for error in errors {
+                eprintln!("{}", error);
+            }
+            eprintln!("----------\r\n");
+            1
+        }
+    };
+    let _ = fs::remove_dir_all(&dir);
+    process::exit(exit_code);
+}
+
+/// Saves the pretty printed output as output.rs in the dir, and compiles it with rustc.
+/// Returns the path of the binary, or rustc's errors mapped back to the toylang code
+pub fn build(compiler: &Compiler, dir: &Path) -> Result<PathBuf, Vec<String>> {
+    fs::create_dir_all(dir).map_err(|e| vec![format!("Problem creating {:?}: {}", dir, e)])?;
+    let source = dir.join("output.rs");
+    let (pretty, position_map) = formatting::pretty_print_with_position_map(&compiler.ast.output);
+    fs::write(&source, pretty).map_err(|e| vec![format!("Problem writing {:?}: {}", source, e)])?;
+    let binary = dir.join(format!("output{}", env::consts::EXE_SUFFIX));
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    // the output is full of unused variables, clones etc. which aren't useful to warn about here
+    let result = Command::new(&rustc)
+        .args([
+            "--edition",
+            "2021",
+            "--error-format=json",
+            "-A",
+            "warnings",
+            "-o",
+        ])
+        .arg(&binary)
+        .arg(&source)
+        .output()
+        .map_err(|e| vec![format!("Problem running {}: {}", rustc, e)])?;
+    if result.status.success() {
+        Ok(binary)
+    } else {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        let lines = compiler.get_source_lines();
+        Err(get_errors_from_rustc_json(compiler, &position_map, &stderr)
+            .iter()
+            .map(|error| error.render(&compiler.file.filename, &lines))
+            .collect())
+    }
+}
+
+/// rustc outputs one JSON diagnostic per line. Each error is a Diagnostic at the Token of the Element
+/// which output the code at the error's primary span, with rustc's error as a note.
+/// If it can't be traced back, the note is rustc's own rendered error instead
+fn get_errors_from_rustc_json(
+    compiler: &Compiler,
+    position_map: &PositionMap,
+    stderr: &str,
+) -> Vec<Diagnostic> {
+    let mut errors = vec![];
+    for line in stderr.lines() {
+        let diagnostic: serde_json::Value = match serde_json::from_str(line) {
+            Ok(diagnostic) => diagnostic,
+            Err(_) => continue,
+        };
+        let primary_span = match diagnostic["spans"].as_array().and_then(|spans| {
+            spans
+                .iter()
+                .find(|span| span["is_primary"].as_bool() == Some(true))
+        }) {
+            Some(span) => span,
+            None => continue,
+        };
+        if diagnostic["level"].as_str() != Some("error") {
+            continue;
+        }
+        let mut note = match diagnostic["code"]["code"].as_str() {
+            Some(code) => format!("rustc error[{}]: ", code),
+            None => "rustc error: ".to_string(),
+        };
+        note.push_str(diagnostic["message"].as_str().unwrap_or(""));
+        if let Some(label) = primary_span["label"].as_str() {
+            note = format!("{}: {}", note, label);
+        }
+        let pretty_line = primary_span["line_start"].as_u64().unwrap_or(0) as usize;
+        let pretty_column =
+            (primary_span["column_start"].as_u64().unwrap_or(1) as usize).saturating_sub(1);
+        let token = formatting::get_original_position(position_map, (pretty_line, pretty_column))
+            .and_then(|(line, column)| {
+                output::get_token_of_output_position(&compiler.ast, line, column)
+            });
+        let error = match token {
+            Some(token) => {
+                Diagnostic::error(ERRORS.rustc_error, Span::from(&token)).with_note(&note)
+            }
+            None => Diagnostic::error(ERRORS.rustc_error, Span::default())
+                .with_note(diagnostic["rendered"].as_str().unwrap_or(&note)),
+        };
+        errors.push(error);
+    }
+    errors
+}
+
+/// Runs the binary, streaming its stdout and stderr, and returns its exit code
+fn run(binary: &Path) -> i32 {
+    match Command::new(binary).status() {
+        Ok(status) => status.code().unwrap_or(1),
+        Err(e) => {
+            eprintln!("Problem running {:?}: {}", binary, e);
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsp::compile;
+
+    #[test]
+    fn test_build() {
+        let dir = env::temp_dir().join(format!("toylang_test_build_{}", process::id()));
+        let binary = build(&compile("= a + 1 2\r\n@ a"), &dir).unwrap();
+        let result = Command::new(binary).output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&result.stdout), "3\n");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_build_errors_are_mapped_to_toylang() {
+        let dir = env::temp_dir().join(format!("toylang_test_build_errors_{}", process::id()));
+        let errors = build(
+            &compile("= a 123\r\n= f \\ i64 i64 x => + x a\r\n@ f 1"),
+            &dir,
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        // the body of a single line function is the row after "=>", on the same line of the file, and "+" outputs its args too
+        assert_eq!(
+            errors[0],
+            "----------\r\nerror[T0061] ./src/:2:0\r\n + x a\r\n ^ rustc found an error in the Rust code output for this element, so it can't be run - see the note for rustc's error\r\nnote: rustc error[E0434]: can't capture dynamic environment in a fn item"
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -13,13 +13,13 @@ pub async fn main() {
         .map(|p: HashMap<String, String>| match p.get("filepath") {
             Some(filepath) => {
                 //let result = compiler_runner::main(input, debug, output, nosave, tokens, code);
-                let mut compiler =
-                    Compiler::new(filepath.clone(), false, None, true, true).unwrap();
+                let mut compiler = Compiler::new(filepath.clone(), false, None, true).unwrap();
                 if let Err(e) = compiler.run(true, false) {
                     println!("Application error: {}", e);
                 }
                 let e = ErrorStackJson {
                     errors: compiler.error_stack,
+                    warnings: compiler.warnings,
                 };
                 let j = serde_json::to_string(&e).unwrap();
                 Response::builder().body(j)
@@ -0,0 +1,181 @@
+/*! Formats toylang source code into a canonical layout, i.e. `toylang fmt`
+
+    - tokens on each line are separated by a single space
+    - the body of a multi-line function (`\ ... =>`) is indented by 4 spaces per level
+    - single line functions stay on one line
+    - `//` comments and `##` rust lines are kept as they are, just re-indented
+    - runs of blank lines are reduced to one, and the file ends with a single line break
+
+    ```bash
+    toylang fmt test.toy
+    toylang fmt test.toy --check
+    ```
+
+    The `--check` flag doesn't write the file, it just exits with an error if the file isn't already formatted, e.g. for CI
+*/
+use crate::ast::elements::ElementInfo;
+use crate::Compiler;
+use std::fs;
+use std::process;
+
+const INDENT: &str = "    ";
+
+/// Returns the formatted source, or the compile errors if it can't be parsed,
+/// since the indentation depends on where the parser decides each function ends
+pub fn format(text: &str) -> Result<String, Vec<String>> {
+    let mut compiler = Compiler::default();
+    compiler.file.filecontents = text.to_string();
+    compiler.set_lines_of_chars();
+    compiler.set_lines_of_tokens();
+
+    let mut lines: Vec<String> = vec![];
+    let mut previous_row_ends_with_arrow = false;
+    for row in 0..compiler.lines_of_tokens.len() {
+        let depth = get_function_depth(&compiler);
+        if compiler.parse_one_line(row).is_err() || !compiler.error_stack.is_empty() {
+            break;
+        }
+        let line_of_tokens = &compiler.lines_of_tokens[row];
+        let content = line_of_tokens
+            .iter()
+            .map(|token| token.0.clone())
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        // lines_of_chars splits after "=>", so the body of a single line function is the next row
+        if previous_row_ends_with_arrow {
+            if !content.is_empty() {
+                if let Some(line) = lines.last_mut() {
+                    line.push(' ');
+                    line.push_str(&content);
+                }
+            }
+        } else if content.is_empty() {
+            lines.push("".to_string());
+        } else {
+            lines.push(format!("{}{}", INDENT.repeat(depth), content));
+        }
+        previous_row_ends_with_arrow = match line_of_tokens.last() {
+            Some(token) => token.0 == "=>",
+            None => false,
+        };
+    }
+    if !compiler.error_stack.is_empty() {
+        return Err(compiler.get_rendered_errors());
+    }
+    Ok(join_lines(lines, get_line_break(text)))
+}
+
+/// Keeps the line breaks of the file, e.g. LF, defaulting to CRLF like the rest of toylang
+fn get_line_break(text: &str) -> &'static str {
+    if !text.contains("\r\n") && text.contains('\n') {
+        "\n"
+    } else {
+        "\r\n"
+    }
+}
+
+/// The number of function bodies still open before parsing the next row
+fn get_function_depth(compiler: &Compiler) -> usize {
+    compiler
+        .ast
+        .parents
+        .iter()
+        .filter(|&&parent_ref| {
+            matches!(
+                compiler.ast.elements[parent_ref].0,
+                ElementInfo::FunctionDef(_, _, _, _)
+            )
+        })
+        .count()
+}
+
+/// Removes leading, trailing and repeated blank lines
+fn join_lines(lines: Vec<String>, line_break: &str) -> String {
+    let mut output = "".to_string();
+    let mut previous_is_blank = true;
+    for line in lines {
+        let is_blank = line.is_empty();
+        if !(is_blank && previous_is_blank) {
+            output = format!("{}{}{}", output, line, line_break);
+        }
+        previous_is_blank = is_blank;
+    }
+    while output.ends_with(&line_break.repeat(2)) {
+        output.truncate(output.len() - line_break.len());
+    }
+    output
+}
+
+/// CLI - format the file in place, or with check just report if it would change
+pub fn main(filepath: String, check: bool) {
+    let text = fs::read_to_string(&filepath).unwrap_or_else(|err| {
+        eprintln!("Problem reading file: {}", err);
+        process::exit(1);
+    });
+    let formatted = format(&text).unwrap_or_else(|errors| {
+        eprintln!("----------\r\n\r\nTOYLANG COMPILE ERROR:");
+        for error in errors {
+            eprintln!("{}", error);
+        }
+        eprintln!("----------\r\n");
+        process::exit(1);
+    });
+    if formatted == text {
+        println!("Already formatted: {}", filepath);
+    } else if check {
+        println!("Would reformat: {}", filepath);
+        process::exit(1);
+    } else {
+        fs::write(&filepath, formatted).unwrap_or_else(|err| {
+            eprintln!("Problem writing file: {}", err);
+            process::exit(1);
+        });
+        println!("Formatted: {}", filepath);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let test_case_passes = vec![
+            ("=   a    123", "= a 123\r\n"),
+            ("\r\n\r\n= a 123\r\n\r\n\r\n@ a\r\n\r\n", "= a 123\r\n\r\n@ a\r\n"),
+            (
+                "  // a comment  \r\n= a \"a    string\"",
+                "// a comment\r\n= a \"a    string\"\r\n",
+            ),
+            (
+                "= a \\ i64 i64 i64 arg1 arg2   =>   + arg1 arg2",
+                "= a \\ i64 i64 i64 arg1 arg2 => + arg1 arg2\r\n",
+            ),
+            (
+                "= a \\ i64 i64 i64 arg1 arg2 =>\r\n= b + arg1 123\r\n  // comment\r\n     - b arg2\r\n@ a 1 2",
+                "= a \\ i64 i64 i64 arg1 arg2 =>\r\n    = b + arg1 123\r\n    // comment\r\n    - b arg2\r\n@ a 1 2\r\n",
+            ),
+        ];
+        for (input, expected) in test_case_passes {
+            assert_eq!(format(input), Ok(expected.to_string()));
+            // formatting is idempotent
+            assert_eq!(format(expected), Ok(expected.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_format_keeps_line_breaks() {
+        let lf = "= a \\ i64 i64 x =>\n+ x 1\n\n\n@ a 1\n";
+        assert_eq!(
+            format(lf),
+            Ok("= a \\ i64 i64 x =>\n    + x 1\n\n@ a 1\n".to_string())
+        );
+        assert_eq!(format("= a 123"), Ok("= a 123\r\n".to_string()));
+    }
+
+    #[test]
+    fn test_format_with_errors() {
+        assert!(format("= a 123\r\nmonkey").is_err());
+    }
+}
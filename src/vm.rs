@@ -0,0 +1,329 @@
+/*! A stack based virtual machine, which runs a [Program] of bytecode compiled from the AST
+
+    For embedding toylang in a Rust program: the source is compiled once, then the Program can be run
+    any number of times, without parsing it again.
+
+    ```
+    use toylang::vm::{Program, Vm};
+
+    let program: Program = "= double \\ i64 i64 x => * x 2\r\n@ double 21".parse().unwrap();
+    let mut vm = Vm::new(false);
+    vm.run(&program).unwrap();
+    assert_eq!(vm.printed, vec!["42"]);
+    ```
+
+    Values, and what is printed, are the same as the [Interpreter](crate::eval::Interpreter),
+    and runtime errors are shown at the Token of the Element which caused them in the same way.
+*/
+pub mod bytecode;
+
+pub use bytecode::{compile, Instruction, Operator, Program, Slot};
+
+use crate::errors;
+use crate::eval::{apply_inbuilt_function, Value};
+
+/// A function call which is running
+struct Frame {
+    function_index: usize,
+    /// the next Instruction
+    ip: usize,
+    /// the position of its first slot on the stack
+    base: usize,
+}
+
+/// Holds the stack while running. The slots of the root are at the bottom,
+/// and each function call adds the slots for its arguments and constants above them
+pub struct Vm {
+    pub stack: Vec<Value>,
+    frames: Vec<Frame>,
+    /// print immediately, otherwise only collect what is printed in `printed`
+    pub echo: bool,
+    pub printed: Vec<String>,
+}
+
+impl Vm {
+    pub fn new(echo: bool) -> Vm {
+        Vm {
+            stack: vec![],
+            frames: vec![],
+            echo,
+            printed: vec![],
+        }
+    }
+
+    /// Runs the whole program, i.e. function 0, returning the value of its last line
+    pub fn run(&mut self, program: &Program) -> Result<Value, String> {
+        self.stack.clear();
+        self.frames.clear();
+        self.printed.clear();
+        self.call(program, 0, 0)?;
+        let result = self.execute(program);
+        if result.is_err() {
+            self.frames.clear();
+        }
+        result
+    }
+
+    /// Runs Instructions until the function called last returns
+    fn execute(&mut self, program: &Program) -> Result<Value, String> {
+        let depth = self.frames.len();
+        loop {
+            let frame = self.frames.last_mut().expect("a function is running");
+            let function = &program.functions[frame.function_index];
+            let instruction = &function.code[frame.ip];
+            let base = frame.base;
+            frame.ip += 1;
+            match instruction {
+                Instruction::Constant(constant_ref) => {
+                    self.stack.push(program.constants[*constant_ref].clone())
+                }
+                Instruction::Unit => self.stack.push(Value::Unit),
+                Instruction::Function(function_index) => {
+                    self.stack.push(Value::Function(*function_index))
+                }
+                Instruction::Load(Slot::Local(slot)) => {
+                    self.stack.push(self.stack[base + slot].clone())
+                }
+                Instruction::Load(Slot::Global(slot)) => self.stack.push(self.stack[*slot].clone()),
+                Instruction::Store(slot) => {
+                    let value = self.pop();
+                    self.stack[base + slot] = value;
+                }
+                Instruction::SetKey(slot, name_ref) => {
+                    let value = self.pop();
+                    let position = match slot {
+                        Slot::Local(slot) => base + slot,
+                        Slot::Global(slot) => *slot,
+                    };
+                    let key = &program.names[*name_ref];
+                    match &mut self.stack[position] {
+                        Value::Struct(_, keys) => match keys.iter_mut().find(|(k, _)| k == key) {
+                            Some((_, v)) => *v = value,
+                            None => return Err(self.get_error(program, "Struct has no such key")),
+                        },
+                        _ => return Err(self.get_error(program, "Not a struct")),
+                    }
+                }
+                Instruction::List(length) => {
+                    let items = self.stack.split_off(self.stack.len() - length);
+                    self.stack.push(Value::List(items));
+                }
+                Instruction::Struct(struct_ref) => {
+                    let (name, keys) = &program.structs[*struct_ref];
+                    let values = self.stack.split_off(self.stack.len() - keys.len());
+                    let keys = keys.iter().cloned().zip(values).collect();
+                    self.stack.push(Value::Struct(name.clone(), keys));
+                }
+                Instruction::Operator(operator) => {
+                    let args = self.stack.split_off(self.stack.len() - operator.arity());
+                    let value = self.apply_operator(program, *operator, args)?;
+                    self.stack.push(value);
+                }
+                Instruction::Call(function_index, arg_count) => {
+                    self.call(program, *function_index, *arg_count)?
+                }
+                Instruction::CallValue(arg_count) => {
+                    let position = self.stack.len() - arg_count - 1;
+                    match self.stack.remove(position) {
+                        Value::Function(function_index) => {
+                            self.call(program, function_index, *arg_count)?
+                        }
+                        _ => return Err(self.get_error(program, "Not a function")),
+                    }
+                }
+                Instruction::Jump(target) => self.jump(*target),
+                Instruction::JumpIfFalse(target) => match self.pop() {
+                    Value::Bool(true) => (),
+                    Value::Bool(false) => self.jump(*target),
+                    _ => return Err(self.get_error(program, "If condition is not a bool")),
+                },
+                Instruction::Println => {
+                    let text = self.pop().to_string();
+                    if self.echo {
+                        println!("{}", text);
+                    }
+                    self.printed.push(text);
+                    self.stack.push(Value::Unit);
+                }
+                Instruction::Pop => {
+                    self.pop();
+                }
+                Instruction::Return => {
+                    let result = self.pop();
+                    self.stack.truncate(base);
+                    self.frames.pop();
+                    if self.frames.len() < depth {
+                        return Ok(result);
+                    }
+                    self.stack.push(result);
+                }
+            }
+        }
+    }
+
+    /// Starts running a function, with its arguments already on the stack
+    fn call(
+        &mut self,
+        program: &Program,
+        function_index: usize,
+        arg_count: usize,
+    ) -> Result<(), String> {
+        let function = &program.functions[function_index];
+        if function.arity != arg_count {
+            return Err(self.get_error(program, "Wrong number of arguments for function"));
+        }
+        let base = self.stack.len() - arg_count;
+        self.stack.resize(base + function.slots, Value::Unit);
+        self.frames.push(Frame {
+            function_index,
+            ip: 0,
+            base,
+        });
+        Ok(())
+    }
+
+    /// Calls a function value from an Operator, e.g. List::map, and runs it to get its result
+    fn call_value(
+        &mut self,
+        program: &Program,
+        function: &Value,
+        args: Vec<Value>,
+    ) -> Result<Value, String> {
+        let function_index = match function {
+            Value::Function(function_index) => *function_index,
+            _ => return Err(self.get_error(program, "Not a function")),
+        };
+        let arg_count = args.len();
+        self.stack.extend(args);
+        self.call(program, function_index, arg_count)?;
+        self.execute(program)
+    }
+
+    fn apply_operator(
+        &mut self,
+        program: &Program,
+        operator: Operator,
+        args: Vec<Value>,
+    ) -> Result<Value, String> {
+        match (operator, args.as_slice()) {
+            (Operator::ListMap, [Value::List(list), function]) => {
+                let mut items = vec![];
+                for item in list {
+                    items.push(self.call_value(program, function, vec![item.clone()])?);
+                }
+                Ok(Value::List(items))
+            }
+            (Operator::ListMapIndex, [Value::List(list), function]) => {
+                let mut items = vec![];
+                for (index, item) in list.iter().enumerate() {
+                    let args = vec![Value::Int(index as i64), item.clone()];
+                    items.push(self.call_value(program, function, args)?);
+                }
+                Ok(Value::List(items))
+            }
+            _ => apply_inbuilt_function(operator.name(), &args)
+                .map_err(|msg| self.get_error(program, &msg)),
+        }
+    }
+
+    fn pop(&mut self) -> Value {
+        self.stack
+            .pop()
+            .expect("the bytecode pushes a value before popping it")
+    }
+
+    fn jump(&mut self, target: usize) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.ip = target;
+        }
+    }
+
+    /// Formats the error at the Token of the Instruction which is running
+    fn get_error(&self, program: &Program, msg: &str) -> String {
+        let token = self.frames.last().and_then(|frame| {
+            let function = &program.functions[frame.function_index];
+            let token_ref = function.tokens.get(frame.ip.saturating_sub(1))?;
+            program.tokens.get(*token_ref)
+        });
+        match token {
+            Some(token) if !token.0.is_empty() => {
+                let line = program.lines.get(token.1).cloned().unwrap_or_default();
+                errors::get_error_for_line(&program.filename, &line, token, msg)
+            }
+            _ => format!("----------\r\n{}", msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::eval_str;
+
+    fn printed(text: &str) -> Vec<String> {
+        let program: Program = text.parse().unwrap();
+        let mut vm = Vm::new(false);
+        vm.run(&program).unwrap();
+        vm.printed
+    }
+
+    #[test]
+    fn test_run() {
+        let test_case_passes = vec![
+            ("= a \\ i64 => 123\r\n@ a", vec!["123"]),
+            ("@ + 1 2", vec!["3"]),
+            ("= a 1.5\r\n@ * a 2.0", vec!["3"]),
+            ("@ \"hello\"", vec!["hello"]),
+            ("= a 0.0\r\n@ / 1.0 -0.0", vec!["-inf"]),
+            ("= a ? == 1 2 \"yes\" \"no\"\r\n@ a", vec!["no"]),
+            (
+                "= a \\ i64 i64 i64 arg1 arg2 =>\r\n= b + arg1 123\r\n- b arg2\r\n@ a 1 2",
+                vec!["122"],
+            ),
+            (".. b 0 3\r\n@ b\r\n.", vec!["0", "1", "2"]),
+            (
+                "= fibonacci \\ i64 i64 n => ? < n 2 1 + fibonacci - n 1 fibonacci - n 2\r\n@ fibonacci 10",
+                vec!["89"],
+            ),
+            (
+                "= list [ 1 2 3 ]\r\n= mapfn \\ i64 i64 i => * i 100\r\n= mapped List::map list ( mapfn )\r\n@ mapped",
+                vec!["[100, 200, 300]"],
+            ),
+            (
+                "= newstruct { = firstname \"firstname\" = age 21 }\r\n= newstruct.age 99\r\n@ newstruct",
+                vec!["Newstruct { firstname: \"firstname\", age: 99 }"],
+            ),
+        ];
+        for (input, expected) in test_case_passes {
+            assert_eq!(printed(input), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_run_same_as_eval() {
+        for (name, input, _) in toylang_common::IntegrationTests::new().tests {
+            if let Ok(expected) = eval_str(&input, false) {
+                assert_eq!(printed(&input), expected, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_again() {
+        let program: Program = "= a 0\r\n.. b 0 2\r\n@ + a b\r\n.".parse().unwrap();
+        let mut vm = Vm::new(false);
+        vm.run(&program).unwrap();
+        vm.run(&program).unwrap();
+        assert_eq!(vm.printed, vec!["0", "1"]);
+    }
+
+    #[test]
+    fn test_run_runtime_error() {
+        let program: Program = "= a 0\r\n@ / 1 a".parse().unwrap();
+        let error = Vm::new(false).run(&program).unwrap_err();
+        assert_eq!(
+            error,
+            "----------\r\n./src/:2:0\r\n@ / 1 a\r\n  ^ attempt to divide by zero"
+        );
+    }
+}
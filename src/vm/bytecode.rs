@@ -0,0 +1,638 @@
+/*! Compiles the AST into a [Program] of bytecode for the [Vm](super::Vm)
+
+    Each function, and the root as function 0, is compiled to a list of [Instruction]s for a stack machine,
+    where every Element pushes exactly one value, like the values returned by the [Interpreter](crate::eval::Interpreter).
+    Constants and args are resolved to numbered slots when compiling, so no names are looked up when running.
+
+    - literals are stored once in the constants pool
+    - Ifs and loops are compiled to jumps within the function
+    - FunctionDefs are compiled after the root, so they can refer to any constant of the root, like the Interpreter
+*/
+use crate::ast::elements::{ElIndex, ElementInfo};
+use crate::ast::output::get_value_children;
+use crate::diagnostic::SourceLine;
+use crate::errors;
+use crate::eval::Value;
+use crate::{parse, Compiler, Token};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Where a constant is kept, i.e. in the current function, or in the root which every function can use
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Slot {
+    Local(usize),
+    Global(usize),
+}
+
+/// The inbuilt functions, which pop their arguments and push their result
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    ListLen,
+    ListReverse,
+    ListAppend,
+    ListMap,
+    ListMapIndex,
+}
+
+/// Toylang's name of each Operator, and how many arguments it pops, in the same order as Operator
+const OPERATORS: [(Operator, &str, usize); 16] = [
+    (Operator::Add, "+", 2),
+    (Operator::Subtract, "-", 2),
+    (Operator::Multiply, "*", 2),
+    (Operator::Divide, "/", 2),
+    (Operator::Remainder, "%", 2),
+    (Operator::Equal, "==", 2),
+    (Operator::NotEqual, "!=", 2),
+    (Operator::Less, "<", 2),
+    (Operator::Greater, ">", 2),
+    (Operator::LessEqual, "<=", 2),
+    (Operator::GreaterEqual, ">=", 2),
+    (Operator::ListLen, "List::len", 1),
+    (Operator::ListReverse, "List::reverse", 1),
+    (Operator::ListAppend, "List::append", 2),
+    (Operator::ListMap, "List::map", 2),
+    (Operator::ListMapIndex, "List::mapindex", 2),
+];
+
+impl Operator {
+    fn from_name(name: &str) -> Option<Operator> {
+        OPERATORS
+            .iter()
+            .find(|(_, operator_name, _)| *operator_name == name)
+            .map(|(operator, _, _)| *operator)
+    }
+
+    pub fn name(self) -> &'static str {
+        self.get().1
+    }
+
+    pub fn arity(self) -> usize {
+        self.get().2
+    }
+
+    fn get(self) -> (Operator, &'static str, usize) {
+        OPERATORS[self as usize]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Instruction {
+    /// push a value from the constants pool
+    Constant(usize),
+    /// push Unit, the value of statements like assignments
+    Unit,
+    /// push a reference to a function, e.g. to pass it as an argument
+    Function(usize),
+    Load(Slot),
+    /// pop into a slot of the current function
+    Store(usize),
+    /// pop a value into a key of the struct in a slot, named by the names pool
+    SetKey(Slot, usize),
+    /// pop this many items into a list
+    List(usize),
+    /// pop the values of the keys of a struct type, from the structs pool
+    Struct(usize),
+    Operator(Operator),
+    /// call a function with this many arguments, which are its first slots
+    Call(usize, usize),
+    /// call the function value below this many arguments
+    CallValue(usize),
+    Jump(usize),
+    /// pop a bool, and jump if it is false
+    JumpIfFalse(usize),
+    Println,
+    Pop,
+    /// pop the result, and return to the caller
+    Return,
+}
+
+/// A compiled function. The args are its first slots, then its constants
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub arity: usize,
+    pub slots: usize,
+    pub code: Vec<Instruction>,
+    /// the Token each Instruction came from, in the tokens pool, for runtime errors
+    pub tokens: Vec<usize>,
+}
+
+/// A compiled toylang program, which can be run any number of times by a [Vm](super::Vm)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Program {
+    /// function 0 is the root of the program
+    pub functions: Vec<Function>,
+    pub constants: Vec<Value>,
+    pub names: Vec<String>,
+    /// the name and keys of each struct type
+    pub structs: Vec<(String, Vec<String>)>,
+    pub tokens: Vec<Token>,
+    /// the source, to show where runtime errors happen
+    pub filename: String,
+    pub lines: Vec<SourceLine>,
+}
+
+/// Compiles the text, returning any compile errors
+impl FromStr for Program {
+    type Err = Vec<String>;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut compiler = Compiler::default();
+        compiler.file.filecontents = text.to_string();
+        let _ = compiler.run_main_tasks();
+        if !compiler.error_stack.is_empty() {
+            return Err(compiler.get_rendered_errors());
+        }
+        compile(&compiler).map_err(|error| vec![error])
+    }
+}
+
+/// The names in the slots of the function being compiled
+struct Scope {
+    function_index: usize,
+    slots: Vec<String>,
+}
+
+struct Bytecode<'a> {
+    compiler: &'a Compiler,
+    program: Program,
+    /// the Element of each function, which are compiled once the root is
+    function_refs: Vec<ElIndex>,
+    root_slots: Vec<String>,
+    /// the position of each Element's Token in the tokens pool
+    token_refs: HashMap<ElIndex, usize>,
+}
+
+/// Compiles the AST of a Compiler which has already run, i.e. without errors
+pub fn compile(compiler: &Compiler) -> Result<Program, String> {
+    let mut function_refs = vec![0];
+    let mut functions = vec![Function {
+        name: "main".to_string(),
+        ..Function::default()
+    }];
+    for (el_index, (el_info, _)) in compiler.ast.elements.iter().enumerate() {
+        if let ElementInfo::FunctionDef(name, argnames, _, _) = el_info {
+            function_refs.push(el_index);
+            functions.push(Function {
+                name: name.clone(),
+                arity: argnames.len(),
+                ..Function::default()
+            });
+        }
+    }
+    let mut bytecode = Bytecode {
+        compiler,
+        program: Program {
+            functions,
+            filename: compiler.file.filename.clone(),
+            lines: compiler.get_source_lines(),
+            ..Program::default()
+        },
+        function_refs,
+        root_slots: vec![],
+        token_refs: HashMap::new(),
+    };
+    for function_index in 0..bytecode.function_refs.len() {
+        bytecode.compile_function(function_index)?;
+    }
+    Ok(bytecode.program)
+}
+
+impl Bytecode<'_> {
+    fn compile_function(&mut self, function_index: usize) -> Result<(), String> {
+        let el_index = self.function_refs[function_index];
+        let (el_info, children) = &self.compiler.ast.elements[el_index];
+        let slots = match el_info {
+            ElementInfo::FunctionDef(_, argnames, _, _) => argnames.clone(),
+            _ => vec![],
+        };
+        let mut scope = Scope {
+            function_index,
+            slots,
+        };
+        self.compile_block(&mut scope, children, el_index)?;
+        self.emit(&scope, el_index, Instruction::Return);
+        let function = &mut self.program.functions[function_index];
+        function.slots = scope.slots.len();
+        if function_index == 0 {
+            self.root_slots = scope.slots;
+        }
+        Ok(())
+    }
+
+    /// Pushes the value of the last line, or Unit if there are none
+    fn compile_block(
+        &mut self,
+        scope: &mut Scope,
+        children: &[ElIndex],
+        el_index: ElIndex,
+    ) -> Result<(), String> {
+        let lines = get_value_children(self.compiler, children);
+        if lines.is_empty() {
+            self.emit(scope, el_index, Instruction::Unit);
+        }
+        for (i, &line_ref) in lines.iter().enumerate() {
+            let is_last = i == lines.len() - 1;
+            // definitions only have a value if they are the result
+            let is_definition = matches!(
+                self.compiler.ast.elements[line_ref].0,
+                ElementInfo::FunctionDef(_, _, _, _) | ElementInfo::Unused
+            );
+            if is_definition && !is_last {
+                continue;
+            }
+            self.compile_element(scope, line_ref)?;
+            if !is_last {
+                self.emit(scope, line_ref, Instruction::Pop);
+            }
+        }
+        Ok(())
+    }
+
+    /// Pushes the value of one Element
+    fn compile_element(&mut self, scope: &mut Scope, el_index: ElIndex) -> Result<(), String> {
+        let (el_info, all_children) = &self.compiler.ast.elements[el_index];
+        let children = get_value_children(self.compiler, all_children);
+        match el_info {
+            ElementInfo::Root => self.compile_block(scope, all_children, el_index)?,
+            ElementInfo::List(_) => {
+                for &child_ref in &children {
+                    self.compile_element(scope, child_ref)?;
+                }
+                self.emit(scope, el_index, Instruction::List(children.len()));
+            }
+            // ints used as return expressions have "as i64" appended for the Rust output
+            ElementInfo::Int(value) => match value.trim_end_matches(" as i64").parse::<i64>() {
+                Ok(i) => self.emit_constant(scope, el_index, Value::Int(i)),
+                Err(_) => return Err(self.get_error(el_index, "Not a valid i64")),
+            },
+            ElementInfo::Float(value) => match value.parse::<f64>() {
+                Ok(float) => self.emit_constant(scope, el_index, Value::Float(float)),
+                Err(_) => return Err(self.get_error(el_index, "Not a valid f64")),
+            },
+            ElementInfo::String(value) => {
+                let value = Value::String(parse::get_string_value(value).unwrap_or_default());
+                self.emit_constant(scope, el_index, value)
+            }
+            ElementInfo::Bool(value) => {
+                self.emit_constant(scope, el_index, Value::Bool(value == "true"))
+            }
+            ElementInfo::Arg(name, _, _, _) | ElementInfo::ConstantRef(name, _, _) => {
+                let instruction = self.get_load(scope, el_index, name)?;
+                self.emit(scope, el_index, instruction);
+            }
+            ElementInfo::Rust(_, _) => {
+                return Err(self.get_error(
+                    el_index,
+                    "Rust code can't be compiled to bytecode, try `toylang run` instead",
+                ))
+            }
+            ElementInfo::Struct(name, _, _) => {
+                let mut keys = vec![];
+                for &child_ref in &children {
+                    keys.push(self.compile_struct_key(scope, child_ref)?);
+                }
+                let struct_ref = self.get_struct(name, keys);
+                self.emit(scope, el_index, Instruction::Struct(struct_ref));
+            }
+            ElementInfo::StructEdit(name, _) => {
+                self.compile_first_child(scope, el_index, &children)?;
+                let (constant, key) = name.split_once('.').unwrap_or((name, ""));
+                let slot = match self.get_slot(scope, constant) {
+                    Some(slot) => slot,
+                    None => {
+                        let msg = format!("'{}' is not a struct", constant);
+                        return Err(self.get_error(el_index, &msg));
+                    }
+                };
+                let name_ref = self.get_name(key);
+                self.emit(scope, el_index, Instruction::SetKey(slot, name_ref));
+                self.emit(scope, el_index, Instruction::Unit);
+            }
+            ElementInfo::Constant(name, _) => {
+                self.compile_first_child(scope, el_index, &children)?;
+                let slot = match scope.slots.iter().position(|slot| slot == name) {
+                    Some(slot) => slot,
+                    None => {
+                        scope.slots.push(name.clone());
+                        scope.slots.len() - 1
+                    }
+                };
+                self.emit(scope, el_index, Instruction::Store(slot));
+                self.emit(scope, el_index, Instruction::Unit);
+            }
+            ElementInfo::Assignment => self.compile_first_child(scope, el_index, &children)?,
+            ElementInfo::InbuiltFunctionCall(name, _, _) => {
+                self.compile_inbuilt_function_call(scope, el_index, name, &children)?
+            }
+            ElementInfo::FunctionCall(name, skip_args, _) => {
+                let function = self.get_function(scope, el_index, name)?;
+                if *skip_args {
+                    self.emit(scope, el_index, function);
+                    return Ok(());
+                }
+                if let Instruction::Function(function_index) = function {
+                    for &child_ref in &children {
+                        self.compile_element(scope, child_ref)?;
+                    }
+                    let call = Instruction::Call(function_index, children.len());
+                    self.emit(scope, el_index, call);
+                } else {
+                    self.emit(scope, el_index, function);
+                    for &child_ref in &children {
+                        self.compile_element(scope, child_ref)?;
+                    }
+                    let call = Instruction::CallValue(children.len());
+                    self.emit(scope, el_index, call);
+                }
+            }
+            ElementInfo::Parens => {
+                if children.len() == 1 {
+                    self.compile_element(scope, children[0])?;
+                } else {
+                    self.emit(scope, el_index, Instruction::Unit);
+                }
+            }
+            ElementInfo::LoopForRange(name, from, to) => {
+                self.compile_loop_for_range(scope, el_index, name, *from, *to, all_children)?
+            }
+            ElementInfo::Println => {
+                self.compile_first_child(scope, el_index, &children)?;
+                self.emit(scope, el_index, Instruction::Println);
+            }
+            ElementInfo::If(_) => {
+                if children.len() != 3 {
+                    return Err(self.get_error(el_index, "If is missing arguments"));
+                }
+                self.compile_element(scope, children[0])?;
+                let jump_to_false = self.emit(scope, el_index, Instruction::JumpIfFalse(0));
+                self.compile_element(scope, children[1])?;
+                let jump_to_end = self.emit(scope, el_index, Instruction::Jump(0));
+                let false_start = self.get_code(scope).len();
+                self.get_code(scope)[jump_to_false] = Instruction::JumpIfFalse(false_start);
+                self.compile_element(scope, children[2])?;
+                let end = self.get_code(scope).len();
+                self.get_code(scope)[jump_to_end] = Instruction::Jump(end);
+            }
+            // definitions are compiled separately, and have no value where they are defined
+            ElementInfo::FunctionDef(_, _, _, _) => {
+                self.emit(scope, el_index, Instruction::Unit);
+            }
+            // explicitly listing other types rather than using _ to not overlook new types in future
+            ElementInfo::InbuiltFunctionDef(_, _, _, _, _, _)
+            | ElementInfo::CommentSingleLine(_)
+            | ElementInfo::Type(_)
+            | ElementInfo::Eol
+            | ElementInfo::Seol
+            | ElementInfo::Indent
+            | ElementInfo::Unused
+            | ElementInfo::FunctionDefWIP
+            | ElementInfo::LoopForRangeWIP => {
+                self.emit(scope, el_index, Instruction::Unit);
+            }
+        }
+        Ok(())
+    }
+
+    fn compile_first_child(
+        &mut self,
+        scope: &mut Scope,
+        el_index: ElIndex,
+        children: &[ElIndex],
+    ) -> Result<(), String> {
+        match children.first() {
+            Some(&child_ref) => self.compile_element(scope, child_ref),
+            None => Err(self.get_error(el_index, "Missing value")),
+        }
+    }
+
+    /// Pushes the value of a struct key, which is either Assignment > Constant > Value,
+    /// or a ConstantRef using a constant of the same name, and returns the name of the key
+    fn compile_struct_key(
+        &mut self,
+        scope: &mut Scope,
+        el_index: ElIndex,
+    ) -> Result<String, String> {
+        let (el_info, children) = &self.compiler.ast.elements[el_index];
+        let children = get_value_children(self.compiler, children);
+        match el_info {
+            ElementInfo::Assignment if !children.is_empty() => {
+                self.compile_struct_key(scope, children[0])
+            }
+            ElementInfo::Constant(name, _) => {
+                self.compile_first_child(scope, el_index, &children)?;
+                Ok(name.clone())
+            }
+            ElementInfo::ConstantRef(name, _, _) => {
+                let instruction = self.get_load(scope, el_index, name)?;
+                self.emit(scope, el_index, instruction);
+                Ok(name.clone())
+            }
+            _ => Err(self.get_error(el_index, "Not a valid struct key")),
+        }
+    }
+
+    fn compile_inbuilt_function_call(
+        &mut self,
+        scope: &mut Scope,
+        el_index: ElIndex,
+        name: &str,
+        children: &[ElIndex],
+    ) -> Result<(), String> {
+        if name == "true" || name == "false" {
+            self.emit_constant(scope, el_index, Value::Bool(name == "true"));
+            return Ok(());
+        }
+        let operator = match Operator::from_name(name) {
+            Some(operator) if operator.arity() == children.len() => operator,
+            _ => {
+                let msg = format!(
+                    "Can't compile inbuilt function '{}' with these arguments",
+                    name
+                );
+                return Err(self.get_error(el_index, &msg));
+            }
+        };
+        for &child_ref in children {
+            self.compile_element(scope, child_ref)?;
+        }
+        self.emit(scope, el_index, Instruction::Operator(operator));
+        Ok(())
+    }
+
+    /// The loop variable is a constant of the current function, which is compared with `to` each time
+    fn compile_loop_for_range(
+        &mut self,
+        scope: &mut Scope,
+        el_index: ElIndex,
+        name: &str,
+        from: usize,
+        to: usize,
+        children: &[ElIndex],
+    ) -> Result<(), String> {
+        let slot = match scope.slots.iter().position(|slot| slot == name) {
+            Some(slot) => slot,
+            None => {
+                scope.slots.push(name.to_string());
+                scope.slots.len() - 1
+            }
+        };
+        self.emit_constant(scope, el_index, Value::Int(from as i64));
+        self.emit(scope, el_index, Instruction::Store(slot));
+        let start = self.get_code(scope).len();
+        self.emit(scope, el_index, Instruction::Load(Slot::Local(slot)));
+        self.emit_constant(scope, el_index, Value::Int(to as i64));
+        self.emit(scope, el_index, Instruction::Operator(Operator::Less));
+        let jump_to_end = self.emit(scope, el_index, Instruction::JumpIfFalse(0));
+        self.compile_block(scope, children, el_index)?;
+        self.emit(scope, el_index, Instruction::Pop);
+        self.emit(scope, el_index, Instruction::Load(Slot::Local(slot)));
+        self.emit_constant(scope, el_index, Value::Int(1));
+        self.emit(scope, el_index, Instruction::Operator(Operator::Add));
+        self.emit(scope, el_index, Instruction::Store(slot));
+        self.emit(scope, el_index, Instruction::Jump(start));
+        let end = self.get_code(scope).len();
+        self.get_code(scope)[jump_to_end] = Instruction::JumpIfFalse(end);
+        self.emit(scope, el_index, Instruction::Unit);
+        Ok(())
+    }
+
+    /// Names are constants of the current function, then of the root, then functions
+    fn get_load(
+        &self,
+        scope: &Scope,
+        el_index: ElIndex,
+        name: &str,
+    ) -> Result<Instruction, String> {
+        if let Some(slot) = self.get_slot(scope, name) {
+            return Ok(Instruction::Load(slot));
+        }
+        match self.get_function_index(name) {
+            Some(function_index) => Ok(Instruction::Function(function_index)),
+            None => {
+                let msg = format!("Constant '{}' has no value", name);
+                Err(self.get_error(el_index, &msg))
+            }
+        }
+    }
+
+    /// Functions are either values of args or constants, e.g. when passed as arguments, or FunctionDefs
+    fn get_function(
+        &self,
+        scope: &Scope,
+        el_index: ElIndex,
+        name: &str,
+    ) -> Result<Instruction, String> {
+        if let Some(slot) = self.get_slot(scope, name) {
+            return Ok(Instruction::Load(slot));
+        }
+        match self.get_function_index(name) {
+            Some(function_index) => Ok(Instruction::Function(function_index)),
+            None => {
+                let msg = format!("Function '{}' is not defined", name);
+                Err(self.get_error(el_index, &msg))
+            }
+        }
+    }
+
+    fn get_slot(&self, scope: &Scope, name: &str) -> Option<Slot> {
+        if let Some(slot) = scope.slots.iter().position(|slot| slot == name) {
+            return Some(Slot::Local(slot));
+        }
+        self.root_slots
+            .iter()
+            .position(|slot| slot == name)
+            .map(Slot::Global)
+    }
+
+    fn get_function_index(&self, name: &str) -> Option<usize> {
+        self.program
+            .functions
+            .iter()
+            .skip(1)
+            .position(|function| function.name == name)
+            .map(|position| position + 1)
+    }
+
+    fn get_struct(&mut self, name: &str, keys: Vec<String>) -> usize {
+        let structs = &mut self.program.structs;
+        match structs.iter().position(|s| s.0 == name && s.1 == keys) {
+            Some(struct_ref) => struct_ref,
+            None => {
+                structs.push((name.to_string(), keys));
+                structs.len() - 1
+            }
+        }
+    }
+
+    fn get_name(&mut self, name: &str) -> usize {
+        let names = &mut self.program.names;
+        match names.iter().position(|existing| existing == name) {
+            Some(name_ref) => name_ref,
+            None => {
+                names.push(name.to_string());
+                names.len() - 1
+            }
+        }
+    }
+
+    fn emit_constant(&mut self, scope: &Scope, el_index: ElIndex, value: Value) {
+        let constants = &mut self.program.constants;
+        // floats by their bits, so e.g. -0.0 doesn't reuse 0.0
+        let is_same = |existing: &Value| match (existing, &value) {
+            (Value::Float(a), Value::Float(b)) => a.to_bits() == b.to_bits(),
+            _ => *existing == value,
+        };
+        let constant_ref = match constants.iter().position(is_same) {
+            Some(constant_ref) => constant_ref,
+            None => {
+                constants.push(value);
+                constants.len() - 1
+            }
+        };
+        self.emit(scope, el_index, Instruction::Constant(constant_ref));
+    }
+
+    /// Appends an Instruction to the current function, returning its position, e.g. to set the target of a jump later
+    fn emit(&mut self, scope: &Scope, el_index: ElIndex, instruction: Instruction) -> usize {
+        let token_ref = match self.token_refs.get(&el_index) {
+            Some(&token_ref) => token_ref,
+            None => {
+                let token = self.compiler.ast.element_tokens.get(el_index);
+                let tokens = &mut self.program.tokens;
+                tokens.push(token.cloned().unwrap_or_default());
+                self.token_refs.insert(el_index, tokens.len() - 1);
+                tokens.len() - 1
+            }
+        };
+        let function = &mut self.program.functions[scope.function_index];
+        function.code.push(instruction);
+        function.tokens.push(token_ref);
+        function.code.len() - 1
+    }
+
+    fn get_code(&mut self, scope: &Scope) -> &mut Vec<Instruction> {
+        &mut self.program.functions[scope.function_index].code
+    }
+
+    fn get_error(&self, el_index: ElIndex, msg: &str) -> String {
+        let token = self.compiler.ast.element_tokens.get(el_index);
+        match token {
+            Some(token) if !token.0.is_empty() => {
+                errors::get_error_for_token(self.compiler, token, msg)
+            }
+            _ => format!("----------\r\n{}", msg),
+        }
+    }
+}
@@ -37,6 +37,16 @@ impl IntegrationTests {
             // String
             ("test_pass_string", "\"string\"", "fn main() {\r\n    \"string\".to_string();\r\n}\r\n"),
             ("test_pass_string_escaped_quote", "\"\"", "fn main() {\r\n    \"\".to_string();\r\n}\r\n"),
+            (
+                "test_pass_string_escapes",
+                "@ \"say \\\"hi\\\"\\tback\\\\slash \\u{1F600}\"",
+                "fn main() {\r\n    println!(\"{}\", \"say \\\"hi\\\"\\tback\\\\slash 😀\".to_string());\r\n}\r\n",
+            ),
+            (
+                "test_pass_string_multi_line",
+                "= a \"\"\"line 1\r\nsay \"hi\" => twice\"\"\"\r\n@ a",
+                "fn main() {\r\n    let a: String = \"line 1\\r\\nsay \\\"hi\\\" => twice\".to_string();\r\n    println!(\"{}\", a.clone());\r\n}\r\n",
+            ),
             //
             // Int
             ("test_pass_int", "1", "fn main() {\r\n    1 as i64;\r\n}\r\n"),
@@ -96,7 +106,7 @@ impl IntegrationTests {
             (
                 "test_pass_list_map",
                 "= list [ 1 ]\r\n= mapfn \\ i64 i64 i => * i 100\r\n= mapped List::map list ( mapfn )",
-                "fn main() {\r\n    let list: Vec<i64> = vec![ 1 ];\r\n    fn mapfn(i: i64) -> i64 {\r\n        i.clone() * 100\r\n    }\r\n    fn mapfn_for_list_map(i: &i64) -> i64 {\r\n        i.clone() * 100\r\n    }\r\nlet mapped: Vec<i64> = list.clone().iter().map(mapfn_for_list_map.clone()).collect();\r\n}\r\n",
+                "fn main() {\r\n    let list: Vec<i64> = vec![ 1 ];\r\n    fn mapfn(i: i64) -> i64 {\r\n        i.clone() * 100\r\n    }\r\n    fn mapfn_for_list_map(i: &i64) -> i64 {\r\n        i.clone() * 100\r\n    }\r\n    let mapped: Vec<i64> = list.clone().iter().map(mapfn_for_list_map.clone()).collect();\r\n}\r\n",
             ),
             //
             // List reverse
@@ -153,6 +163,7 @@ impl IntegrationTests {
             ("test_pass_nested_internal_function_call3", "= a + 1 * 3 2", "fn main() {\r\n    let a: i64 = 1 + 3 * 2;\r\n}\r\n"),
             ("test_pass_func_def_singleline1", "= a \\ i64 => 123", "fn main() {\r\n    fn a() -> i64 {\r\n        123 as i64\r\n    }\r\n}\r\n"),
             ("test_pass_func_def_singleline2", "= a \\ i64 i64 arg1 => + 123 arg1", "fn main() {\r\n    fn a(arg1: i64) -> i64 {\r\n        123 + arg1.clone()\r\n    }\r\n}\r\n"),
+            ("test_pass_func_def_singleline_same_arg_names", "= a \\ i64 i64 arg1 => + 1 arg1\r\n= b \\ f64 f64 arg1 => * arg1 2.0", "fn main() {\r\n    fn a(arg1: i64) -> i64 {\r\n        1 + arg1.clone()\r\n    }\r\n    fn b(arg1: f64) -> f64 {\r\n        arg1.clone() * 2.0\r\n    }\r\n}\r\n"),
             ("test_pass_func_def_singleline_list", "= a \\ [ i64 ] => [ 1 2 3 ]", "fn main() {\r\n    fn a() -> Vec<i64> {\r\n        vec![ 1, 2, 3 ]\r\n    }\r\n}\r\n"),
             ("test_pass_func_def_singleline_nested_list", "= a \\ [ [ i64 ] ] => [ [ 1 2 3 ] [ 4 5 6 ] ]", "fn main() {\r\n    fn a() -> Vec<Vec<i64>> {\r\n        vec![ vec![ 1, 2, 3 ], vec![ 4, 5, 6 ] ]\r\n    }\r\n}\r\n"),
             ("test_pass_func_def_multiline1", "= a \\ i64 i64 i64 arg1 arg2 =>\r\n+ arg1 arg2", "fn main() {\r\n    fn a(arg1: i64, arg2: i64) -> i64 {\r\n        arg1.clone() + arg2.clone()\r\n    }\r\n}\r\n"),
@@ -333,6 +344,13 @@ impl IntegrationTests {
                 "= fibonacci \\ i64 i64 n => ? < n 2 1 + fibonacci - n 1 fibonacci - n 2\r\n@ fibonacci 10",
                 "fn main() {\r\n    fn fibonacci(n: i64) -> i64 {\r\n        if n.clone() < 2 {\r\n            1\r\n        } else {\r\n            fibonacci(n.clone() - 1) + fibonacci(n.clone() - 2)\r\n        }\r\n    }\r\n    println!(\"{}\", fibonacci(10));\r\n}\r\n",
             ),
+            //
+            // Loops
+            (
+                "test_pass_loop_for_range_then_statement",
+                "= a 1\r\n.. i 0 3\r\n@ + a i\r\n.\r\n= b 2\r\n@ b",
+                "fn main() {\r\n    let a: i64 = 1;\r\n    for i in 0..3 {\r\n        println!(\"{}\", a.clone() + i.clone());\r\n    }\r\n    let b: i64 = 2;\r\n    println!(\"{}\", b.clone());\r\n}\r\n",
+            ),
             /* Loops - for loops - not required?
             ("for_loops",
                 "= a \\ i64 i64 arg1 => + 123 arg1\r\n.. b 0 100\r\na b\r\n.",